@@ -3,19 +3,38 @@ CREATE DATABASE `mestockydb`;
 CREATE TABLE `tb_category` (
   `id` bigint unsigned NOT NULL AUTO_INCREMENT,
   `name` varchar(100) NOT NULL,
+  `version` bigint unsigned NOT NULL DEFAULT 1,
+  `created_at` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  `updated_at` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  `created_by` bigint unsigned DEFAULT NULL,
+  `updated_by` bigint unsigned DEFAULT NULL,
+  `deleted_at` datetime DEFAULT NULL,
   PRIMARY KEY (`id`),
-  UNIQUE KEY `tb_category_unique` (`name`)
+  UNIQUE KEY `tb_category_unique` (`name`),
+  KEY `tb_category_created_at_IDX` (`created_at`),
+  KEY `tb_category_updated_at_IDX` (`updated_at`)
 ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
 
 CREATE TABLE `tb_product` (
   `id` bigint unsigned NOT NULL AUTO_INCREMENT,
   `name` varchar(100) NOT NULL,
+  `barcode` varchar(100) DEFAULT NULL,
   `quantity` bigint unsigned NOT NULL,
   `min_quantity` bigint unsigned NOT NULL,
   `category_id` bigint unsigned NOT NULL,
   `description` varchar(5000) DEFAULT NULL,
+  `abc_class` char(1) DEFAULT NULL,
+  `version` bigint unsigned NOT NULL DEFAULT 1,
+  `created_at` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  `updated_at` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  `created_by` bigint unsigned DEFAULT NULL,
+  `updated_by` bigint unsigned DEFAULT NULL,
+  `deleted_at` datetime DEFAULT NULL,
   PRIMARY KEY (`id`),
   UNIQUE KEY `tb_product_unique` (`name`),
+  UNIQUE KEY `tb_product_unique_barcode` (`barcode`),
+  KEY `tb_product_created_at_IDX` (`created_at`),
+  KEY `tb_product_updated_at_IDX` (`updated_at`),
   KEY `tb_product_tb_category_FK` (`category_id`),
   CONSTRAINT `tb_product_tb_category_FK` FOREIGN KEY (`category_id`) REFERENCES `tb_category` (`id`) ON DELETE RESTRICT ON UPDATE RESTRICT
 ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
@@ -23,8 +42,128 @@ CREATE TABLE `tb_product` (
 CREATE TABLE `tb_reason` (
   `id` bigint unsigned NOT NULL AUTO_INCREMENT,
   `name` varchar(100) NOT NULL,
+  `created_at` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  `updated_at` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  `created_by` bigint unsigned DEFAULT NULL,
+  `updated_by` bigint unsigned DEFAULT NULL,
+  `deleted_at` datetime DEFAULT NULL,
   PRIMARY KEY (`id`),
-  UNIQUE KEY `tb_return_reason_unique_name` (`name`)
+  UNIQUE KEY `tb_return_reason_unique_name` (`name`),
+  KEY `tb_reason_created_at_IDX` (`created_at`),
+  KEY `tb_reason_updated_at_IDX` (`updated_at`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+CREATE TABLE `tb_role` (
+  `id` tinyint unsigned NOT NULL AUTO_INCREMENT,
+  `name` varchar(100) NOT NULL,
+  PRIMARY KEY (`id`),
+  UNIQUE KEY `tb_role_unique_name` (`name`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+CREATE TABLE `tb_permission` (
+  `id` bigint unsigned NOT NULL AUTO_INCREMENT,
+  `name` varchar(100) NOT NULL,
+  PRIMARY KEY (`id`),
+  UNIQUE KEY `tb_permission_unique_name` (`name`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+CREATE TABLE `tb_role_permission` (
+  `role_id` tinyint unsigned NOT NULL,
+  `permission_id` bigint unsigned NOT NULL,
+  PRIMARY KEY (`role_id`,`permission_id`),
+  KEY `tb_role_permission_tb_permission_FK` (`permission_id`),
+  CONSTRAINT `tb_role_permission_tb_permission_FK` FOREIGN KEY (`permission_id`) REFERENCES `tb_permission` (`id`) ON DELETE CASCADE ON UPDATE RESTRICT,
+  CONSTRAINT `tb_role_permission_tb_role_FK` FOREIGN KEY (`role_id`) REFERENCES `tb_role` (`id`) ON DELETE CASCADE ON UPDATE RESTRICT
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+CREATE TABLE `tb_user` (
+  `id` bigint unsigned NOT NULL AUTO_INCREMENT,
+  `username` varchar(100) NOT NULL,
+  `password` varchar(255) NOT NULL,
+  `email` varchar(100) NOT NULL,
+  `role` tinyint unsigned NOT NULL,
+  `phone` char(15) DEFAULT NULL,
+  `active` tinyint(1) NOT NULL DEFAULT '1',
+  `failed_attempts` int unsigned NOT NULL DEFAULT '0',
+  `locked_until` datetime DEFAULT NULL,
+  `totp_secret` varchar(64) DEFAULT NULL,
+  `totp_enabled` tinyint(1) NOT NULL DEFAULT '0',
+  `totp_last_step` bigint unsigned DEFAULT NULL,
+  `created_at` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  `updated_at` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  `created_by` bigint unsigned DEFAULT NULL,
+  `updated_by` bigint unsigned DEFAULT NULL,
+  PRIMARY KEY (`id`),
+  UNIQUE KEY `tb_user_unique_email` (`email`),
+  KEY `tb_user_tb_role_FK` (`role`),
+  KEY `tb_user_created_at_IDX` (`created_at`),
+  KEY `tb_user_updated_at_IDX` (`updated_at`),
+  CONSTRAINT `tb_user_tb_role_FK` FOREIGN KEY (`role`) REFERENCES `tb_role` (`id`) ON DELETE RESTRICT ON UPDATE RESTRICT,
+  CONSTRAINT `tb_user_tb_user_created_FK` FOREIGN KEY (`created_by`) REFERENCES `tb_user` (`id`) ON DELETE SET NULL ON UPDATE RESTRICT,
+  CONSTRAINT `tb_user_tb_user_updated_FK` FOREIGN KEY (`updated_by`) REFERENCES `tb_user` (`id`) ON DELETE SET NULL ON UPDATE RESTRICT
+) ENGINE=InnoDB AUTO_INCREMENT=2 DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+ALTER TABLE `tb_category`
+  ADD CONSTRAINT `tb_category_tb_user_created_FK` FOREIGN KEY (`created_by`) REFERENCES `tb_user` (`id`) ON DELETE SET NULL ON UPDATE RESTRICT,
+  ADD CONSTRAINT `tb_category_tb_user_updated_FK` FOREIGN KEY (`updated_by`) REFERENCES `tb_user` (`id`) ON DELETE SET NULL ON UPDATE RESTRICT;
+
+ALTER TABLE `tb_product`
+  ADD CONSTRAINT `tb_product_tb_user_created_FK` FOREIGN KEY (`created_by`) REFERENCES `tb_user` (`id`) ON DELETE SET NULL ON UPDATE RESTRICT,
+  ADD CONSTRAINT `tb_product_tb_user_updated_FK` FOREIGN KEY (`updated_by`) REFERENCES `tb_user` (`id`) ON DELETE SET NULL ON UPDATE RESTRICT;
+
+ALTER TABLE `tb_reason`
+  ADD CONSTRAINT `tb_reason_tb_user_created_FK` FOREIGN KEY (`created_by`) REFERENCES `tb_user` (`id`) ON DELETE SET NULL ON UPDATE RESTRICT,
+  ADD CONSTRAINT `tb_reason_tb_user_updated_FK` FOREIGN KEY (`updated_by`) REFERENCES `tb_user` (`id`) ON DELETE SET NULL ON UPDATE RESTRICT;
+
+CREATE TABLE `tb_login_attempt` (
+  `ip` varchar(45) NOT NULL,
+  `failed_attempts` int unsigned NOT NULL DEFAULT '0',
+  `locked_until` datetime DEFAULT NULL,
+  `last_attempt_at` datetime NOT NULL,
+  PRIMARY KEY (`ip`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+CREATE TABLE `tb_user_permission` (
+  `user_id` bigint unsigned NOT NULL,
+  `permission_id` bigint unsigned NOT NULL,
+  PRIMARY KEY (`user_id`,`permission_id`),
+  KEY `tb_user_permission_tb_permission_FK` (`permission_id`),
+  CONSTRAINT `tb_user_permission_tb_permission_FK` FOREIGN KEY (`permission_id`) REFERENCES `tb_permission` (`id`) ON DELETE CASCADE ON UPDATE RESTRICT,
+  CONSTRAINT `tb_user_permission_tb_user_FK` FOREIGN KEY (`user_id`) REFERENCES `tb_user` (`id`) ON DELETE CASCADE ON UPDATE RESTRICT
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+CREATE TABLE `tb_user_recovery_code` (
+  `id` bigint unsigned NOT NULL AUTO_INCREMENT,
+  `user_id` bigint unsigned NOT NULL,
+  `code_hash` char(64) NOT NULL,
+  `used_at` datetime DEFAULT NULL,
+  PRIMARY KEY (`id`),
+  KEY `tb_user_recovery_code_tb_user_FK` (`user_id`),
+  CONSTRAINT `tb_user_recovery_code_tb_user_FK` FOREIGN KEY (`user_id`) REFERENCES `tb_user` (`id`) ON DELETE CASCADE ON UPDATE RESTRICT
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+CREATE TABLE `tb_api_key` (
+  `id` bigint unsigned NOT NULL AUTO_INCREMENT,
+  `name` varchar(100) NOT NULL,
+  `prefix` char(12) NOT NULL,
+  `key_hash` char(64) NOT NULL,
+  `created_by` bigint unsigned DEFAULT NULL,
+  `created_at` datetime NOT NULL,
+  `last_used_at` datetime DEFAULT NULL,
+  `revoked_at` datetime DEFAULT NULL,
+  PRIMARY KEY (`id`),
+  UNIQUE KEY `tb_api_key_unique_key_hash` (`key_hash`),
+  KEY `tb_api_key_tb_user_FK` (`created_by`),
+  CONSTRAINT `tb_api_key_tb_user_FK` FOREIGN KEY (`created_by`) REFERENCES `tb_user` (`id`) ON DELETE SET NULL ON UPDATE RESTRICT
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+CREATE TABLE `tb_api_key_permission` (
+  `api_key_id` bigint unsigned NOT NULL,
+  `permission_id` bigint unsigned NOT NULL,
+  PRIMARY KEY (`api_key_id`,`permission_id`),
+  KEY `tb_api_key_permission_tb_permission_FK` (`permission_id`),
+  CONSTRAINT `tb_api_key_permission_tb_api_key_FK` FOREIGN KEY (`api_key_id`) REFERENCES `tb_api_key` (`id`) ON DELETE CASCADE ON UPDATE RESTRICT,
+  CONSTRAINT `tb_api_key_permission_tb_permission_FK` FOREIGN KEY (`permission_id`) REFERENCES `tb_permission` (`id`) ON DELETE CASCADE ON UPDATE RESTRICT
 ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
 
 CREATE TABLE `tb_report` (
@@ -34,21 +173,146 @@ CREATE TABLE `tb_report` (
   `reason_id` bigint unsigned NOT NULL,
   `product_id` bigint unsigned NOT NULL,
   `change_type` tinyint(1) NOT NULL,
+  `user_id` bigint unsigned DEFAULT NULL,
+  `api_key_id` bigint unsigned DEFAULT NULL,
+  `reversal_of` bigint unsigned DEFAULT NULL,
+  `document` varchar(100) DEFAULT NULL,
   PRIMARY KEY (`id`),
+  UNIQUE KEY `tb_report_unique_reversal_of` (`reversal_of`),
   KEY `tb_report_tb_reason_FK` (`reason_id`),
   KEY `tb_report_tb_product_FK` (`product_id`),
-  CONSTRAINT `tb_report_tb_product_FK` FOREIGN KEY (`product_id`) REFERENCES `tb_product` (`id`) ON DELETE CASCADE ON UPDATE RESTRICT,
-  CONSTRAINT `tb_report_tb_reason_FK` FOREIGN KEY (`reason_id`) REFERENCES `tb_reason` (`id`)
+  KEY `tb_report_tb_user_FK` (`user_id`),
+  KEY `tb_report_tb_api_key_FK` (`api_key_id`),
+  CONSTRAINT `tb_report_tb_product_FK` FOREIGN KEY (`product_id`) REFERENCES `tb_product` (`id`) ON DELETE RESTRICT ON UPDATE RESTRICT,
+  CONSTRAINT `tb_report_tb_reason_FK` FOREIGN KEY (`reason_id`) REFERENCES `tb_reason` (`id`),
+  CONSTRAINT `tb_report_tb_user_FK` FOREIGN KEY (`user_id`) REFERENCES `tb_user` (`id`) ON DELETE SET NULL ON UPDATE RESTRICT,
+  CONSTRAINT `tb_report_tb_api_key_FK` FOREIGN KEY (`api_key_id`) REFERENCES `tb_api_key` (`id`) ON DELETE SET NULL ON UPDATE RESTRICT,
+  CONSTRAINT `tb_report_tb_report_FK` FOREIGN KEY (`reversal_of`) REFERENCES `tb_report` (`id`) ON DELETE RESTRICT ON UPDATE RESTRICT
 ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
 
-CREATE TABLE `tb_user` (
+CREATE TABLE `tb_audit` (
   `id` bigint unsigned NOT NULL AUTO_INCREMENT,
-  `username` varchar(100) NOT NULL,
-  `password` varchar(255) NOT NULL,
-  `email` varchar(100) NOT NULL,
-  `role` tinyint unsigned NOT NULL,
-  `phone` char(15) DEFAULT NULL,
+  `user_id` bigint unsigned DEFAULT NULL,
+  `api_key_id` bigint unsigned DEFAULT NULL,
+  `entity` varchar(50) NOT NULL,
+  `entity_id` bigint unsigned NOT NULL,
+  `action` varchar(50) NOT NULL,
+  `before_data` json DEFAULT NULL,
+  `after_data` json DEFAULT NULL,
+  `created_at` datetime NOT NULL,
   PRIMARY KEY (`id`),
-  UNIQUE KEY `tb_user_unique_email` (`email`),
-  KEY `tb_user_tb_role_FK` (`role`)
-) ENGINE=InnoDB AUTO_INCREMENT=2 DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
\ No newline at end of file
+  KEY `tb_audit_entity_IDX` (`entity`,`entity_id`),
+  KEY `tb_audit_created_at_IDX` (`created_at`),
+  KEY `tb_audit_tb_user_FK` (`user_id`),
+  KEY `tb_audit_tb_api_key_FK` (`api_key_id`),
+  CONSTRAINT `tb_audit_tb_user_FK` FOREIGN KEY (`user_id`) REFERENCES `tb_user` (`id`) ON DELETE SET NULL ON UPDATE RESTRICT,
+  CONSTRAINT `tb_audit_tb_api_key_FK` FOREIGN KEY (`api_key_id`) REFERENCES `tb_api_key` (`id`) ON DELETE SET NULL ON UPDATE RESTRICT
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+CREATE TABLE `tb_reservation` (
+  `id` bigint unsigned NOT NULL AUTO_INCREMENT,
+  `product_id` bigint unsigned NOT NULL,
+  `holder` varchar(100) NOT NULL,
+  `quantity` bigint unsigned NOT NULL,
+  `consumed_quantity` bigint unsigned NOT NULL DEFAULT '0',
+  `status` varchar(20) NOT NULL,
+  `expires_at` datetime NOT NULL,
+  `created_at` datetime NOT NULL,
+  `closed_at` datetime DEFAULT NULL,
+  `user_id` bigint unsigned DEFAULT NULL,
+  `api_key_id` bigint unsigned DEFAULT NULL,
+  PRIMARY KEY (`id`),
+  KEY `tb_reservation_status_IDX` (`status`,`expires_at`),
+  KEY `tb_reservation_tb_product_FK` (`product_id`),
+  KEY `tb_reservation_tb_user_FK` (`user_id`),
+  KEY `tb_reservation_tb_api_key_FK` (`api_key_id`),
+  CONSTRAINT `tb_reservation_tb_product_FK` FOREIGN KEY (`product_id`) REFERENCES `tb_product` (`id`) ON DELETE RESTRICT ON UPDATE RESTRICT,
+  CONSTRAINT `tb_reservation_tb_user_FK` FOREIGN KEY (`user_id`) REFERENCES `tb_user` (`id`) ON DELETE SET NULL ON UPDATE RESTRICT,
+  CONSTRAINT `tb_reservation_tb_api_key_FK` FOREIGN KEY (`api_key_id`) REFERENCES `tb_api_key` (`id`) ON DELETE SET NULL ON UPDATE RESTRICT
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+CREATE TABLE `tb_stocktake` (
+  `id` bigint unsigned NOT NULL AUTO_INCREMENT,
+  `category_id` bigint unsigned DEFAULT NULL,
+  `status` varchar(20) NOT NULL,
+  `created_at` datetime NOT NULL,
+  `closed_at` datetime DEFAULT NULL,
+  PRIMARY KEY (`id`),
+  KEY `tb_stocktake_status_IDX` (`status`),
+  KEY `tb_stocktake_tb_category_FK` (`category_id`),
+  CONSTRAINT `tb_stocktake_tb_category_FK` FOREIGN KEY (`category_id`) REFERENCES `tb_category` (`id`) ON DELETE RESTRICT ON UPDATE RESTRICT
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+CREATE TABLE `tb_stocktake_item` (
+  `stocktake_id` bigint unsigned NOT NULL,
+  `product_id` bigint unsigned NOT NULL,
+  `expected_quantity` bigint unsigned NOT NULL,
+  PRIMARY KEY (`stocktake_id`,`product_id`),
+  KEY `tb_stocktake_item_tb_product_FK` (`product_id`),
+  CONSTRAINT `tb_stocktake_item_tb_stocktake_FK` FOREIGN KEY (`stocktake_id`) REFERENCES `tb_stocktake` (`id`) ON DELETE CASCADE ON UPDATE RESTRICT,
+  CONSTRAINT `tb_stocktake_item_tb_product_FK` FOREIGN KEY (`product_id`) REFERENCES `tb_product` (`id`) ON DELETE RESTRICT ON UPDATE RESTRICT
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+CREATE TABLE `tb_stocktake_count` (
+  `stocktake_id` bigint unsigned NOT NULL,
+  `product_id` bigint unsigned NOT NULL,
+  `user_id` bigint unsigned NOT NULL,
+  `quantity` bigint unsigned NOT NULL,
+  `counted_at` datetime NOT NULL,
+  PRIMARY KEY (`stocktake_id`,`product_id`,`user_id`),
+  KEY `tb_stocktake_count_tb_user_FK` (`user_id`),
+  CONSTRAINT `tb_stocktake_count_tb_stocktake_item_FK` FOREIGN KEY (`stocktake_id`, `product_id`) REFERENCES `tb_stocktake_item` (`stocktake_id`, `product_id`) ON DELETE CASCADE ON UPDATE RESTRICT,
+  CONSTRAINT `tb_stocktake_count_tb_user_FK` FOREIGN KEY (`user_id`) REFERENCES `tb_user` (`id`) ON DELETE CASCADE ON UPDATE RESTRICT
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+INSERT INTO `tb_role` (`id`, `name`) VALUES
+  (1, 'Administrador'),
+  (2, 'Gerente'),
+  (3, 'Operador'),
+  (4, 'Visualizador');
+
+INSERT INTO `tb_permission` (`name`) VALUES
+  ('product.read'),
+  ('product.create'),
+  ('product.update'),
+  ('product.delete'),
+  ('product.import'),
+  ('product.classify'),
+  ('stock.move'),
+  ('category.read'),
+  ('category.manage'),
+  ('reason.read'),
+  ('reason.manage'),
+  ('report.read'),
+  ('report.reverse'),
+  ('user.manage'),
+  ('user.security'),
+  ('role.manage'),
+  ('apikey.manage'),
+  ('audit.read'),
+  ('stocktake.manage'),
+  ('stocktake.count'),
+  ('reservation.manage');
+
+INSERT INTO `tb_role_permission` (`role_id`, `permission_id`)
+  SELECT 1, `id` FROM `tb_permission`;
+
+INSERT INTO `tb_role_permission` (`role_id`, `permission_id`)
+  SELECT 2, `id` FROM `tb_permission`
+  WHERE `name` NOT IN ('user.security', 'role.manage', 'apikey.manage');
+
+INSERT INTO `tb_role_permission` (`role_id`, `permission_id`)
+  SELECT 3, `id` FROM `tb_permission`
+  WHERE `name` IN (
+    'product.read', 'product.create', 'product.update', 'product.delete', 'stock.move',
+    'category.read', 'reason.read', 'report.read', 'report.reverse', 'stocktake.count',
+    'reservation.manage'
+  );
+
+INSERT INTO `tb_role_permission` (`role_id`, `permission_id`)
+  SELECT 4, `id` FROM `tb_permission`
+  WHERE `name` IN ('product.read', 'report.read');
+
+INSERT INTO `tb_reason` (`name`) VALUES
+  ('Ajuste de inventário'),
+  ('Estoque inicial');
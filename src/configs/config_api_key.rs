@@ -0,0 +1,14 @@
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+pub fn generate_api_key() -> String {
+    let mut bytes = [0u8; 32];
+
+    rand::thread_rng().fill_bytes(&mut bytes);
+
+    format!("msk_{}", hex::encode(bytes))
+}
+
+pub fn hash_api_key(api_key: &str) -> String {
+    hex::encode(Sha256::digest(api_key.as_bytes()))
+}
@@ -1,3 +1,5 @@
+use std::sync::LazyLock;
+
 use crate::configs::config_environment::get_cost;
 
 pub fn encrypt_password(password: &str) -> String {
@@ -9,3 +11,9 @@ pub fn encrypt_password(password: &str) -> String {
 pub fn verify_password(password: &str, encrypted_password: &str) -> bool {
     bcrypt::verify(password, encrypted_password).unwrap()
 }
+
+static DUMMY_PASSWORD: LazyLock<String> = LazyLock::new(|| encrypt_password("mestocky"));
+
+pub fn verify_dummy_password(password: &str) -> bool {
+    verify_password(password, &DUMMY_PASSWORD)
+}
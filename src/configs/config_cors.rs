@@ -8,13 +8,23 @@ pub fn make_cors() -> rocket_cors::Cors {
             Method::Get,
             Method::Post,
             Method::Put,
+            Method::Patch,
             Method::Delete,
             Method::Options,
         ]
         .into_iter()
         .map(From::from)
         .collect(),
-        allowed_headers: AllowedHeaders::some(&["token", "Content-Type"]),
+        allowed_headers: AllowedHeaders::some(&[
+            "token",
+            "Authorization",
+            "Content-Type",
+            "If-Match",
+        ]),
+        expose_headers: ["Deprecation", "Link", "ETag"]
+            .into_iter()
+            .map(String::from)
+            .collect(),
         allow_credentials: true,
         ..Default::default()
     }
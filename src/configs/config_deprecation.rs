@@ -0,0 +1,42 @@
+use std::collections::HashSet;
+
+use rocket::{fairing::AdHoc, http::Header};
+
+use crate::routes::api_v1::{API_V1_BASE, make_v1_routes};
+
+// RFC 9745 date (2026-10-19) from which the bare paths are deprecated in favor of /api/v1
+const LEGACY_DEPRECATION_DATE: &str = "@1792368000";
+
+pub fn make_deprecation_fairing() -> AdHoc {
+    let legacy_routes: HashSet<String> = make_v1_routes()
+        .into_iter()
+        .filter_map(|route| route.name)
+        .map(|name| name.into_owned())
+        .collect();
+
+    AdHoc::on_response("Aviso de rotas depreciadas", move |request, response| {
+        let is_legacy = match request.route() {
+            Some(route) => {
+                route.uri.base() == "/"
+                    && route
+                        .name
+                        .as_ref()
+                        .is_some_and(|name| legacy_routes.contains(name.as_ref()))
+            }
+            None => false,
+        };
+
+        let successor = format!(
+            "<{}{}>; rel=\"successor-version\"",
+            API_V1_BASE,
+            request.uri().path()
+        );
+
+        Box::pin(async move {
+            if is_legacy {
+                response.set_header(Header::new("Deprecation", LEGACY_DEPRECATION_DATE));
+                response.set_header(Header::new("Link", successor));
+            }
+        })
+    })
+}
@@ -1,15 +1,13 @@
 use dotenv::Error;
 
-pub fn get_database_url() -> String {
-    let database_url = get_by_dotenv("DATABASE_URL");
+use crate::entities::enums::analytics_enums::ForecastMethod;
 
-    database_url
+pub fn get_database_url() -> String {
+    get_by_dotenv("DATABASE_URL")
 }
 
 pub fn get_jwt_secret() -> String {
-    let jwt_secret = get_by_dotenv("JWT_SECRET");
-
-    jwt_secret
+    get_by_dotenv("JWT_SECRET")
 }
 
 pub fn get_cost() -> u32 {
@@ -17,7 +15,7 @@ pub fn get_cost() -> u32 {
 
     match cost.parse::<u32>() {
         Ok(value) => {
-            if value < 4 || value > 32 {
+            if !(4..=32).contains(&value) {
                 panic!("O valor da variável da ENCRYPT_COST deve ser 4-32");
             }
 
@@ -27,6 +25,138 @@ pub fn get_cost() -> u32 {
     }
 }
 
+pub fn get_login_max_attempts() -> u32 {
+    get_number_by_dotenv_or("LOGIN_MAX_ATTEMPTS", 5)
+}
+
+pub fn get_login_lockout_seconds() -> u32 {
+    get_number_by_dotenv_or("LOGIN_LOCKOUT_SECONDS", 60)
+}
+
+pub fn get_login_max_lockout_seconds() -> u32 {
+    get_number_by_dotenv_or("LOGIN_MAX_LOCKOUT_SECONDS", 3600)
+}
+
+pub fn get_login_ip_max_attempts() -> u32 {
+    get_number_by_dotenv_or("LOGIN_IP_MAX_ATTEMPTS", 20)
+}
+
+pub fn get_login_ip_window_seconds() -> u32 {
+    get_number_by_dotenv_or("LOGIN_IP_WINDOW_SECONDS", 900)
+}
+
+pub fn get_trusted_ip_header() -> Option<String> {
+    let header = get_by_dotenv_or("TRUSTED_IP_HEADER", "");
+
+    match header.trim().is_empty() {
+        true => None,
+        false => Some(header.trim().to_string()),
+    }
+}
+
+pub fn get_soft_delete_retention_days() -> u32 {
+    get_number_by_dotenv_or("SOFT_DELETE_RETENTION_DAYS", 30)
+}
+
+pub fn get_purge_interval_seconds() -> u32 {
+    get_number_by_dotenv_or("PURGE_INTERVAL_SECONDS", 3600)
+}
+
+pub fn get_reservation_release_interval_seconds() -> u32 {
+    get_number_by_dotenv_or("RESERVATION_RELEASE_INTERVAL_SECONDS", 60)
+}
+
+pub fn get_stocktake_reason() -> String {
+    get_by_dotenv_or("STOCKTAKE_REASON", "Ajuste de inventário")
+}
+
+pub fn get_import_reason() -> String {
+    get_by_dotenv_or("IMPORT_REASON", "Estoque inicial")
+}
+
+pub fn get_forecast_method() -> ForecastMethod {
+    let method = get_by_dotenv_or("FORECAST_METHOD", "moving_average");
+
+    match ForecastMethod::parse(&method) {
+        Some(method) => method,
+        None => panic!(
+            "O valor da variável da FORECAST_METHOD deve ser moving_average ou exponential_smoothing"
+        ),
+    }
+}
+
+pub fn get_forecast_window_days() -> u32 {
+    get_number_by_dotenv_or("FORECAST_WINDOW_DAYS", 30)
+}
+
+pub fn get_forecast_smoothing_alpha() -> f64 {
+    let alpha = get_number_by_dotenv_or("FORECAST_SMOOTHING_ALPHA", 30);
+
+    if !(1..=100).contains(&alpha) {
+        panic!("O valor da variável da FORECAST_SMOOTHING_ALPHA deve ser 1-100");
+    }
+
+    alpha as f64 / 100.0
+}
+
+pub fn get_forecast_lead_time_days() -> u32 {
+    get_number_by_dotenv_or("FORECAST_LEAD_TIME_DAYS", 7)
+}
+
+pub fn get_abc_window_days() -> u32 {
+    get_number_by_dotenv_or("ABC_WINDOW_DAYS", 90)
+}
+
+pub fn get_abc_class_percents() -> (u32, u32) {
+    let class_a = get_number_by_dotenv_or("ABC_CLASS_A_PERCENT", 80);
+    let class_b = get_number_by_dotenv_or("ABC_CLASS_B_PERCENT", 95);
+
+    if class_a == 0 || class_a >= class_b || class_b > 100 {
+        panic!(
+            "Os valores das variáveis ABC_CLASS_A_PERCENT e ABC_CLASS_B_PERCENT devem ser crescentes e estar entre 1-100"
+        );
+    }
+
+    (class_a, class_b)
+}
+
+pub fn get_totp_issuer() -> String {
+    get_by_dotenv_or("TOTP_ISSUER", "Mestocky")
+}
+
+pub fn get_totp_required_roles() -> Vec<u8> {
+    get_by_dotenv_or("TOTP_REQUIRED_ROLES", "")
+        .split(',')
+        .map(str::trim)
+        .filter(|role| !role.is_empty())
+        .map(|role| match role.parse::<u8>() {
+            Ok(role) => role,
+            Err(_) => {
+                panic!("O valor da variável da TOTP_REQUIRED_ROLES deve ser uma lista de números")
+            }
+        })
+        .collect()
+}
+
+fn get_number_by_dotenv_or(value: &str, default: u32) -> u32 {
+    match dotenv::var(value) {
+        Ok(number) => match number.parse::<u32>() {
+            Ok(number) => number,
+            Err(_) => panic!("O valor da variável da {value} deve ser um número"),
+        },
+        Err(Error::EnvVar(_)) => default,
+        Err(_) => panic!("Não foi possível acessar as variáveis de ambiente"),
+    }
+}
+
+fn get_by_dotenv_or(value: &str, default: &str) -> String {
+    match dotenv::var(value) {
+        Ok(value) => value,
+        Err(Error::EnvVar(_)) => default.to_string(),
+        Err(_) => panic!("Não foi possível acessar as variáveis de ambiente"),
+    }
+}
+
 fn get_by_dotenv(value: &str) -> String {
     match dotenv::var(value) {
         Ok(value) => value,
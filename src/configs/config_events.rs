@@ -0,0 +1,23 @@
+use std::sync::LazyLock;
+
+use rocket::tokio::sync::broadcast::{self, Receiver, Sender};
+
+use crate::entities::dtos::event_dtos::StockEventDTO;
+
+const EVENTS_CAPACITY: usize = 1024;
+
+static EVENTS: LazyLock<Sender<StockEventDTO>> =
+    LazyLock::new(|| broadcast::channel(EVENTS_CAPACITY).0);
+
+pub fn subscribe() -> Receiver<StockEventDTO> {
+    EVENTS.subscribe()
+}
+
+pub fn has_subscribers() -> bool {
+    EVENTS.receiver_count() > 0
+}
+
+pub fn publish(event: StockEventDTO) {
+    // Sending only fails when nobody is listening, which is not an error
+    let _ = EVENTS.send(event);
+}
@@ -6,41 +6,54 @@ use serde::{Deserialize, Serialize};
 use crate::configs::config_environment::get_jwt_secret;
 
 pub fn generate_token(email: String) -> Result<String, Error> {
+    encode_claim(email, 14400, false) // 4 Hours
+}
+
+pub fn generate_challenge_token(email: String) -> Result<String, Error> {
+    encode_claim(email, 300, true) // 5 Minutes
+}
+
+pub fn valid_token(token: &str) -> bool {
+    match decode_claim(token) {
+        Some(claim) => !claim.challenge,
+        None => false,
+    }
+}
+
+pub fn get_email_by_token(token: &str) -> String {
+    decode_claim(token).unwrap().sub
+}
+
+pub fn get_email_by_challenge_token(token: &str) -> Option<String> {
+    match decode_claim(token) {
+        Some(claim) if claim.challenge => Some(claim.sub),
+        _ => None,
+    }
+}
+
+fn encode_claim(email: String, duration: u64, challenge: bool) -> Result<String, Error> {
     let expiration = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap()
         .as_secs()
-        + 14400; // 4 Hours
+        + duration;
 
     let claim = Claim {
         sub: email,
         exp: expiration as u32,
+        challenge,
     };
 
     let jwt_secret = get_jwt_secret();
 
-    let token = encode(
+    encode(
         &Header::default(),
         &claim,
         &EncodingKey::from_secret(jwt_secret.as_bytes()),
-    );
-
-    Ok(token?)
+    )
 }
 
-pub fn valid_token(token: &str) -> bool {
-    let jwt_secret = get_jwt_secret();
-
-    let claim = decode::<Claim>(
-        token,
-        &DecodingKey::from_secret(jwt_secret.as_bytes()),
-        &Validation::default(),
-    );
-
-    claim.is_ok()
-}
-
-pub fn get_email_by_token(token: &str) -> String {
+fn decode_claim(token: &str) -> Option<Claim> {
     let jwt_secret = get_jwt_secret();
 
     let claim = decode::<Claim>(
@@ -49,11 +62,13 @@ pub fn get_email_by_token(token: &str) -> String {
         &Validation::default(),
     );
 
-    claim.unwrap().claims.sub
+    claim.ok().map(|token_data| token_data.claims)
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 struct Claim {
     sub: String,
     exp: u32,
+    #[serde(default)]
+    challenge: bool,
 }
@@ -0,0 +1,177 @@
+use utoipa::{
+    Modify, OpenApi,
+    openapi::{
+        Ref, RefOr, Response,
+        security::{ApiKey, ApiKeyValue, SecurityScheme},
+    },
+};
+use utoipa_swagger_ui::SwaggerUi;
+
+use crate::{
+    entities::enums::{
+        analytics_enums::{AbcClass, AnalyticsBucket, AnalyticsGroup, ForecastMethod},
+        export_enums::ExportFormat,
+        sort_enums::{SortField, SortOrder},
+    },
+    routes::{
+        generic_functions::ErrorResponse, route_analytics, route_api_key, route_audit,
+        route_category, route_event, route_graphql, route_product, route_reason, route_report,
+        route_reservation, route_role, route_scanner, route_stocktake, route_user,
+    },
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Mestocky", description = "API de controle de estoque"),
+    servers((url = "/api/v1")),
+    paths(
+        route_user::route_login,
+        route_user::route_login_two_factor,
+        route_user::route_login_two_factor_enrollment,
+        route_user::route_valid_token,
+        route_user::route_user_get_all,
+        route_user::route_user_export,
+        route_user::route_user_create,
+        route_user::route_user_update_informations,
+        route_user::route_user_update_credentials,
+        route_user::route_user_update,
+        route_user::route_user_patch,
+        route_user::route_user_reset_password,
+        route_user::route_user_update_active,
+        route_user::route_user_unlock,
+        route_user::route_user_two_factor_enrollment,
+        route_user::route_user_two_factor_confirm,
+        route_user::route_user_two_factor_reset,
+        route_user::route_user_delete,
+        route_category::route_category_get_all,
+        route_category::route_category_get_all_admin,
+        route_category::route_category_export,
+        route_category::route_category_create,
+        route_category::route_category_update,
+        route_category::route_category_patch,
+        route_category::route_category_delete,
+        route_category::route_category_restore,
+        route_product::route_product_get_all,
+        route_product::route_product_export,
+        route_product::route_product_get_by_id,
+        route_product::route_product_informations,
+        route_product::route_product_create,
+        route_product::route_product_import,
+        route_product::route_product_update,
+        route_product::route_product_patch,
+        route_product::route_product_delete,
+        route_product::route_product_restore,
+        route_product::route_product_quantity_update,
+        route_product::route_product_quantity_batch,
+        route_reason::route_reason_get_all,
+        route_reason::route_reason_create,
+        route_reason::route_reason_update,
+        route_reason::route_reason_delete,
+        route_reason::route_reason_restore,
+        route_report::route_report_get_all,
+        route_report::route_report_export,
+        route_report::route_report_pdf_stock,
+        route_report::route_report_pdf_product,
+        route_report::route_report_integrity,
+        route_report::route_report_reversal,
+        route_analytics::route_analytics_stock_history,
+        route_analytics::route_analytics_consumption,
+        route_analytics::route_analytics_ranking,
+        route_analytics::route_analytics_forecast,
+        route_analytics::route_analytics_abc,
+        route_event::route_events,
+        route_scanner::route_scanner,
+        route_graphql::route_graphql,
+        route_reservation::route_reservation_get_all,
+        route_reservation::route_reservation_create,
+        route_reservation::route_reservation_release,
+        route_role::route_role_get_all,
+        route_role::route_permission_get_all,
+        route_role::route_role_create,
+        route_role::route_role_update,
+        route_role::route_role_delete,
+        route_role::route_user_permissions_update,
+        route_stocktake::route_stocktake_get_all,
+        route_stocktake::route_stocktake_get_items,
+        route_stocktake::route_stocktake_create,
+        route_stocktake::route_stocktake_count,
+        route_stocktake::route_stocktake_approve,
+        route_stocktake::route_stocktake_cancel,
+        route_api_key::route_api_key_get_all,
+        route_api_key::route_api_key_create,
+        route_api_key::route_api_key_revoke,
+        route_audit::route_audit_get_all,
+    ),
+    components(
+        schemas(
+            AbcClass,
+            AnalyticsBucket,
+            AnalyticsGroup,
+            ExportFormat,
+            ForecastMethod,
+            SortField,
+            SortOrder
+        ),
+        responses(ErrorResponse)
+    ),
+    modifiers(&SecurityAddon),
+    security(("token" = []), ("api_key" = [])),
+)]
+pub struct ApiDoc;
+
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+
+        components.add_security_scheme(
+            "token",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::with_description(
+                "token",
+                "JWT retornado pelo login",
+            ))),
+        );
+        components.add_security_scheme(
+            "api_key",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::with_description(
+                "Authorization",
+                "Chave de API no formato `ApiKey <chave>`",
+            ))),
+        );
+
+        // Every authenticated route may answer with these, so they are added once here
+        let error_response = || RefOr::<Response>::Ref(Ref::from_response_name("ErrorResponse"));
+
+        for path_item in openapi.paths.paths.values_mut() {
+            let operations = [
+                &mut path_item.get,
+                &mut path_item.post,
+                &mut path_item.put,
+                &mut path_item.delete,
+                &mut path_item.patch,
+            ];
+
+            for operation in operations.into_iter().flatten() {
+                let responses = &mut operation.responses.responses;
+
+                if operation.security.is_none() {
+                    responses
+                        .entry("401".to_string())
+                        .or_insert_with(error_response);
+                    responses
+                        .entry("403".to_string())
+                        .or_insert_with(error_response);
+                }
+
+                responses
+                    .entry("500".to_string())
+                    .or_insert_with(error_response);
+            }
+        }
+    }
+}
+
+pub fn make_swagger_ui() -> SwaggerUi {
+    SwaggerUi::new("/swagger-ui/<_..>").url("/openapi.json", ApiDoc::openapi())
+}
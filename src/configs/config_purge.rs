@@ -0,0 +1,30 @@
+use std::time::Duration;
+
+use rocket::{fairing::AdHoc, tokio};
+use sea_orm::DatabaseConnection;
+
+use crate::{configs::config_environment::get_purge_interval_seconds, services::service_purge};
+
+pub fn make_purge_job() -> AdHoc {
+    AdHoc::on_liftoff("Purge de entidades removidas", |rocket| {
+        Box::pin(async move {
+            let database = match rocket.state::<DatabaseConnection>() {
+                Some(database) => database.clone(),
+                None => panic!("Não foi possível acessar o banco de dados"),
+            };
+
+            let period = Duration::from_secs(get_purge_interval_seconds().max(1) as u64);
+
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(period);
+
+                loop {
+                    interval.tick().await;
+
+                    // Failures are logged and retried on the next tick
+                    service_purge::purge_deleted_entities(&database).await;
+                }
+            });
+        })
+    })
+}
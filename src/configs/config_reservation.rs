@@ -0,0 +1,34 @@
+use std::time::Duration;
+
+use rocket::{fairing::AdHoc, tokio};
+use sea_orm::DatabaseConnection;
+
+use crate::{
+    configs::config_environment::get_reservation_release_interval_seconds,
+    services::service_reservation,
+};
+
+pub fn make_reservation_release_job() -> AdHoc {
+    AdHoc::on_liftoff("Liberação de reservas expiradas", |rocket| {
+        Box::pin(async move {
+            let database = match rocket.state::<DatabaseConnection>() {
+                Some(database) => database.clone(),
+                None => panic!("Não foi possível acessar o banco de dados"),
+            };
+
+            let period =
+                Duration::from_secs(get_reservation_release_interval_seconds().max(1) as u64);
+
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(period);
+
+                loop {
+                    interval.tick().await;
+
+                    // Expired reservations no longer count as reserved, so a failed run is harmless
+                    let _ = service_reservation::release_expired(&database).await;
+                }
+            });
+        })
+    })
+}
@@ -0,0 +1,69 @@
+use chrono::Utc;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use totp_rs::{Algorithm, Secret, TOTP};
+
+use crate::configs::config_environment::get_totp_issuer;
+
+const RECOVERY_CODES_AMOUNT: usize = 10;
+const TOTP_STEP_SECONDS: u64 = 30;
+
+pub fn generate_totp_secret() -> String {
+    let mut bytes = [0u8; 20];
+
+    rand::thread_rng().fill_bytes(&mut bytes);
+
+    Secret::Raw(bytes.to_vec()).to_encoded().to_string()
+}
+
+pub fn get_provisioning_uri(secret: &str, email: &str) -> Option<String> {
+    build_totp(secret, email).map(|totp| totp.get_url())
+}
+
+// Returns the time step the code belongs to, so the caller can refuse it once it is used
+pub fn verify_totp_code(secret: &str, email: &str, code: &str) -> Option<u64> {
+    let totp = build_totp(secret, email)?;
+    let current_step = Utc::now().timestamp().max(0) as u64 / TOTP_STEP_SECONDS;
+
+    // One step of clock drift is accepted on either side
+    (current_step.saturating_sub(1)..=current_step + 1)
+        .find(|step| totp.check(code.trim(), step * TOTP_STEP_SECONDS))
+}
+
+pub fn generate_recovery_codes() -> Vec<String> {
+    (0..RECOVERY_CODES_AMOUNT)
+        .map(|_| {
+            let mut bytes = [0u8; 5];
+
+            rand::thread_rng().fill_bytes(&mut bytes);
+
+            let code = hex::encode(bytes);
+
+            format!("{}-{}", &code[..5], &code[5..])
+        })
+        .collect()
+}
+
+pub fn hash_recovery_code(code: &str) -> String {
+    let normalized_code = code.trim().replace('-', "").to_lowercase();
+
+    hex::encode(Sha256::digest(normalized_code.as_bytes()))
+}
+
+fn build_totp(secret: &str, email: &str) -> Option<TOTP> {
+    let secret = match Secret::Encoded(secret.to_string()).to_bytes() {
+        Ok(secret) => secret,
+        Err(_) => return None,
+    };
+
+    TOTP::new(
+        Algorithm::SHA1,
+        6,
+        0,
+        TOTP_STEP_SECONDS,
+        secret,
+        Some(get_totp_issuer()),
+        email.to_string(),
+    )
+    .ok()
+}
@@ -1,5 +1,12 @@
+pub mod config_api_key;
 pub mod config_bcrypt;
 pub mod config_cors;
 pub mod config_database;
+pub mod config_deprecation;
 pub mod config_environment;
+pub mod config_events;
 pub mod config_jwt;
+pub mod config_openapi;
+pub mod config_purge;
+pub mod config_reservation;
+pub mod config_totp;
@@ -0,0 +1,336 @@
+use sea_orm::FromQueryResult;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::entities::enums::analytics_enums::{AnalyticsBucket, AnalyticsGroup, ForecastMethod};
+
+#[derive(FromForm, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct StockHistoryFilterDTO {
+    product_id: u64,
+    from: Option<String>,
+    to: Option<String>,
+    bucket: Option<AnalyticsBucket>,
+}
+
+impl StockHistoryFilterDTO {
+    pub fn get_product_id(&self) -> &u64 {
+        &self.product_id
+    }
+
+    pub fn get_from(&self) -> &Option<String> {
+        &self.from
+    }
+
+    pub fn get_to(&self) -> &Option<String> {
+        &self.to
+    }
+
+    pub fn get_bucket(&self) -> AnalyticsBucket {
+        self.bucket.unwrap_or(AnalyticsBucket::Day)
+    }
+}
+
+#[derive(FromForm, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ConsumptionFilterDTO {
+    product_id: Option<u64>,
+    category_id: Option<u64>,
+    from: Option<String>,
+    to: Option<String>,
+    bucket: Option<AnalyticsBucket>,
+    group_by: Option<AnalyticsGroup>,
+}
+
+impl ConsumptionFilterDTO {
+    pub fn get_product_id(&self) -> &Option<u64> {
+        &self.product_id
+    }
+
+    pub fn get_category_id(&self) -> &Option<u64> {
+        &self.category_id
+    }
+
+    pub fn get_from(&self) -> &Option<String> {
+        &self.from
+    }
+
+    pub fn get_to(&self) -> &Option<String> {
+        &self.to
+    }
+
+    pub fn get_bucket(&self) -> AnalyticsBucket {
+        self.bucket.unwrap_or(AnalyticsBucket::Day)
+    }
+
+    pub fn get_group_by(&self) -> AnalyticsGroup {
+        self.group_by.unwrap_or(AnalyticsGroup::Product)
+    }
+}
+
+#[derive(FromForm, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RankingFilterDTO {
+    from: Option<String>,
+    to: Option<String>,
+    limit: Option<u64>,
+}
+
+impl RankingFilterDTO {
+    pub fn get_from(&self) -> &Option<String> {
+        &self.from
+    }
+
+    pub fn get_to(&self) -> &Option<String> {
+        &self.to
+    }
+
+    pub fn get_limit(&self) -> &Option<u64> {
+        &self.limit
+    }
+}
+
+#[derive(FromQueryResult)]
+pub struct BucketMovementDTO {
+    bucket: String,
+    entries: u64,
+    exits: u64,
+}
+
+impl BucketMovementDTO {
+    pub fn get_bucket(&self) -> &String {
+        &self.bucket
+    }
+
+    pub fn get_entries(&self) -> &u64 {
+        &self.entries
+    }
+
+    pub fn get_exits(&self) -> &u64 {
+        &self.exits
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct StockHistoryPointDTO {
+    bucket: String,
+    entries: u64,
+    exits: u64,
+    quantity: i64,
+}
+
+impl StockHistoryPointDTO {
+    pub fn new(bucket: String, entries: u64, exits: u64, quantity: i64) -> Self {
+        Self {
+            bucket,
+            entries,
+            exits,
+            quantity,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, FromQueryResult, ToSchema)]
+pub struct ConsumptionDTO {
+    bucket: String,
+    id: u64,
+    name: String,
+    quantity: u64,
+}
+
+#[derive(Serialize, Deserialize, FromQueryResult, ToSchema)]
+pub struct MoverDTO {
+    product_id: u64,
+    product: String,
+    category: String,
+    quantity: u64,
+}
+
+#[derive(Serialize, Deserialize, FromQueryResult, ToSchema)]
+pub struct DeadStockDTO {
+    product_id: u64,
+    product: String,
+    category: String,
+    quantity: u64,
+    last_exit: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct RankingDTO {
+    top_movers: Vec<MoverDTO>,
+    dead_stock: Vec<DeadStockDTO>,
+}
+
+impl RankingDTO {
+    pub fn new(top_movers: Vec<MoverDTO>, dead_stock: Vec<DeadStockDTO>) -> Self {
+        Self {
+            top_movers,
+            dead_stock,
+        }
+    }
+}
+
+#[derive(FromForm, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ForecastFilterDTO {
+    method: Option<ForecastMethod>,
+    window_days: Option<u32>,
+    lead_time_days: Option<u32>,
+    category_id: Option<u64>,
+    at_risk_only: Option<bool>,
+}
+
+impl ForecastFilterDTO {
+    pub fn get_method(&self) -> &Option<ForecastMethod> {
+        &self.method
+    }
+
+    pub fn get_window_days(&self) -> &Option<u32> {
+        &self.window_days
+    }
+
+    pub fn get_lead_time_days(&self) -> &Option<u32> {
+        &self.lead_time_days
+    }
+
+    pub fn get_category_id(&self) -> &Option<u64> {
+        &self.category_id
+    }
+
+    pub fn get_at_risk_only(&self) -> bool {
+        self.at_risk_only.unwrap_or(false)
+    }
+}
+
+#[derive(FromQueryResult)]
+pub struct DailyConsumptionDTO {
+    product_id: u64,
+    day: String,
+    quantity: u64,
+}
+
+impl DailyConsumptionDTO {
+    pub fn get_product_id(&self) -> &u64 {
+        &self.product_id
+    }
+
+    pub fn get_day(&self) -> &String {
+        &self.day
+    }
+
+    pub fn get_quantity(&self) -> &u64 {
+        &self.quantity
+    }
+}
+
+#[derive(Serialize, Deserialize, Default, Debug, ToSchema)]
+pub struct CoverDTO {
+    daily_demand: f64,
+    days_of_cover: Option<f64>,
+    stockout_date: Option<String>,
+}
+
+impl CoverDTO {
+    pub fn new(
+        daily_demand: f64,
+        days_of_cover: Option<f64>,
+        stockout_date: Option<String>,
+    ) -> Self {
+        Self {
+            daily_demand,
+            days_of_cover,
+            stockout_date,
+        }
+    }
+
+    pub fn get_days_of_cover(&self) -> &Option<f64> {
+        &self.days_of_cover
+    }
+}
+
+#[derive(FromQueryResult)]
+pub struct ForecastProductDTO {
+    product_id: u64,
+    product: String,
+    category: String,
+    available: u64,
+}
+
+impl ForecastProductDTO {
+    pub fn get_product_id(&self) -> &u64 {
+        &self.product_id
+    }
+
+    pub fn get_available(&self) -> &u64 {
+        &self.available
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct ForecastDTO {
+    product_id: u64,
+    product: String,
+    category: String,
+    available: u64,
+    #[serde(flatten)]
+    cover: CoverDTO,
+    at_risk: bool,
+}
+
+impl ForecastDTO {
+    pub fn new(product: ForecastProductDTO, cover: CoverDTO, at_risk: bool) -> Self {
+        Self {
+            product_id: product.product_id,
+            product: product.product,
+            category: product.category,
+            available: product.available,
+            cover,
+            at_risk,
+        }
+    }
+
+    pub fn get_cover(&self) -> &CoverDTO {
+        &self.cover
+    }
+
+    pub fn get_at_risk(&self) -> &bool {
+        &self.at_risk
+    }
+}
+
+#[derive(FromQueryResult)]
+pub struct ProductVolumeDTO {
+    product_id: u64,
+    volume: u64,
+}
+
+impl ProductVolumeDTO {
+    pub fn get_product_id(&self) -> &u64 {
+        &self.product_id
+    }
+
+    pub fn get_volume(&self) -> &u64 {
+        &self.volume
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct AbcClassificationDTO {
+    window_days: u32,
+    total_volume: u64,
+    a: usize,
+    b: usize,
+    c: usize,
+}
+
+impl AbcClassificationDTO {
+    pub fn new(window_days: u32, total_volume: u64, a: usize, b: usize, c: usize) -> Self {
+        Self {
+            window_days,
+            total_volume,
+            a,
+            b,
+            c,
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use sea_orm::FromQueryResult;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct ApiKeyViewDTO {
+    id: u64,
+    name: String,
+    prefix: String,
+    created_by: Option<String>,
+    created_at: String,
+    last_used_at: Option<String>,
+    revoked_at: Option<String>,
+    permissions: Vec<String>,
+}
+
+impl ApiKeyViewDTO {
+    pub fn from_rows(rows: Vec<ApiKeyPermissionQueryDTO>) -> Vec<Self> {
+        let mut api_keys: Vec<Self> = Vec::new();
+
+        for row in rows {
+            let api_key = match api_keys.last_mut() {
+                Some(api_key) if api_key.id == row.id => api_key,
+                _ => {
+                    api_keys.push(Self {
+                        id: row.id,
+                        name: row.name,
+                        prefix: row.prefix,
+                        created_by: row.created_by,
+                        created_at: row.created_at,
+                        last_used_at: row.last_used_at,
+                        revoked_at: row.revoked_at,
+                        permissions: Vec::new(),
+                    });
+
+                    api_keys.last_mut().unwrap()
+                }
+            };
+
+            if let Some(permission) = row.permission {
+                api_key.permissions.push(permission);
+            }
+        }
+
+        api_keys
+    }
+}
+
+#[derive(FromQueryResult)]
+pub struct ApiKeyPermissionQueryDTO {
+    id: u64,
+    name: String,
+    prefix: String,
+    created_by: Option<String>,
+    created_at: String,
+    last_used_at: Option<String>,
+    revoked_at: Option<String>,
+    permission: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct ApiKeyCreateDTO {
+    name: String,
+    permissions: Vec<String>,
+}
+
+impl ApiKeyCreateDTO {
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn get_permissions(&self) -> &Vec<String> {
+        &self.permissions
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct ApiKeyCreatedDTO {
+    id: u64,
+    key: String,
+}
+
+impl ApiKeyCreatedDTO {
+    pub fn new(id: u64, key: String) -> Self {
+        Self { id, key }
+    }
+}
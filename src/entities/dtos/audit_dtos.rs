@@ -0,0 +1,49 @@
+use rocket::FromForm;
+use sea_orm::{FromQueryResult, JsonValue};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Serialize, Deserialize, FromQueryResult, ToSchema)]
+pub struct AuditViewDTO {
+    id: u64,
+    user: Option<String>,
+    api_key: Option<String>,
+    entity: String,
+    entity_id: u64,
+    action: String,
+    before_data: Option<JsonValue>,
+    after_data: Option<JsonValue>,
+    created_at: String,
+}
+
+#[derive(FromForm, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AuditFilterDTO {
+    entity: Option<String>,
+    user_id: Option<u64>,
+    api_key_id: Option<u64>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+}
+
+impl AuditFilterDTO {
+    pub fn get_entity(&self) -> &Option<String> {
+        &self.entity
+    }
+
+    pub fn get_user_id(&self) -> &Option<u64> {
+        &self.user_id
+    }
+
+    pub fn get_api_key_id(&self) -> &Option<u64> {
+        &self.api_key_id
+    }
+
+    pub fn get_start_date(&self) -> &Option<String> {
+        &self.start_date
+    }
+
+    pub fn get_end_date(&self) -> &Option<String> {
+        &self.end_date
+    }
+}
@@ -1,27 +1,90 @@
+use async_graphql::InputObject;
 use sea_orm::FromQueryResult;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Serialize, Deserialize)]
+use crate::entities::{
+    dtos::{
+        export_dtos::{ExportCell, ExportRow},
+        generic_dtos::deserialize_patch_value,
+    },
+    enums::export_enums::ExportLanguage,
+};
+
+#[derive(Serialize, Deserialize, FromQueryResult, InputObject, ToSchema)]
+#[graphql(name = "CategoryUpdateInput")]
 pub struct CategoryDTO {
     id: u64,
     name: String,
+    version: Option<u64>,
+    #[serde(skip_deserializing)]
+    #[graphql(skip)]
+    created_at: Option<String>,
+    #[serde(skip_deserializing)]
+    #[graphql(skip)]
+    updated_at: Option<String>,
+    #[serde(skip_deserializing)]
+    #[graphql(skip)]
+    created_by: Option<String>,
+    #[serde(skip_deserializing)]
+    #[graphql(skip)]
+    updated_by: Option<String>,
+    #[serde(skip_deserializing, skip_serializing_if = "Option::is_none")]
+    #[graphql(skip)]
+    deleted_at: Option<String>,
 }
 
 impl CategoryDTO {
-    pub fn new(id: u64, name: String) -> Self {
-        Self { id, name }
-    }
-
     pub fn get_id(&self) -> &u64 {
         &self.id
     }
 
-    pub fn get_name(&self) -> &String {
+    pub fn get_version(&self) -> &Option<u64> {
+        &self.version
+    }
+
+    pub fn set_version(&mut self, version: u64) {
+        self.version = Some(version);
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CategoryPatchDTO {
+    #[serde(default, deserialize_with = "deserialize_patch_value")]
+    #[schema(value_type = String, required = false)]
+    name: Option<String>,
+    version: Option<u64>,
+}
+
+impl CategoryPatchDTO {
+    pub fn get_name(&self) -> &Option<String> {
         &self.name
     }
+
+    pub fn get_version(&self) -> &Option<u64> {
+        &self.version
+    }
+
+    pub fn set_version(&mut self, version: u64) {
+        self.version = Some(version);
+    }
 }
 
-#[derive(Serialize, Deserialize)]
+// PUT keeps its original rules, where an empty name leaves it unchanged
+impl From<CategoryDTO> for CategoryPatchDTO {
+    fn from(value: CategoryDTO) -> Self {
+        Self {
+            name: match value.name.trim().is_empty() {
+                true => None,
+                false => Some(value.name),
+            },
+            version: value.version,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, InputObject, ToSchema)]
+#[graphql(name = "CategoryCreateInput")]
 pub struct CategoryCreateDTO {
     name: String,
 }
@@ -32,9 +95,23 @@ impl CategoryCreateDTO {
     }
 }
 
-#[derive(Serialize, Deserialize, FromQueryResult)]
+#[derive(Serialize, Deserialize, FromQueryResult, ToSchema)]
 pub struct CategoryViewDTO {
     id: u64,
     name: String,
     quantity: Option<u64>,
+    version: u64,
+    created_at: String,
+    updated_at: String,
+    created_by: Option<String>,
+    updated_by: Option<String>,
+}
+
+impl ExportRow for CategoryViewDTO {
+    const HEADERS: &'static [(&'static str, &'static str)] =
+        &[("ID", "ID"), ("Nome", "Name"), ("Produtos", "Products")];
+
+    fn into_cells(self, _language: ExportLanguage) -> Vec<ExportCell> {
+        vec![self.id.into(), self.name.into(), self.quantity.into()]
+    }
 }
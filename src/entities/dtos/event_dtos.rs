@@ -0,0 +1,65 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::entities::{enums::event_enums::StockEventType, tb_product};
+
+#[derive(Serialize, Clone, ToSchema)]
+pub struct MovementEventDTO {
+    #[serde(skip)]
+    product_id: u64,
+    change_type: bool,
+    quantity: u64,
+}
+
+impl MovementEventDTO {
+    pub fn new(product_id: u64, change_type: bool, quantity: u64) -> Self {
+        Self {
+            product_id,
+            change_type,
+            quantity,
+        }
+    }
+
+    pub fn get_product_id(&self) -> &u64 {
+        &self.product_id
+    }
+}
+
+#[derive(Serialize, Clone, ToSchema)]
+pub struct StockEventDTO {
+    #[serde(skip)]
+    event_type: StockEventType,
+    product_id: u64,
+    name: String,
+    category_id: u64,
+    quantity: u64,
+    min_quantity: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    movement: Option<MovementEventDTO>,
+}
+
+impl StockEventDTO {
+    pub fn new(
+        event_type: StockEventType,
+        product: &tb_product::Model,
+        movement: Option<MovementEventDTO>,
+    ) -> Self {
+        Self {
+            event_type,
+            product_id: product.id,
+            name: product.name.clone(),
+            category_id: product.category_id,
+            quantity: product.quantity,
+            min_quantity: product.min_quantity,
+            movement,
+        }
+    }
+
+    pub fn get_event_type(&self) -> &StockEventType {
+        &self.event_type
+    }
+
+    pub fn get_category_id(&self) -> &u64 {
+        &self.category_id
+    }
+}
@@ -0,0 +1,39 @@
+use crate::entities::enums::export_enums::ExportLanguage;
+
+pub enum ExportCell {
+    Number(u64),
+    Text(String),
+}
+
+impl From<u64> for ExportCell {
+    fn from(number: u64) -> Self {
+        ExportCell::Number(number)
+    }
+}
+
+impl From<String> for ExportCell {
+    fn from(text: String) -> Self {
+        ExportCell::Text(text)
+    }
+}
+
+impl From<&str> for ExportCell {
+    fn from(text: &str) -> Self {
+        ExportCell::Text(text.to_string())
+    }
+}
+
+impl<T: Into<ExportCell>> From<Option<T>> for ExportCell {
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => value.into(),
+            None => ExportCell::Text(String::new()),
+        }
+    }
+}
+
+pub trait ExportRow {
+    const HEADERS: &'static [(&'static str, &'static str)];
+
+    fn into_cells(self, language: ExportLanguage) -> Vec<ExportCell>;
+}
@@ -1,4 +1,8 @@
 use sea_orm::FromQueryResult;
+use serde::{Deserialize, Deserializer};
+use utoipa::IntoParams;
+
+use crate::entities::enums::sort_enums::{SortField, SortOrder};
 
 #[derive(FromQueryResult)]
 pub struct ExistsDTO {
@@ -10,3 +14,66 @@ impl ExistsDTO {
         self.exist
     }
 }
+
+pub struct ActorDTO {
+    user_id: Option<u64>,
+    api_key_id: Option<u64>,
+}
+
+impl ActorDTO {
+    pub fn new(user_id: Option<u64>, api_key_id: Option<u64>) -> Self {
+        Self {
+            user_id,
+            api_key_id,
+        }
+    }
+
+    pub fn get_user_id(&self) -> &Option<u64> {
+        &self.user_id
+    }
+
+    pub fn get_api_key_id(&self) -> &Option<u64> {
+        &self.api_key_id
+    }
+}
+
+#[derive(FromForm, IntoParams, Default)]
+#[into_params(parameter_in = Query)]
+pub struct SortDTO {
+    sort: Option<SortField>,
+    order: Option<SortOrder>,
+}
+
+impl SortDTO {
+    pub fn get_sort(&self) -> &Option<SortField> {
+        &self.sort
+    }
+
+    pub fn get_order(&self) -> SortOrder {
+        self.order.unwrap_or_default()
+    }
+}
+
+// JSON merge patch (RFC 7396) fields are None when absent. A null only fits nullable fields, where
+// it becomes Some(None), and is rejected by the others
+pub fn deserialize_patch_value<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    match T::deserialize(deserializer) {
+        Ok(value) => Ok(Some(value)),
+        Err(error) => Err(error),
+    }
+}
+
+pub fn deserialize_patch_nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    match Option::<T>::deserialize(deserializer) {
+        Ok(value) => Ok(Some(value)),
+        Err(error) => Err(error),
+    }
+}
@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Deserialize)]
+pub struct ProductImportRowDTO {
+    name: String,
+    category: String,
+    min_quantity: u64,
+    description: Option<String>,
+    #[serde(default)]
+    quantity: Option<u64>,
+}
+
+impl ProductImportRowDTO {
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn get_category(&self) -> &String {
+        &self.category
+    }
+
+    pub fn get_min_quantity(&self) -> &u64 {
+        &self.min_quantity
+    }
+
+    pub fn get_description(&self) -> &Option<String> {
+        &self.description
+    }
+
+    pub fn get_quantity(&self) -> u64 {
+        self.quantity.unwrap_or(0)
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ProductImportLineDTO {
+    line: u64,
+    name: Option<String>,
+    category: Option<String>,
+    action: Option<&'static str>,
+    create_category: bool,
+    quantity: u64,
+    error: Option<String>,
+}
+
+impl ProductImportLineDTO {
+    pub fn planned(
+        line: u64,
+        row: &ProductImportRowDTO,
+        action: &'static str,
+        create_category: bool,
+    ) -> Self {
+        Self {
+            line,
+            name: Some(row.get_name().clone()),
+            category: Some(row.get_category().clone()),
+            action: Some(action),
+            create_category,
+            quantity: row.get_quantity(),
+            error: None,
+        }
+    }
+
+    pub fn failed(line: u64, row: Option<&ProductImportRowDTO>, error: &str) -> Self {
+        Self {
+            line,
+            name: row.map(|row| row.get_name().clone()),
+            category: row.map(|row| row.get_category().clone()),
+            action: None,
+            create_category: false,
+            quantity: row.map_or(0, |row| row.get_quantity()),
+            error: Some(error.to_string()),
+        }
+    }
+
+    pub fn is_failed(&self) -> bool {
+        self.error.is_some()
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ProductImportResultDTO {
+    dry_run: bool,
+    applied: bool,
+    created: usize,
+    updated: usize,
+    created_categories: usize,
+    errors: usize,
+    lines: Vec<ProductImportLineDTO>,
+}
+
+impl ProductImportResultDTO {
+    pub fn new(
+        dry_run: bool,
+        applied: bool,
+        created: usize,
+        updated: usize,
+        created_categories: usize,
+        lines: Vec<ProductImportLineDTO>,
+    ) -> Self {
+        Self {
+            dry_run,
+            applied,
+            created,
+            updated,
+            created_categories,
+            errors: lines.iter().filter(|line| line.is_failed()).count(),
+            lines,
+        }
+    }
+
+    pub fn get_dry_run(&self) -> &bool {
+        &self.dry_run
+    }
+
+    pub fn get_applied(&self) -> &bool {
+        &self.applied
+    }
+}
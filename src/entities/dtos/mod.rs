@@ -1,6 +1,16 @@
+pub mod analytics_dtos;
+pub mod api_key_dtos;
+pub mod audit_dtos;
 pub mod category_dtos;
+pub mod event_dtos;
+pub mod export_dtos;
 pub mod generic_dtos;
+pub mod import_dtos;
 pub mod product_dtos;
 pub mod reason_dtos;
 pub mod report_dtos;
+pub mod reservation_dtos;
+pub mod role_dtos;
+pub mod scanner_dtos;
+pub mod stocktake_dtos;
 pub mod user_dtos;
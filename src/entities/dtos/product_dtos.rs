@@ -1,9 +1,22 @@
+use async_graphql::InputObject;
 use sea_orm::FromQueryResult;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Serialize, Deserialize)]
+use crate::entities::{
+    dtos::{
+        analytics_dtos::CoverDTO,
+        export_dtos::{ExportCell, ExportRow},
+        generic_dtos::{deserialize_patch_nullable, deserialize_patch_value},
+    },
+    enums::export_enums::ExportLanguage,
+};
+
+#[derive(Serialize, Deserialize, InputObject, ToSchema)]
+#[graphql(name = "ProductCreateInput")]
 pub struct ProductCreateDTO {
     name: String,
+    barcode: Option<String>,
     min_quantity: u64,
     category_id: u64,
     description: Option<String>,
@@ -14,6 +27,10 @@ impl ProductCreateDTO {
         &self.name
     }
 
+    pub fn get_barcode(&self) -> &Option<String> {
+        &self.barcode
+    }
+
     pub fn get_min_quantity(&self) -> &u64 {
         &self.min_quantity
     }
@@ -27,13 +44,16 @@ impl ProductCreateDTO {
     }
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, InputObject, ToSchema)]
+#[graphql(name = "ProductUpdateInput")]
 pub struct ProductUpdateDTO {
     id: u64,
     name: String,
+    barcode: Option<String>,
     min_quantity: u64,
     category_id: u64,
     description: Option<String>,
+    version: Option<u64>,
 }
 
 impl ProductUpdateDTO {
@@ -41,51 +61,230 @@ impl ProductUpdateDTO {
         &self.id
     }
 
-    pub fn get_name(&self) -> &String {
+    pub fn set_version(&mut self, version: u64) {
+        self.version = Some(version);
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct ProductPatchDTO {
+    #[serde(default, deserialize_with = "deserialize_patch_value")]
+    #[schema(value_type = String, required = false)]
+    name: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_patch_nullable")]
+    #[schema(value_type = Option<String>, required = false)]
+    barcode: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_patch_value")]
+    #[schema(value_type = u64, required = false)]
+    min_quantity: Option<u64>,
+    #[serde(default, deserialize_with = "deserialize_patch_value")]
+    #[schema(value_type = u64, required = false)]
+    category_id: Option<u64>,
+    #[serde(default, deserialize_with = "deserialize_patch_nullable")]
+    #[schema(value_type = Option<String>, required = false)]
+    description: Option<Option<String>>,
+    version: Option<u64>,
+}
+
+impl ProductPatchDTO {
+    pub fn get_name(&self) -> &Option<String> {
         &self.name
     }
 
-    pub fn get_min_quantity(&self) -> &u64 {
+    pub fn get_barcode(&self) -> &Option<Option<String>> {
+        &self.barcode
+    }
+
+    pub fn get_min_quantity(&self) -> &Option<u64> {
         &self.min_quantity
     }
 
-    pub fn get_category_id(&self) -> &u64 {
+    pub fn get_category_id(&self) -> &Option<u64> {
         &self.category_id
     }
 
-    pub fn get_description(&self) -> &Option<String> {
+    pub fn get_description(&self) -> &Option<Option<String>> {
         &self.description
     }
+
+    pub fn get_version(&self) -> &Option<u64> {
+        &self.version
+    }
+
+    pub fn set_version(&mut self, version: u64) {
+        self.version = Some(version);
+    }
+}
+
+// PUT keeps its original rules, where empty strings and 0 leave the field unchanged
+impl From<ProductUpdateDTO> for ProductPatchDTO {
+    fn from(value: ProductUpdateDTO) -> Self {
+        Self {
+            name: match value.name.trim().is_empty() {
+                true => None,
+                false => Some(value.name),
+            },
+            barcode: match value.barcode {
+                Some(barcode) if !barcode.trim().is_empty() => Some(Some(barcode)),
+                _ => None,
+            },
+            min_quantity: match value.min_quantity {
+                0 => None,
+                min_quantity => Some(min_quantity),
+            },
+            category_id: match value.category_id {
+                0 => None,
+                category_id => Some(category_id),
+            },
+            description: match value.description {
+                Some(description) if !description.trim().is_empty() => Some(Some(description)),
+                _ => None,
+            },
+            version: value.version,
+        }
+    }
 }
 
-#[derive(Serialize, Deserialize, FromQueryResult)]
+#[derive(Serialize, Deserialize, FromQueryResult, ToSchema)]
 pub struct ProductViewDTO {
     id: u64,
     name: String,
+    barcode: Option<String>,
     quantity: u64,
+    available: u64,
     min_quantity: u64,
     category: String,
     description: Option<String>,
+    abc_class: Option<String>,
+    version: u64,
+    created_at: String,
+    updated_at: String,
+    created_by: Option<String>,
+    updated_by: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deleted_at: Option<String>,
+    #[sea_orm(skip)]
+    #[serde(flatten)]
+    cover: CoverDTO,
 }
 
-#[derive(Serialize, Deserialize, FromQueryResult, Debug)]
+impl ProductViewDTO {
+    pub fn get_id(&self) -> &u64 {
+        &self.id
+    }
+
+    pub fn get_available(&self) -> &u64 {
+        &self.available
+    }
+
+    pub fn set_cover(&mut self, cover: CoverDTO) {
+        self.cover = cover;
+    }
+}
+
+#[derive(FromQueryResult)]
+pub struct ProductAvailableDTO {
+    id: u64,
+    available: u64,
+}
+
+impl ProductAvailableDTO {
+    pub fn get_id(&self) -> &u64 {
+        &self.id
+    }
+
+    pub fn get_available(&self) -> &u64 {
+        &self.available
+    }
+}
+
+impl ExportRow for ProductViewDTO {
+    const HEADERS: &'static [(&'static str, &'static str)] = &[
+        ("ID", "ID"),
+        ("Nome", "Name"),
+        ("Código de barras", "Barcode"),
+        ("Quantidade", "Quantity"),
+        ("Disponível", "Available"),
+        ("Quantidade mínima", "Minimum quantity"),
+        ("Categoria", "Category"),
+        ("Descrição", "Description"),
+        ("Classe ABC", "ABC class"),
+        ("Removido em", "Deleted at"),
+    ];
+
+    fn into_cells(self, _language: ExportLanguage) -> Vec<ExportCell> {
+        vec![
+            self.id.into(),
+            self.name.into(),
+            self.barcode.into(),
+            self.quantity.into(),
+            self.available.into(),
+            self.min_quantity.into(),
+            self.category.into(),
+            self.description.into(),
+            self.abc_class.into(),
+            self.deleted_at.into(),
+        ]
+    }
+}
+
+#[derive(Serialize, Deserialize, FromQueryResult, Debug, ToSchema)]
 pub struct ProductSummaryDTO {
     name: String,
+    barcode: Option<String>,
     quantity: u64,
+    available: u64,
     min_quantity: u64,
     category: String,
     description: Option<String>,
+    abc_class: Option<String>,
+    version: u64,
+    created_at: String,
+    updated_at: String,
+    created_by: Option<String>,
+    updated_by: Option<String>,
+    #[sea_orm(skip)]
+    #[serde(flatten)]
+    cover: CoverDTO,
+}
+
+impl ProductSummaryDTO {
+    pub fn get_version(&self) -> &u64 {
+        &self.version
+    }
+
+    pub fn get_available(&self) -> &u64 {
+        &self.available
+    }
+
+    pub fn set_cover(&mut self, cover: CoverDTO) {
+        self.cover = cover;
+    }
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, InputObject, ToSchema)]
+#[graphql(name = "ProductChangeQuantityInput")]
 pub struct ProductChangeQuantityDTO {
     id: u64,
     change_type: bool,
     quantity: u64,
     reason_id: u64,
+    reservation_id: Option<u64>,
+    document: Option<String>,
 }
 
 impl ProductChangeQuantityDTO {
+    pub fn new(id: u64, change_type: bool, quantity: u64, reason_id: u64) -> Self {
+        Self {
+            id,
+            change_type,
+            quantity,
+            reason_id,
+            reservation_id: None,
+            document: None,
+        }
+    }
+
     pub fn get_id(&self) -> &u64 {
         &self.id
     }
@@ -101,6 +300,93 @@ impl ProductChangeQuantityDTO {
     pub fn get_reason_id(&self) -> &u64 {
         &self.reason_id
     }
+
+    pub fn get_reservation_id(&self) -> &Option<u64> {
+        &self.reservation_id
+    }
+
+    pub fn get_document(&self) -> &Option<String> {
+        &self.document
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct ProductBatchMovementDTO {
+    id: u64,
+    change_type: bool,
+    quantity: u64,
+    reservation_id: Option<u64>,
+}
+
+impl ProductBatchMovementDTO {
+    pub fn get_id(&self) -> &u64 {
+        &self.id
+    }
+
+    pub fn into_change_quantity_dto(
+        self,
+        reason_id: u64,
+        document: Option<String>,
+    ) -> ProductChangeQuantityDTO {
+        ProductChangeQuantityDTO {
+            id: self.id,
+            change_type: self.change_type,
+            quantity: self.quantity,
+            reason_id,
+            reservation_id: self.reservation_id,
+            document,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct ProductBatchQuantityDTO {
+    reason_id: u64,
+    document: Option<String>,
+    #[serde(default)]
+    partial: bool,
+    movements: Vec<ProductBatchMovementDTO>,
+}
+
+impl ProductBatchQuantityDTO {
+    pub fn get_reason_id(&self) -> &u64 {
+        &self.reason_id
+    }
+
+    pub fn get_document(&self) -> &Option<String> {
+        &self.document
+    }
+
+    pub fn get_partial(&self) -> &bool {
+        &self.partial
+    }
+
+    pub fn get_movements(&self) -> &Vec<ProductBatchMovementDTO> {
+        &self.movements
+    }
+
+    pub fn into_movements(self) -> Vec<ProductBatchMovementDTO> {
+        self.movements
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct ProductBatchResultDTO {
+    line: usize,
+    product_id: u64,
+    success: bool,
+    error: Option<String>,
+}
+
+impl ProductBatchResultDTO {
+    pub fn new(line: usize, product_id: u64, error: Option<String>) -> Self {
+        Self {
+            line,
+            product_id,
+            success: error.is_none(),
+            error,
+        }
+    }
 }
 
 #[derive(FromQueryResult)]
@@ -110,7 +396,7 @@ pub struct ProductInformationsGetDTO {
     warnings: Option<u64>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 pub struct ProductInformationsViewDTO {
     quantity: u64,
     total: u64,
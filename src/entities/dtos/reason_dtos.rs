@@ -1,16 +1,31 @@
+use async_graphql::InputObject;
+use sea_orm::FromQueryResult;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, FromQueryResult, InputObject, ToSchema)]
+#[graphql(name = "ReasonUpdateInput")]
 pub struct ReasonDTO {
     id: u64,
     name: String,
+    #[serde(skip_deserializing)]
+    #[graphql(skip)]
+    created_at: Option<String>,
+    #[serde(skip_deserializing)]
+    #[graphql(skip)]
+    updated_at: Option<String>,
+    #[serde(skip_deserializing)]
+    #[graphql(skip)]
+    created_by: Option<String>,
+    #[serde(skip_deserializing)]
+    #[graphql(skip)]
+    updated_by: Option<String>,
+    #[serde(skip_deserializing, skip_serializing_if = "Option::is_none")]
+    #[graphql(skip)]
+    deleted_at: Option<String>,
 }
 
 impl ReasonDTO {
-    pub fn new(id: u64, name: String) -> Self {
-        Self { id, name }
-    }
-
     pub fn get_id(&self) -> &u64 {
         &self.id
     }
@@ -20,7 +35,8 @@ impl ReasonDTO {
     }
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, InputObject, ToSchema)]
+#[graphql(name = "ReasonCreateInput")]
 pub struct ReasonCreateDTO {
     name: String,
 }
@@ -1,7 +1,13 @@
 use sea_orm::FromQueryResult;
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 
-#[derive(Serialize, Deserialize, FromQueryResult)]
+use crate::entities::{
+    dtos::export_dtos::{ExportCell, ExportRow},
+    enums::export_enums::ExportLanguage,
+};
+
+#[derive(Serialize, Deserialize, FromQueryResult, ToSchema)]
 pub struct ReportViewDTO {
     id: u64,
     change_type: bool,
@@ -9,6 +15,43 @@ pub struct ReportViewDTO {
     product: String,
     reason: String,
     date: String,
+    user: Option<String>,
+    api_key: Option<String>,
+    reversal_of: Option<u64>,
+    document: Option<String>,
+}
+
+impl ExportRow for ReportViewDTO {
+    const HEADERS: &'static [(&'static str, &'static str)] = &[
+        ("ID", "ID"),
+        ("Tipo", "Type"),
+        ("Quantidade", "Quantity"),
+        ("Produto", "Product"),
+        ("Motivo", "Reason"),
+        ("Data", "Date"),
+        ("Usuário", "User"),
+        ("Chave de API", "API key"),
+        ("Estorno de", "Reversal of"),
+        ("Documento", "Document"),
+    ];
+
+    fn into_cells(self, language: ExportLanguage) -> Vec<ExportCell> {
+        vec![
+            self.id.into(),
+            match self.change_type {
+                true => language.translate("Entrada", "Entry").into(),
+                false => language.translate("Saída", "Exit").into(),
+            },
+            self.quantity.into(),
+            self.product.into(),
+            self.reason.into(),
+            self.date.into(),
+            self.user.into(),
+            self.api_key.into(),
+            self.reversal_of.into(),
+            self.document.into(),
+        ]
+    }
 }
 
 impl ReportViewDTO {
@@ -21,18 +64,116 @@ impl ReportViewDTO {
     }
 }
 
-#[derive(Serialize, Deserialize)]
-pub struct ReportUpdateDTO {
-    id: u64,
-    reason_id: u64
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct ReportReversalDTO {
+    reason_id: Option<u64>,
 }
 
-impl ReportUpdateDTO {
-    pub fn get_id(&self) -> &u64 {
-        &self.id
+impl ReportReversalDTO {
+    pub fn get_reason_id(&self) -> &Option<u64> {
+        &self.reason_id
     }
+}
 
-    pub fn get_reason_id(&self) -> &u64 {
-        &self.reason_id
+#[derive(Serialize, Deserialize, FromQueryResult, ToSchema)]
+pub struct ReportIntegrityDTO {
+    product_id: u64,
+    product: String,
+    quantity: u64,
+    ledger_quantity: i64,
+}
+
+#[derive(FromQueryResult)]
+pub struct StockPositionDTO {
+    category: String,
+    product: String,
+    quantity: u64,
+    min_quantity: u64,
+}
+
+impl StockPositionDTO {
+    pub fn get_category(&self) -> &String {
+        &self.category
+    }
+
+    pub fn get_product(&self) -> &String {
+        &self.product
+    }
+
+    pub fn get_quantity(&self) -> &u64 {
+        &self.quantity
+    }
+
+    pub fn get_min_quantity(&self) -> &u64 {
+        &self.min_quantity
     }
-}
\ No newline at end of file
+}
+
+#[derive(FromForm, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ProductStatementFilterDTO {
+    start_date: Option<String>,
+    end_date: Option<String>,
+}
+
+impl ProductStatementFilterDTO {
+    pub fn get_start_date(&self) -> &Option<String> {
+        &self.start_date
+    }
+
+    pub fn get_end_date(&self) -> &Option<String> {
+        &self.end_date
+    }
+}
+
+#[derive(FromQueryResult)]
+pub struct ProductStatementLineDTO {
+    date: String,
+    change_type: bool,
+    quantity: u64,
+    reason: String,
+    document: Option<String>,
+    user: Option<String>,
+    api_key: Option<String>,
+}
+
+impl ProductStatementLineDTO {
+    pub fn get_date(&self) -> &String {
+        &self.date
+    }
+
+    pub fn get_change_type(&self) -> &bool {
+        &self.change_type
+    }
+
+    pub fn get_quantity(&self) -> &u64 {
+        &self.quantity
+    }
+
+    pub fn get_reason(&self) -> &String {
+        &self.reason
+    }
+
+    pub fn get_document(&self) -> &Option<String> {
+        &self.document
+    }
+
+    pub fn get_actor(&self) -> Option<&String> {
+        self.user.as_ref().or(self.api_key.as_ref())
+    }
+
+    pub fn set_date(&mut self, date: String) {
+        self.date = date;
+    }
+}
+
+#[derive(FromQueryResult)]
+pub struct OpeningBalanceDTO {
+    balance: Option<i64>,
+}
+
+impl OpeningBalanceDTO {
+    pub fn get_into_balance(self) -> i64 {
+        self.balance.unwrap_or(0)
+    }
+}
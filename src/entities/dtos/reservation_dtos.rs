@@ -0,0 +1,53 @@
+use sea_orm::FromQueryResult;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct ReservationCreateDTO {
+    product_id: u64,
+    holder: String,
+    quantity: u64,
+    expires_at: String,
+}
+
+impl ReservationCreateDTO {
+    pub fn get_product_id(&self) -> &u64 {
+        &self.product_id
+    }
+
+    pub fn get_holder(&self) -> &String {
+        &self.holder
+    }
+
+    pub fn get_quantity(&self) -> &u64 {
+        &self.quantity
+    }
+
+    pub fn get_expires_at(&self) -> &String {
+        &self.expires_at
+    }
+}
+
+#[derive(Serialize, Deserialize, FromQueryResult, ToSchema)]
+pub struct ReservationViewDTO {
+    id: u64,
+    product_id: u64,
+    product: String,
+    holder: String,
+    quantity: u64,
+    consumed_quantity: u64,
+    expires_at: String,
+    created_at: String,
+    created_by: Option<String>,
+}
+
+#[derive(FromQueryResult)]
+pub struct ReservedQuantityDTO {
+    reserved: Option<u64>,
+}
+
+impl ReservedQuantityDTO {
+    pub fn get_into_reserved(self) -> u64 {
+        self.reserved.unwrap_or(0)
+    }
+}
@@ -0,0 +1,106 @@
+use sea_orm::FromQueryResult;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct RoleViewDTO {
+    id: u8,
+    name: String,
+    permissions: Vec<String>,
+}
+
+impl RoleViewDTO {
+    pub fn new(id: u8, name: String, permissions: Vec<String>) -> Self {
+        Self {
+            id,
+            name,
+            permissions,
+        }
+    }
+
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn from_rows(rows: Vec<RolePermissionQueryDTO>) -> Vec<Self> {
+        let mut roles: Vec<Self> = Vec::new();
+
+        for row in rows {
+            let role = match roles.last_mut() {
+                Some(role) if role.id == row.id => role,
+                _ => {
+                    roles.push(Self {
+                        id: row.id,
+                        name: row.name,
+                        permissions: Vec::new(),
+                    });
+
+                    roles.last_mut().unwrap()
+                }
+            };
+
+            if let Some(permission) = row.permission {
+                role.permissions.push(permission);
+            }
+        }
+
+        roles
+    }
+}
+
+#[derive(FromQueryResult)]
+pub struct RolePermissionQueryDTO {
+    id: u8,
+    name: String,
+    permission: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct RoleDTO {
+    name: String,
+    permissions: Vec<String>,
+}
+
+impl RoleDTO {
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn get_permissions(&self) -> &Vec<String> {
+        &self.permissions
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct PermissionDTO {
+    id: u64,
+    name: String,
+}
+
+impl PermissionDTO {
+    pub fn new(id: u64, name: String) -> Self {
+        Self { id, name }
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct PermissionsUpdateDTO {
+    permissions: Vec<String>,
+}
+
+impl PermissionsUpdateDTO {
+    pub fn get_permissions(&self) -> &Vec<String> {
+        &self.permissions
+    }
+}
+
+#[derive(FromQueryResult)]
+pub struct PermissionNameQueryDTO {
+    name: String,
+}
+
+impl PermissionNameQueryDTO {
+    pub fn get_into_name(self) -> String {
+        self.name
+    }
+}
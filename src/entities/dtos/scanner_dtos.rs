@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+
+use crate::entities::{
+    dtos::product_dtos::ProductSummaryDTO, enums::scanner_enums::ScannerDirection,
+};
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ScannerCommandDTO {
+    Scan {
+        request_id: Option<String>,
+        barcode: String,
+    },
+    Movement {
+        request_id: Option<String>,
+        barcode: String,
+        quantity: u64,
+        direction: ScannerDirection,
+        reason_id: u64,
+    },
+}
+
+#[derive(Serialize)]
+pub struct ScannerProductDTO {
+    product_id: u64,
+    #[serde(flatten)]
+    product: ProductSummaryDTO,
+}
+
+impl ScannerProductDTO {
+    pub fn new(product_id: u64, product: ProductSummaryDTO) -> Self {
+        Self {
+            product_id,
+            product,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ScannerReplyDTO {
+    Product {
+        request_id: Option<String>,
+        product: ScannerProductDTO,
+    },
+    Ack {
+        request_id: Option<String>,
+        product: ScannerProductDTO,
+    },
+    Error {
+        request_id: Option<String>,
+        status: u16,
+        message: &'static str,
+    },
+}
@@ -0,0 +1,66 @@
+use sea_orm::FromQueryResult;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct StocktakeCreateDTO {
+    category_id: Option<u64>,
+}
+
+impl StocktakeCreateDTO {
+    pub fn get_category_id(&self) -> &Option<u64> {
+        &self.category_id
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct StocktakeCountDTO {
+    product_id: u64,
+    quantity: u64,
+}
+
+impl StocktakeCountDTO {
+    pub fn get_product_id(&self) -> &u64 {
+        &self.product_id
+    }
+
+    pub fn get_quantity(&self) -> &u64 {
+        &self.quantity
+    }
+}
+
+#[derive(Serialize, Deserialize, FromQueryResult, ToSchema)]
+pub struct StocktakeViewDTO {
+    id: u64,
+    category: Option<String>,
+    status: String,
+    created_at: String,
+    closed_at: Option<String>,
+    products: u64,
+    counted_products: u64,
+}
+
+#[derive(Serialize, Deserialize, FromQueryResult, ToSchema)]
+pub struct StocktakeItemViewDTO {
+    product_id: u64,
+    product: String,
+    expected_quantity: u64,
+    quantity: u64,
+    counted_quantity: Option<u64>,
+    counters: u64,
+    variance: Option<i64>,
+}
+
+impl StocktakeItemViewDTO {
+    pub fn get_product_id(&self) -> &u64 {
+        &self.product_id
+    }
+
+    pub fn get_quantity(&self) -> &u64 {
+        &self.quantity
+    }
+
+    pub fn get_counted_quantity(&self) -> &Option<u64> {
+        &self.counted_quantity
+    }
+}
@@ -1,36 +1,55 @@
+use async_graphql::{InputObject, SimpleObject};
 use sea_orm::FromQueryResult;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-use crate::entities::enums::user_enums::UserRole;
+use crate::entities::{
+    dtos::{
+        export_dtos::{ExportCell, ExportRow},
+        generic_dtos::{deserialize_patch_nullable, deserialize_patch_value},
+    },
+    enums::export_enums::ExportLanguage,
+};
 
-#[derive(Serialize, Deserialize, FromQueryResult)]
-pub struct UserSummaryForAdminQueryDTO {
-    id: u64,
-    username: String,
-    email: String,
-    role: u8,
-}
-
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, FromQueryResult, SimpleObject, ToSchema)]
+#[graphql(name = "User")]
 pub struct UserSummaryForAdminDTO {
     id: u64,
     username: String,
     email: String,
     role: String,
+    active: bool,
+    created_at: String,
+    updated_at: String,
+    created_by: Option<String>,
+    updated_by: Option<String>,
 }
 
-impl From<UserSummaryForAdminQueryDTO> for UserSummaryForAdminDTO {
-    fn from(value: UserSummaryForAdminQueryDTO) -> Self {
-        Self {
-            id: value.id,
-            username: value.username,
-            email: value.email,
-            role: UserRole::code_to_string(value.role),
-        }
+impl ExportRow for UserSummaryForAdminDTO {
+    const HEADERS: &'static [(&'static str, &'static str)] = &[
+        ("ID", "ID"),
+        ("Usuário", "Username"),
+        ("E-mail", "Email"),
+        ("Perfil", "Role"),
+        ("Ativo", "Active"),
+    ];
+
+    fn into_cells(self, language: ExportLanguage) -> Vec<ExportCell> {
+        vec![
+            self.id.into(),
+            self.username.into(),
+            self.email.into(),
+            self.role.into(),
+            match self.active {
+                true => language.translate("Sim", "Yes").into(),
+                false => language.translate("Não", "No").into(),
+            },
+        ]
     }
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, InputObject, ToSchema)]
+#[graphql(name = "UserCreateInput")]
 pub struct UserCreateDTO {
     username: String,
     password: String,
@@ -61,7 +80,7 @@ impl UserCreateDTO {
     }
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 pub struct UserCredentialsUpdateDTO {
     old_password: String,
     new_password: String,
@@ -77,7 +96,7 @@ impl UserCredentialsUpdateDTO {
     }
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 pub struct UserInformationsUpdateDTO {
     username: String,
     email: String,
@@ -98,7 +117,96 @@ impl UserInformationsUpdateDTO {
     }
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, InputObject, ToSchema)]
+#[graphql(name = "UserUpdateInput")]
+pub struct UserAdminUpdateDTO {
+    username: String,
+    email: String,
+    role: u8,
+    phone: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct UserPatchDTO {
+    #[serde(default, deserialize_with = "deserialize_patch_value")]
+    #[schema(value_type = String, required = false)]
+    username: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_patch_value")]
+    #[schema(value_type = String, required = false)]
+    email: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_patch_value")]
+    #[schema(value_type = u8, required = false)]
+    role: Option<u8>,
+    #[serde(default, deserialize_with = "deserialize_patch_nullable")]
+    #[schema(value_type = Option<String>, required = false)]
+    phone: Option<Option<String>>,
+}
+
+impl UserPatchDTO {
+    pub fn get_username(&self) -> &Option<String> {
+        &self.username
+    }
+
+    pub fn get_email(&self) -> &Option<String> {
+        &self.email
+    }
+
+    pub fn get_role(&self) -> &Option<u8> {
+        &self.role
+    }
+
+    pub fn get_phone(&self) -> &Option<Option<String>> {
+        &self.phone
+    }
+}
+
+// PUT keeps its original rules, where empty strings and 0 leave the field unchanged
+impl From<UserAdminUpdateDTO> for UserPatchDTO {
+    fn from(value: UserAdminUpdateDTO) -> Self {
+        Self {
+            username: match value.username.trim().is_empty() {
+                true => None,
+                false => Some(value.username),
+            },
+            email: match value.email.trim().is_empty() {
+                true => None,
+                false => Some(value.email),
+            },
+            role: match value.role {
+                0 => None,
+                role => Some(role),
+            },
+            phone: match value.phone {
+                Some(phone) if !phone.trim().is_empty() => Some(Some(phone)),
+                _ => None,
+            },
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct UserPasswordResetDTO {
+    new_password: String,
+}
+
+impl UserPasswordResetDTO {
+    pub fn get_new_password(&self) -> &str {
+        &self.new_password
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct UserActiveUpdateDTO {
+    active: bool,
+}
+
+impl UserActiveUpdateDTO {
+    pub fn get_active(&self) -> &bool {
+        &self.active
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
 pub struct LoginDTO {
     email: String,
     password: String,
@@ -114,7 +222,7 @@ impl LoginDTO {
     }
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 pub struct ValidedTokenDTO {
     valided: bool,
 }
@@ -125,21 +233,106 @@ impl ValidedTokenDTO {
     }
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 pub struct AuthenticationDTO {
     token: String,
     role: u8,
     username: String,
     rolename: String,
+    permissions: Vec<String>,
 }
 
 impl AuthenticationDTO {
-    pub fn new(token: String, role: u8, username: String, role_name: String) -> Self {
+    pub fn new(
+        token: String,
+        role: u8,
+        username: String,
+        role_name: String,
+        permissions: Vec<String>,
+    ) -> Self {
         Self {
             token,
             role,
             username,
             rolename: role_name,
+            permissions,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct LoginChallengeDTO {
+    challenge_token: String,
+    enrollment_required: bool,
+}
+
+impl LoginChallengeDTO {
+    pub fn new(challenge_token: String, enrollment_required: bool) -> Self {
+        Self {
+            challenge_token,
+            enrollment_required,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum LoginResultDTO {
+    Authenticated(AuthenticationDTO),
+    Challenge(LoginChallengeDTO),
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct TwoFactorChallengeDTO {
+    challenge_token: String,
+}
+
+impl TwoFactorChallengeDTO {
+    pub fn get_challenge_token(&self) -> &str {
+        &self.challenge_token
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct TwoFactorLoginDTO {
+    challenge_token: String,
+    code: String,
+}
+
+impl TwoFactorLoginDTO {
+    pub fn get_challenge_token(&self) -> &str {
+        &self.challenge_token
+    }
+
+    pub fn get_code(&self) -> &str {
+        &self.code
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct TwoFactorCodeDTO {
+    code: String,
+}
+
+impl TwoFactorCodeDTO {
+    pub fn get_code(&self) -> &str {
+        &self.code
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct TwoFactorEnrollmentDTO {
+    secret: String,
+    provisioning_uri: String,
+    recovery_codes: Vec<String>,
+}
+
+impl TwoFactorEnrollmentDTO {
+    pub fn new(secret: String, provisioning_uri: String, recovery_codes: Vec<String>) -> Self {
+        Self {
+            secret,
+            provisioning_uri,
+            recovery_codes,
         }
     }
 }
@@ -0,0 +1,53 @@
+use async_graphql::Enum;
+use utoipa::ToSchema;
+
+#[derive(FromFormField, Clone, Copy, ToSchema)]
+#[schema(rename_all = "lowercase")]
+pub enum AnalyticsBucket {
+    Day,
+    Week,
+    Month,
+}
+
+#[derive(FromFormField, Clone, Copy, ToSchema)]
+#[schema(rename_all = "lowercase")]
+pub enum AnalyticsGroup {
+    Product,
+    Category,
+}
+
+#[derive(FromFormField, Clone, Copy, ToSchema)]
+#[schema(rename_all = "snake_case")]
+pub enum ForecastMethod {
+    #[field(value = "moving_average")]
+    MovingAverage,
+    #[field(value = "exponential_smoothing")]
+    ExponentialSmoothing,
+}
+
+impl ForecastMethod {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "moving_average" => Some(ForecastMethod::MovingAverage),
+            "exponential_smoothing" => Some(ForecastMethod::ExponentialSmoothing),
+            _ => None,
+        }
+    }
+}
+
+#[derive(FromFormField, Enum, Clone, Copy, PartialEq, Eq, ToSchema)]
+pub enum AbcClass {
+    A,
+    B,
+    C,
+}
+
+impl AbcClass {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AbcClass::A => "A",
+            AbcClass::B => "B",
+            AbcClass::C => "C",
+        }
+    }
+}
@@ -0,0 +1,65 @@
+pub enum AuditEntity {
+    ApiKey,
+    Category,
+    Product,
+    Reason,
+    Reservation,
+    Role,
+    Stocktake,
+    User,
+}
+
+impl AuditEntity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditEntity::ApiKey => "api_key",
+            AuditEntity::Category => "category",
+            AuditEntity::Product => "product",
+            AuditEntity::Reason => "reason",
+            AuditEntity::Reservation => "reservation",
+            AuditEntity::Role => "role",
+            AuditEntity::Stocktake => "stocktake",
+            AuditEntity::User => "user",
+        }
+    }
+}
+
+pub enum AuditAction {
+    Create,
+    Update,
+    Delete,
+    Restore,
+    Purge,
+    Approve,
+    Cancel,
+    PasswordChange,
+    PasswordReset,
+    PermissionsUpdate,
+    Unlock,
+    TwoFactorEnable,
+    TwoFactorReset,
+    Revoke,
+    Release,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::Create => "create",
+            AuditAction::Update => "update",
+            AuditAction::Delete => "delete",
+            AuditAction::Restore => "restore",
+            AuditAction::Purge => "purge",
+            AuditAction::Approve => "approve",
+            AuditAction::Cancel => "cancel",
+            AuditAction::PasswordChange => "password_change",
+            AuditAction::PasswordReset => "password_reset",
+            AuditAction::PermissionsUpdate => "permissions_update",
+            AuditAction::Unlock => "unlock",
+            AuditAction::TwoFactorEnable => "two_factor_enable",
+            AuditAction::TwoFactorReset => "two_factor_reset",
+            AuditAction::Revoke => "revoke",
+            AuditAction::Release => "release",
+        }
+    }
+}
@@ -0,0 +1,18 @@
+#[derive(Clone, Copy)]
+pub enum StockEventType {
+    MovementCreated,
+    ProductUpdated,
+    ProductDeleted,
+    StockBelowMinimum,
+}
+
+impl StockEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StockEventType::MovementCreated => "movement.created",
+            StockEventType::ProductUpdated => "product.updated",
+            StockEventType::ProductDeleted => "product.deleted",
+            StockEventType::StockBelowMinimum => "stock.below_minimum",
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use utoipa::ToSchema;
+
+#[derive(FromFormField, Clone, Copy, ToSchema)]
+#[schema(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Csv,
+    Xlsx,
+}
+
+impl ExportFormat {
+    pub fn get_extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Xlsx => "xlsx",
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum ExportLanguage {
+    Portuguese,
+    English,
+}
+
+impl ExportLanguage {
+    pub fn translate(&self, portuguese: &'static str, english: &'static str) -> &'static str {
+        match self {
+            ExportLanguage::Portuguese => portuguese,
+            ExportLanguage::English => english,
+        }
+    }
+}
@@ -0,0 +1,13 @@
+pub enum ImportAction {
+    Create,
+    Update,
+}
+
+impl ImportAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportAction::Create => "create",
+            ImportAction::Update => "update",
+        }
+    }
+}
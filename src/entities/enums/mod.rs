@@ -1 +1,10 @@
-pub mod user_enums;
+pub mod analytics_enums;
+pub mod audit_enums;
+pub mod event_enums;
+pub mod export_enums;
+pub mod import_enums;
+pub mod permission_enums;
+pub mod reservation_enums;
+pub mod scanner_enums;
+pub mod sort_enums;
+pub mod stocktake_enums;
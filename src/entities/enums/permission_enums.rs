@@ -0,0 +1,39 @@
+pub trait Permission: Send + Sync + 'static {
+    const CODE: &'static str;
+}
+
+macro_rules! permissions {
+    ($($name:ident => $code:literal),* $(,)?) => {
+        $(
+            pub struct $name;
+
+            impl Permission for $name {
+                const CODE: &'static str = $code;
+            }
+        )*
+    };
+}
+
+permissions! {
+    ProductRead => "product.read",
+    ProductCreate => "product.create",
+    ProductUpdate => "product.update",
+    ProductDelete => "product.delete",
+    ProductImport => "product.import",
+    ProductClassify => "product.classify",
+    StockMove => "stock.move",
+    CategoryRead => "category.read",
+    CategoryManage => "category.manage",
+    ReasonRead => "reason.read",
+    ReasonManage => "reason.manage",
+    ReportRead => "report.read",
+    ReportReverse => "report.reverse",
+    UserManage => "user.manage",
+    UserSecurity => "user.security",
+    RoleManage => "role.manage",
+    ApiKeyManage => "apikey.manage",
+    AuditRead => "audit.read",
+    StocktakeManage => "stocktake.manage",
+    StocktakeCount => "stocktake.count",
+    ReservationManage => "reservation.manage",
+}
@@ -0,0 +1,17 @@
+pub enum ReservationStatus {
+    Active,
+    Consumed,
+    Released,
+    Expired,
+}
+
+impl ReservationStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReservationStatus::Active => "active",
+            ReservationStatus::Consumed => "consumed",
+            ReservationStatus::Released => "released",
+            ReservationStatus::Expired => "expired",
+        }
+    }
+}
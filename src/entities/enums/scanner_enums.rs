@@ -0,0 +1,14 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum ScannerDirection {
+    In,
+    Out,
+}
+
+impl ScannerDirection {
+    pub fn is_entry(&self) -> bool {
+        matches!(self, ScannerDirection::In)
+    }
+}
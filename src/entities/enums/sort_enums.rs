@@ -0,0 +1,31 @@
+use utoipa::ToSchema;
+
+#[derive(FromFormField, Clone, Copy, ToSchema)]
+#[schema(rename_all = "snake_case")]
+pub enum SortField {
+    #[field(value = "created_at")]
+    CreatedAt,
+    #[field(value = "updated_at")]
+    UpdatedAt,
+    #[field(value = "created_by")]
+    CreatedBy,
+    #[field(value = "updated_by")]
+    UpdatedBy,
+}
+
+#[derive(FromFormField, Clone, Copy, Default, ToSchema)]
+#[schema(rename_all = "lowercase")]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl SortOrder {
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            SortOrder::Asc => "ASC",
+            SortOrder::Desc => "DESC",
+        }
+    }
+}
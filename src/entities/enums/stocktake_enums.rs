@@ -0,0 +1,15 @@
+pub enum StocktakeStatus {
+    Open,
+    Approved,
+    Cancelled,
+}
+
+impl StocktakeStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StocktakeStatus::Open => "open",
+            StocktakeStatus::Approved => "approved",
+            StocktakeStatus::Cancelled => "cancelled",
+        }
+    }
+}
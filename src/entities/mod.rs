@@ -1,10 +1,50 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.19
 
+#![allow(clippy::enum_variant_names)]
+
+// Stamps the authorship columns on insert and update. `updated_by` is set by the services from
+// the actor, and writes made with `Entity::update` (stock movements, login counters) skip it
+macro_rules! authorship_behavior {
+    () => {
+        #[async_trait::async_trait]
+        impl ActiveModelBehavior for ActiveModel {
+            async fn before_save<C>(mut self, _db: &C, insert: bool) -> Result<Self, DbErr>
+            where
+                C: ConnectionTrait,
+            {
+                let now = chrono::Utc::now().naive_utc();
+
+                if insert {
+                    self.created_at = sea_orm::ActiveValue::Set(now);
+                    self.created_by = self.updated_by.clone();
+                }
+
+                self.updated_at = sea_orm::ActiveValue::Set(now);
+
+                Ok(self)
+            }
+        }
+    };
+}
+
+pub mod tb_api_key;
+pub mod tb_api_key_permission;
+pub mod tb_audit;
 pub mod tb_category;
+pub mod tb_login_attempt;
+pub mod tb_permission;
 pub mod tb_product;
 pub mod tb_reason;
 pub mod tb_report;
+pub mod tb_reservation;
+pub mod tb_role;
+pub mod tb_role_permission;
+pub mod tb_stocktake;
+pub mod tb_stocktake_count;
+pub mod tb_stocktake_item;
 pub mod tb_user;
+pub mod tb_user_permission;
+pub mod tb_user_recovery_code;
 
 pub mod dtos;
 pub mod enums;
@@ -1,20 +1,29 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.19
 
 use sea_orm::entity::prelude::*;
+use serde::Serialize;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
 #[sea_orm(table_name = "tb_category")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: u64,
     #[sea_orm(unique)]
     pub name: String,
+    pub version: u64,
+    pub created_at: DateTime,
+    pub updated_at: DateTime,
+    pub created_by: Option<u64>,
+    pub updated_by: Option<u64>,
+    pub deleted_at: Option<DateTime>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
     #[sea_orm(has_many = "super::tb_product::Entity")]
     TbProduct,
+    #[sea_orm(has_many = "super::tb_stocktake::Entity")]
+    TbStocktake,
 }
 
 impl Related<super::tb_product::Entity> for Entity {
@@ -23,4 +32,10 @@ impl Related<super::tb_product::Entity> for Entity {
     }
 }
 
-impl ActiveModelBehavior for ActiveModel {}
+impl Related<super::tb_stocktake::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TbStocktake.def()
+    }
+}
+
+authorship_behavior!();
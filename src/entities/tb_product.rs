@@ -1,18 +1,28 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.19
 
 use sea_orm::entity::prelude::*;
+use serde::Serialize;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
 #[sea_orm(table_name = "tb_product")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: u64,
     #[sea_orm(unique)]
     pub name: String,
+    #[sea_orm(unique)]
+    pub barcode: Option<String>,
     pub quantity: u64,
     pub min_quantity: u64,
     pub category_id: u64,
     pub description: Option<String>,
+    pub abc_class: Option<String>,
+    pub version: u64,
+    pub created_at: DateTime,
+    pub updated_at: DateTime,
+    pub created_by: Option<u64>,
+    pub updated_by: Option<u64>,
+    pub deleted_at: Option<DateTime>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -27,6 +37,10 @@ pub enum Relation {
     TbCategory,
     #[sea_orm(has_many = "super::tb_report::Entity")]
     TbReport,
+    #[sea_orm(has_many = "super::tb_reservation::Entity")]
+    TbReservation,
+    #[sea_orm(has_many = "super::tb_stocktake_item::Entity")]
+    TbStocktakeItem,
 }
 
 impl Related<super::tb_category::Entity> for Entity {
@@ -41,4 +55,16 @@ impl Related<super::tb_report::Entity> for Entity {
     }
 }
 
-impl ActiveModelBehavior for ActiveModel {}
+impl Related<super::tb_reservation::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TbReservation.def()
+    }
+}
+
+impl Related<super::tb_stocktake_item::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TbStocktakeItem.def()
+    }
+}
+
+authorship_behavior!();
@@ -1,14 +1,20 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.19
 
 use sea_orm::entity::prelude::*;
+use serde::Serialize;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
 #[sea_orm(table_name = "tb_reason")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: u64,
     #[sea_orm(unique)]
     pub name: String,
+    pub created_at: DateTime,
+    pub updated_at: DateTime,
+    pub created_by: Option<u64>,
+    pub updated_by: Option<u64>,
+    pub deleted_at: Option<DateTime>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -23,4 +29,4 @@ impl Related<super::tb_report::Entity> for Entity {
     }
 }
 
-impl ActiveModelBehavior for ActiveModel {}
+authorship_behavior!();
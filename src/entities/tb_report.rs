@@ -1,8 +1,9 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.19
 
 use sea_orm::entity::prelude::*;
+use serde::Serialize;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
 #[sea_orm(table_name = "tb_report")]
 pub struct Model {
     #[sea_orm(primary_key)]
@@ -12,16 +13,29 @@ pub struct Model {
     pub reason_id: u64,
     pub product_id: u64,
     pub change_type: i8,
+    pub user_id: Option<u64>,
+    pub api_key_id: Option<u64>,
+    #[sea_orm(unique)]
+    pub reversal_of: Option<u64>,
+    pub document: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::tb_api_key::Entity",
+        from = "Column::ApiKeyId",
+        to = "super::tb_api_key::Column::Id",
+        on_update = "Restrict",
+        on_delete = "SetNull"
+    )]
+    TbApiKey,
     #[sea_orm(
         belongs_to = "super::tb_product::Entity",
         from = "Column::ProductId",
         to = "super::tb_product::Column::Id",
         on_update = "Restrict",
-        on_delete = "Cascade"
+        on_delete = "Restrict"
     )]
     TbProduct,
     #[sea_orm(
@@ -32,6 +46,28 @@ pub enum Relation {
         on_delete = "NoAction"
     )]
     TbReason,
+    #[sea_orm(
+        belongs_to = "Entity",
+        from = "Column::ReversalOf",
+        to = "Column::Id",
+        on_update = "Restrict",
+        on_delete = "Restrict"
+    )]
+    SelfRef,
+    #[sea_orm(
+        belongs_to = "super::tb_user::Entity",
+        from = "Column::UserId",
+        to = "super::tb_user::Column::Id",
+        on_update = "Restrict",
+        on_delete = "SetNull"
+    )]
+    TbUser,
+}
+
+impl Related<super::tb_api_key::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TbApiKey.def()
+    }
 }
 
 impl Related<super::tb_product::Entity> for Entity {
@@ -46,4 +82,10 @@ impl Related<super::tb_reason::Entity> for Entity {
     }
 }
 
+impl Related<super::tb_user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TbUser.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
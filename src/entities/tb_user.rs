@@ -1,21 +1,90 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.19
 
 use sea_orm::entity::prelude::*;
+use serde::Serialize;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
 #[sea_orm(table_name = "tb_user")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: u64,
     pub username: String,
+    #[serde(skip_serializing)]
     pub password: String,
     #[sea_orm(unique)]
     pub email: String,
     pub role: u8,
     pub phone: Option<String>,
+    pub active: bool,
+    pub failed_attempts: u32,
+    pub locked_until: Option<DateTime>,
+    #[serde(skip_serializing)]
+    pub totp_secret: Option<String>,
+    pub totp_enabled: bool,
+    #[serde(skip_serializing)]
+    pub totp_last_step: Option<u64>,
+    pub created_at: DateTime,
+    pub updated_at: DateTime,
+    pub created_by: Option<u64>,
+    pub updated_by: Option<u64>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(has_many = "super::tb_audit::Entity")]
+    TbAudit,
+    #[sea_orm(
+        belongs_to = "super::tb_role::Entity",
+        from = "Column::Role",
+        to = "super::tb_role::Column::Id",
+        on_update = "Restrict",
+        on_delete = "Restrict"
+    )]
+    TbRole,
+    #[sea_orm(has_many = "super::tb_reservation::Entity")]
+    TbReservation,
+    #[sea_orm(has_many = "super::tb_stocktake_count::Entity")]
+    TbStocktakeCount,
+    #[sea_orm(has_many = "super::tb_user_permission::Entity")]
+    TbUserPermission,
+    #[sea_orm(has_many = "super::tb_user_recovery_code::Entity")]
+    TbUserRecoveryCode,
+}
+
+impl Related<super::tb_audit::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TbAudit.def()
+    }
+}
+
+impl Related<super::tb_role::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TbRole.def()
+    }
+}
+
+impl Related<super::tb_reservation::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TbReservation.def()
+    }
+}
+
+impl Related<super::tb_stocktake_count::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TbStocktakeCount.def()
+    }
+}
+
+impl Related<super::tb_user_permission::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TbUserPermission.def()
+    }
+}
+
+impl Related<super::tb_user_recovery_code::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TbUserRecoveryCode.def()
+    }
+}
 
-impl ActiveModelBehavior for ActiveModel {}
+authorship_behavior!();
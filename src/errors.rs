@@ -6,10 +6,19 @@ pub enum BackendError {
     ResourceAlreadyInsertedError,
     ResourceNotFoundError,
     ResourceConflitUpdateError,
+    ResourceLockedError,
+    ResourceVersionRequiredError,
+    ResourceStaleVersionError,
 
     NotEnoughtResourcers,
 
     InvalidCredentialsError,
+    TooManyAttemptsError,
+    InactiveUserError,
+    PermissionDeniedError,
 
     InvalidValuesError,
+    PayloadTooLargeError,
+
+    ExportError,
 }
@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+
+use async_graphql::{Error, dataloader::Loader};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+
+use crate::{
+    entities::{tb_api_key, tb_category, tb_product, tb_reason, tb_report, tb_user},
+    errors::BackendError,
+    graphql::graphql_schema::to_graphql_error,
+    services::{service_product, service_report},
+};
+
+macro_rules! model_loader {
+    ($($loader:ident => $entity:ident),* $(,)?) => {
+        $(
+            pub struct $loader(DatabaseConnection);
+
+            impl $loader {
+                pub fn new(database: DatabaseConnection) -> Self {
+                    Self(database)
+                }
+            }
+
+            impl Loader<u64> for $loader {
+                type Value = $entity::Model;
+                type Error = Error;
+
+                async fn load(&self, keys: &[u64]) -> Result<HashMap<u64, Self::Value>, Self::Error> {
+                    let result = $entity::Entity::find()
+                        .filter($entity::Column::Id.is_in(keys.to_vec()))
+                        .all(&self.0)
+                        .await;
+
+                    match result {
+                        Ok(models) => Ok(models.into_iter().map(|model| (model.id, model)).collect()),
+                        Err(db_err) => Err(to_graphql_error(BackendError::DatabaseError(db_err))),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+model_loader! {
+    CategoryLoader => tb_category,
+    ProductLoader => tb_product,
+    ReasonLoader => tb_reason,
+    UserLoader => tb_user,
+    ApiKeyLoader => tb_api_key,
+}
+
+pub struct AvailableLoader(DatabaseConnection);
+
+impl AvailableLoader {
+    pub fn new(database: DatabaseConnection) -> Self {
+        Self(database)
+    }
+}
+
+impl Loader<u64> for AvailableLoader {
+    type Value = u64;
+    type Error = Error;
+
+    async fn load(&self, keys: &[u64]) -> Result<HashMap<u64, Self::Value>, Self::Error> {
+        match service_product::find_available_by_ids(&self.0, keys).await {
+            Ok(available) => Ok(available),
+            Err(backend_error) => Err(to_graphql_error(backend_error)),
+        }
+    }
+}
+
+pub struct MovementLoader(DatabaseConnection);
+
+impl MovementLoader {
+    pub fn new(database: DatabaseConnection) -> Self {
+        Self(database)
+    }
+}
+
+impl Loader<(u64, u64)> for MovementLoader {
+    type Value = Vec<tb_report::Model>;
+    type Error = Error;
+
+    async fn load(
+        &self,
+        keys: &[(u64, u64)],
+    ) -> Result<HashMap<(u64, u64), Self::Value>, Self::Error> {
+        // Products asked with the same limit are loaded together in a single query
+        let mut product_ids_by_limit: HashMap<u64, Vec<u64>> = HashMap::new();
+
+        for (product_id, limit) in keys {
+            product_ids_by_limit
+                .entry(*limit)
+                .or_default()
+                .push(*product_id);
+        }
+
+        let mut movements: HashMap<(u64, u64), Self::Value> =
+            keys.iter().map(|key| (*key, Vec::new())).collect();
+
+        for (limit, product_ids) in product_ids_by_limit {
+            let reports = match service_report::find_recent_by_product_ids(
+                &self.0,
+                &product_ids,
+                limit,
+            )
+            .await
+            {
+                Ok(reports) => reports,
+                Err(backend_error) => return Err(to_graphql_error(backend_error)),
+            };
+
+            for report in reports {
+                movements
+                    .entry((report.product_id, limit))
+                    .or_default()
+                    .push(report);
+            }
+        }
+
+        Ok(movements)
+    }
+}
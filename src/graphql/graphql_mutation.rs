@@ -0,0 +1,200 @@
+use async_graphql::{Context, Error, Object};
+
+use crate::{
+    entities::{
+        dtos::{
+            category_dtos::{CategoryCreateDTO, CategoryDTO},
+            product_dtos::{ProductChangeQuantityDTO, ProductCreateDTO, ProductUpdateDTO},
+            reason_dtos::{ReasonCreateDTO, ReasonDTO},
+            user_dtos::{UserAdminUpdateDTO, UserCreateDTO},
+        },
+        enums::permission_enums::{
+            CategoryManage, ProductCreate, ProductUpdate, ReasonManage, StockMove, UserManage,
+        },
+    },
+    errors::BackendError,
+    graphql::graphql_schema::{authorize, get_database, to_graphql_error},
+    services::{service_category, service_product, service_reason, service_user},
+};
+
+pub struct MutationRoot;
+
+#[Object]
+impl MutationRoot {
+    async fn create_product(
+        &self,
+        ctx: &Context<'_>,
+        input: ProductCreateDTO,
+    ) -> Result<bool, Error> {
+        let caller = match authorize::<ProductCreate>(ctx) {
+            Ok(caller) => caller,
+            Err(error) => return Err(error),
+        };
+
+        let result = service_product::create_product(
+            get_database(ctx),
+            input,
+            caller.get_authentication().clone(),
+        )
+        .await;
+
+        to_mutation_result(result)
+    }
+
+    async fn update_product(
+        &self,
+        ctx: &Context<'_>,
+        input: ProductUpdateDTO,
+    ) -> Result<bool, Error> {
+        let caller = match authorize::<ProductUpdate>(ctx) {
+            Ok(caller) => caller,
+            Err(error) => return Err(error),
+        };
+
+        let result = service_product::update_product(
+            get_database(ctx),
+            input,
+            caller.get_authentication().clone(),
+        )
+        .await;
+
+        to_mutation_result(result)
+    }
+
+    async fn change_product_quantity(
+        &self,
+        ctx: &Context<'_>,
+        input: ProductChangeQuantityDTO,
+    ) -> Result<bool, Error> {
+        let caller = match authorize::<StockMove>(ctx) {
+            Ok(caller) => caller,
+            Err(error) => return Err(error),
+        };
+
+        let result = service_product::change_quantity(
+            get_database(ctx),
+            input,
+            caller.get_authentication().clone(),
+        )
+        .await;
+
+        to_mutation_result(result)
+    }
+
+    async fn create_category(
+        &self,
+        ctx: &Context<'_>,
+        input: CategoryCreateDTO,
+    ) -> Result<bool, Error> {
+        let caller = match authorize::<CategoryManage>(ctx) {
+            Ok(caller) => caller,
+            Err(error) => return Err(error),
+        };
+
+        let result = service_category::create_category(
+            get_database(ctx),
+            input,
+            caller.get_authentication().clone(),
+        )
+        .await;
+
+        to_mutation_result(result)
+    }
+
+    async fn update_category(&self, ctx: &Context<'_>, input: CategoryDTO) -> Result<bool, Error> {
+        let caller = match authorize::<CategoryManage>(ctx) {
+            Ok(caller) => caller,
+            Err(error) => return Err(error),
+        };
+
+        let result = service_category::update_category(
+            get_database(ctx),
+            input,
+            caller.get_authentication().clone(),
+        )
+        .await;
+
+        to_mutation_result(result)
+    }
+
+    async fn create_reason(
+        &self,
+        ctx: &Context<'_>,
+        input: ReasonCreateDTO,
+    ) -> Result<bool, Error> {
+        let caller = match authorize::<ReasonManage>(ctx) {
+            Ok(caller) => caller,
+            Err(error) => return Err(error),
+        };
+
+        let result = service_reason::create_reason(
+            get_database(ctx),
+            input,
+            caller.get_authentication().clone(),
+        )
+        .await;
+
+        to_mutation_result(result)
+    }
+
+    async fn update_reason(&self, ctx: &Context<'_>, input: ReasonDTO) -> Result<bool, Error> {
+        let caller = match authorize::<ReasonManage>(ctx) {
+            Ok(caller) => caller,
+            Err(error) => return Err(error),
+        };
+
+        let result = service_reason::update_reason(
+            get_database(ctx),
+            input,
+            caller.get_authentication().clone(),
+        )
+        .await;
+
+        to_mutation_result(result)
+    }
+
+    async fn create_user(&self, ctx: &Context<'_>, input: UserCreateDTO) -> Result<bool, Error> {
+        let caller = match authorize::<UserManage>(ctx) {
+            Ok(caller) => caller,
+            Err(error) => return Err(error),
+        };
+
+        let result = service_user::create_user(
+            get_database(ctx),
+            input,
+            caller.get_authentication().clone(),
+        )
+        .await;
+
+        to_mutation_result(result)
+    }
+
+    async fn update_user(
+        &self,
+        ctx: &Context<'_>,
+        id: u64,
+        input: UserAdminUpdateDTO,
+    ) -> Result<bool, Error> {
+        let caller = match authorize::<UserManage>(ctx) {
+            Ok(caller) => caller,
+            Err(error) => return Err(error),
+        };
+
+        let result = service_user::update_user(
+            get_database(ctx),
+            id,
+            input,
+            caller.get_authentication().clone(),
+        )
+        .await;
+
+        to_mutation_result(result)
+    }
+}
+
+fn to_mutation_result(result: Result<(), BackendError>) -> Result<bool, Error> {
+    match result {
+        Ok(_) => Ok(true),
+        Err(backend_error) => Err(to_graphql_error(backend_error)),
+    }
+}
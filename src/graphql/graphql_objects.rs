@@ -0,0 +1,300 @@
+use async_graphql::{Context, Error, Object, dataloader::DataLoader};
+
+use crate::{
+    entities::{
+        enums::permission_enums::{ProductRead, ReportRead, UserManage},
+        tb_category, tb_product, tb_reason, tb_report,
+    },
+    errors::BackendError,
+    graphql::{
+        graphql_loaders::{
+            ApiKeyLoader, AvailableLoader, CategoryLoader, MovementLoader, ProductLoader,
+            ReasonLoader, UserLoader,
+        },
+        graphql_schema::{authorize, to_graphql_error},
+    },
+    services::service_report,
+};
+
+const DEFAULT_MOVEMENTS: u64 = 10;
+const MAX_MOVEMENTS: u64 = 100;
+
+pub struct CategoryObject(tb_category::Model);
+
+impl From<tb_category::Model> for CategoryObject {
+    fn from(model: tb_category::Model) -> Self {
+        Self(model)
+    }
+}
+
+#[Object(name = "Category")]
+impl CategoryObject {
+    async fn id(&self) -> u64 {
+        self.0.id
+    }
+
+    async fn name(&self) -> &String {
+        &self.0.name
+    }
+
+    async fn version(&self) -> u64 {
+        self.0.version
+    }
+
+    async fn created_at(&self) -> String {
+        self.0.created_at.to_string()
+    }
+
+    async fn updated_at(&self) -> String {
+        self.0.updated_at.to_string()
+    }
+
+    async fn created_by(&self, ctx: &Context<'_>) -> Result<Option<String>, Error> {
+        load_username(ctx, self.0.created_by).await
+    }
+
+    async fn updated_by(&self, ctx: &Context<'_>) -> Result<Option<String>, Error> {
+        load_username(ctx, self.0.updated_by).await
+    }
+
+    async fn deleted_at(&self) -> Option<String> {
+        self.0.deleted_at.map(|deleted_at| deleted_at.to_string())
+    }
+}
+
+pub struct ReasonObject(tb_reason::Model);
+
+impl From<tb_reason::Model> for ReasonObject {
+    fn from(model: tb_reason::Model) -> Self {
+        Self(model)
+    }
+}
+
+#[Object(name = "Reason")]
+impl ReasonObject {
+    async fn id(&self) -> u64 {
+        self.0.id
+    }
+
+    async fn name(&self) -> &String {
+        &self.0.name
+    }
+
+    async fn created_at(&self) -> String {
+        self.0.created_at.to_string()
+    }
+
+    async fn updated_at(&self) -> String {
+        self.0.updated_at.to_string()
+    }
+
+    async fn created_by(&self, ctx: &Context<'_>) -> Result<Option<String>, Error> {
+        load_username(ctx, self.0.created_by).await
+    }
+
+    async fn updated_by(&self, ctx: &Context<'_>) -> Result<Option<String>, Error> {
+        load_username(ctx, self.0.updated_by).await
+    }
+
+    async fn deleted_at(&self) -> Option<String> {
+        self.0.deleted_at.map(|deleted_at| deleted_at.to_string())
+    }
+}
+
+pub struct ProductObject(tb_product::Model);
+
+impl From<tb_product::Model> for ProductObject {
+    fn from(model: tb_product::Model) -> Self {
+        Self(model)
+    }
+}
+
+#[Object(name = "Product")]
+impl ProductObject {
+    async fn id(&self) -> u64 {
+        self.0.id
+    }
+
+    async fn name(&self) -> &String {
+        &self.0.name
+    }
+
+    async fn barcode(&self) -> &Option<String> {
+        &self.0.barcode
+    }
+
+    async fn quantity(&self) -> u64 {
+        self.0.quantity
+    }
+
+    async fn available(&self, ctx: &Context<'_>) -> Result<u64, Error> {
+        let loader = ctx.data_unchecked::<DataLoader<AvailableLoader>>();
+
+        match loader.load_one(self.0.id).await {
+            Ok(available) => Ok(available.unwrap_or(self.0.quantity)),
+            Err(error) => Err(error),
+        }
+    }
+
+    async fn min_quantity(&self) -> u64 {
+        self.0.min_quantity
+    }
+
+    async fn description(&self) -> &Option<String> {
+        &self.0.description
+    }
+
+    async fn abc_class(&self) -> &Option<String> {
+        &self.0.abc_class
+    }
+
+    async fn version(&self) -> u64 {
+        self.0.version
+    }
+
+    async fn created_at(&self) -> String {
+        self.0.created_at.to_string()
+    }
+
+    async fn updated_at(&self) -> String {
+        self.0.updated_at.to_string()
+    }
+
+    async fn created_by(&self, ctx: &Context<'_>) -> Result<Option<String>, Error> {
+        load_username(ctx, self.0.created_by).await
+    }
+
+    async fn updated_by(&self, ctx: &Context<'_>) -> Result<Option<String>, Error> {
+        load_username(ctx, self.0.updated_by).await
+    }
+
+    async fn deleted_at(&self) -> Option<String> {
+        self.0.deleted_at.map(|deleted_at| deleted_at.to_string())
+    }
+
+    async fn category(&self, ctx: &Context<'_>) -> Result<CategoryObject, Error> {
+        let loader = ctx.data_unchecked::<DataLoader<CategoryLoader>>();
+
+        match loader.load_one(self.0.category_id).await {
+            Ok(Some(category)) => Ok(category.into()),
+            Ok(None) => Err(to_graphql_error(BackendError::ResourceNotFoundError)),
+            Err(error) => Err(error),
+        }
+    }
+
+    async fn movements(
+        &self,
+        ctx: &Context<'_>,
+        limit: Option<u64>,
+    ) -> Result<Vec<ReportObject>, Error> {
+        authorize::<ReportRead>(ctx)?;
+
+        let limit = limit.unwrap_or(DEFAULT_MOVEMENTS);
+
+        if limit == 0 || limit > MAX_MOVEMENTS {
+            return Err(to_graphql_error(BackendError::InvalidValuesError));
+        }
+
+        let loader = ctx.data_unchecked::<DataLoader<MovementLoader>>();
+
+        match loader.load_one((self.0.id, limit)).await {
+            Ok(reports) => Ok(reports
+                .unwrap_or_default()
+                .into_iter()
+                .map(ReportObject::from)
+                .collect()),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+pub struct ReportObject(tb_report::Model);
+
+impl From<tb_report::Model> for ReportObject {
+    fn from(model: tb_report::Model) -> Self {
+        Self(model)
+    }
+}
+
+#[Object(name = "Report")]
+impl ReportObject {
+    async fn id(&self) -> u64 {
+        self.0.id
+    }
+
+    async fn change_type(&self) -> bool {
+        self.0.change_type != 0
+    }
+
+    async fn quantity(&self) -> u64 {
+        self.0.quantity
+    }
+
+    async fn date(&self) -> String {
+        service_report::format_local_date(&self.0.date)
+    }
+
+    async fn document(&self) -> &Option<String> {
+        &self.0.document
+    }
+
+    async fn reversal_of(&self) -> Option<u64> {
+        self.0.reversal_of
+    }
+
+    async fn product(&self, ctx: &Context<'_>) -> Result<ProductObject, Error> {
+        authorize::<ProductRead>(ctx)?;
+
+        let loader = ctx.data_unchecked::<DataLoader<ProductLoader>>();
+
+        match loader.load_one(self.0.product_id).await {
+            Ok(Some(product)) => Ok(product.into()),
+            Ok(None) => Err(to_graphql_error(BackendError::ResourceNotFoundError)),
+            Err(error) => Err(error),
+        }
+    }
+
+    async fn reason(&self, ctx: &Context<'_>) -> Result<ReasonObject, Error> {
+        let loader = ctx.data_unchecked::<DataLoader<ReasonLoader>>();
+
+        match loader.load_one(self.0.reason_id).await {
+            Ok(Some(reason)) => Ok(reason.into()),
+            Ok(None) => Err(to_graphql_error(BackendError::ResourceNotFoundError)),
+            Err(error) => Err(error),
+        }
+    }
+
+    async fn user(&self, ctx: &Context<'_>) -> Result<Option<String>, Error> {
+        load_username(ctx, self.0.user_id).await
+    }
+
+    async fn api_key(&self, ctx: &Context<'_>) -> Result<Option<String>, Error> {
+        let api_key_id = match self.0.api_key_id {
+            Some(api_key_id) => api_key_id,
+            None => return Ok(None),
+        };
+
+        let loader = ctx.data_unchecked::<DataLoader<ApiKeyLoader>>();
+
+        match loader.load_one(api_key_id).await {
+            Ok(api_key) => Ok(api_key.map(|api_key| api_key.name)),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+async fn load_username(ctx: &Context<'_>, user_id: Option<u64>) -> Result<Option<String>, Error> {
+    authorize::<UserManage>(ctx)?;
+
+    let user_id = match user_id {
+        Some(user_id) => user_id,
+        None => return Ok(None),
+    };
+
+    let loader = ctx.data_unchecked::<DataLoader<UserLoader>>();
+
+    match loader.load_one(user_id).await {
+        Ok(user) => Ok(user.map(|user| user.username)),
+        Err(error) => Err(error),
+    }
+}
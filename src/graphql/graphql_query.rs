@@ -0,0 +1,154 @@
+use async_graphql::{Context, Error, Object, dataloader::DataLoader};
+
+use crate::{
+    entities::{
+        dtos::{generic_dtos::SortDTO, user_dtos::UserSummaryForAdminDTO},
+        enums::{
+            analytics_enums::AbcClass,
+            permission_enums::{
+                CategoryManage, CategoryRead, Permission, ProductDelete, ProductRead, ReasonManage,
+                ReasonRead, ReportRead, UserManage,
+            },
+        },
+    },
+    errors::BackendError,
+    graphql::{
+        graphql_loaders::ProductLoader,
+        graphql_objects::{CategoryObject, ProductObject, ReasonObject, ReportObject},
+        graphql_schema::{GraphQLCaller, authorize, get_database, to_graphql_error},
+    },
+    services::{service_category, service_product, service_reason, service_report, service_user},
+};
+
+const DEFAULT_REPORTS: u64 = 100;
+const MAX_REPORTS: u64 = 1000;
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn categories(
+        &self,
+        ctx: &Context<'_>,
+        include_deleted: Option<bool>,
+    ) -> Result<Vec<CategoryObject>, Error> {
+        let caller = match authorize::<CategoryRead>(ctx) {
+            Ok(caller) => caller,
+            Err(error) => return Err(error),
+        };
+
+        let include_deleted =
+            match resolve_include_deleted::<CategoryManage>(caller, include_deleted) {
+                Ok(include_deleted) => include_deleted,
+                Err(error) => return Err(error),
+            };
+
+        match service_category::find_all(get_database(ctx), include_deleted).await {
+            Ok(categories) => Ok(categories.into_iter().map(CategoryObject::from).collect()),
+            Err(backend_error) => Err(to_graphql_error(backend_error)),
+        }
+    }
+
+    async fn products(
+        &self,
+        ctx: &Context<'_>,
+        include_deleted: Option<bool>,
+        category_id: Option<u64>,
+        abc_class: Option<AbcClass>,
+    ) -> Result<Vec<ProductObject>, Error> {
+        let caller = match authorize::<ProductRead>(ctx) {
+            Ok(caller) => caller,
+            Err(error) => return Err(error),
+        };
+
+        let include_deleted =
+            match resolve_include_deleted::<ProductDelete>(caller, include_deleted) {
+                Ok(include_deleted) => include_deleted,
+                Err(error) => return Err(error),
+            };
+
+        let result =
+            service_product::find_all(get_database(ctx), include_deleted, category_id, abc_class)
+                .await;
+
+        match result {
+            Ok(products) => Ok(products.into_iter().map(ProductObject::from).collect()),
+            Err(backend_error) => Err(to_graphql_error(backend_error)),
+        }
+    }
+
+    async fn product(&self, ctx: &Context<'_>, id: u64) -> Result<Option<ProductObject>, Error> {
+        authorize::<ProductRead>(ctx)?;
+
+        let loader = ctx.data_unchecked::<DataLoader<ProductLoader>>();
+
+        match loader.load_one(id).await {
+            Ok(product) => Ok(product
+                .filter(|product| product.deleted_at.is_none())
+                .map(ProductObject::from)),
+            Err(error) => Err(error),
+        }
+    }
+
+    async fn reasons(
+        &self,
+        ctx: &Context<'_>,
+        include_deleted: Option<bool>,
+    ) -> Result<Vec<ReasonObject>, Error> {
+        let caller = match authorize::<ReasonRead>(ctx) {
+            Ok(caller) => caller,
+            Err(error) => return Err(error),
+        };
+
+        let include_deleted = match resolve_include_deleted::<ReasonManage>(caller, include_deleted)
+        {
+            Ok(include_deleted) => include_deleted,
+            Err(error) => return Err(error),
+        };
+
+        match service_reason::find_all(get_database(ctx), include_deleted).await {
+            Ok(reasons) => Ok(reasons.into_iter().map(ReasonObject::from).collect()),
+            Err(backend_error) => Err(to_graphql_error(backend_error)),
+        }
+    }
+
+    async fn reports(
+        &self,
+        ctx: &Context<'_>,
+        product_id: Option<u64>,
+        limit: Option<u64>,
+    ) -> Result<Vec<ReportObject>, Error> {
+        authorize::<ReportRead>(ctx)?;
+
+        let limit = limit.unwrap_or(DEFAULT_REPORTS);
+
+        if limit == 0 || limit > MAX_REPORTS {
+            return Err(to_graphql_error(BackendError::InvalidValuesError));
+        }
+
+        match service_report::find_all(get_database(ctx), product_id, limit).await {
+            Ok(reports) => Ok(reports.into_iter().map(ReportObject::from).collect()),
+            Err(backend_error) => Err(to_graphql_error(backend_error)),
+        }
+    }
+
+    async fn users(&self, ctx: &Context<'_>) -> Result<Vec<UserSummaryForAdminDTO>, Error> {
+        authorize::<UserManage>(ctx)?;
+
+        match service_user::get_all_users(get_database(ctx), &SortDTO::default()).await {
+            Ok(users) => Ok(users),
+            Err(backend_error) => Err(to_graphql_error(backend_error)),
+        }
+    }
+}
+
+fn resolve_include_deleted<P: Permission>(
+    caller: &GraphQLCaller,
+    include_deleted: Option<bool>,
+) -> Result<bool, Error> {
+    match (include_deleted, caller.has_permission::<P>()) {
+        (Some(true), true) => Ok(true),
+        (Some(true), false) => Err(to_graphql_error(BackendError::PermissionDeniedError)),
+        _ => Ok(false),
+    }
+}
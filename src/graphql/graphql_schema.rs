@@ -0,0 +1,108 @@
+use async_graphql::{
+    Context, EmptySubscription, Error, ErrorExtensions, Request, Schema, dataloader::DataLoader,
+};
+use rocket::{response::status::Custom, tokio};
+use sea_orm::DatabaseConnection;
+
+use crate::{
+    entities::enums::permission_enums::Permission,
+    errors::BackendError,
+    graphql::{
+        graphql_loaders::{
+            ApiKeyLoader, AvailableLoader, CategoryLoader, MovementLoader, ProductLoader,
+            ReasonLoader, UserLoader,
+        },
+        graphql_mutation::MutationRoot,
+        graphql_query::QueryRoot,
+    },
+    guards::guard_user::AuthenticationGuard,
+    routes::generic_functions::catch_backend_error,
+};
+
+pub type GraphQLSchema = Schema<QueryRoot, MutationRoot, EmptySubscription>;
+
+const MAX_DEPTH: usize = 8;
+const MAX_COMPLEXITY: usize = 500;
+
+pub struct GraphQLCaller {
+    authentication: AuthenticationGuard,
+    permissions: Vec<String>,
+}
+
+impl GraphQLCaller {
+    pub fn get_authentication(&self) -> &AuthenticationGuard {
+        &self.authentication
+    }
+
+    pub fn has_permission<P: Permission>(&self) -> bool {
+        self.permissions.iter().any(|name| name == P::CODE)
+    }
+}
+
+pub fn make_schema() -> GraphQLSchema {
+    Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+        .limit_depth(MAX_DEPTH)
+        .limit_complexity(MAX_COMPLEXITY)
+        .finish()
+}
+
+pub fn prepare_request(
+    request: Request,
+    database: &DatabaseConnection,
+    authentication: AuthenticationGuard,
+    permissions: Vec<String>,
+) -> Request {
+    request
+        .data(database.clone())
+        .data(GraphQLCaller {
+            authentication,
+            permissions,
+        })
+        .data(DataLoader::new(
+            CategoryLoader::new(database.clone()),
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(
+            ProductLoader::new(database.clone()),
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(
+            ReasonLoader::new(database.clone()),
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(
+            UserLoader::new(database.clone()),
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(
+            ApiKeyLoader::new(database.clone()),
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(
+            AvailableLoader::new(database.clone()),
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(
+            MovementLoader::new(database.clone()),
+            tokio::spawn,
+        ))
+}
+
+pub fn authorize<'a, P: Permission>(ctx: &Context<'a>) -> Result<&'a GraphQLCaller, Error> {
+    let caller = ctx.data_unchecked::<GraphQLCaller>();
+
+    match caller.has_permission::<P>() {
+        true => Ok(caller),
+        false => Err(to_graphql_error(BackendError::PermissionDeniedError)),
+    }
+}
+
+pub fn get_database<'a>(ctx: &Context<'a>) -> &'a DatabaseConnection {
+    ctx.data_unchecked::<DatabaseConnection>()
+}
+
+pub fn to_graphql_error(backend_error: BackendError) -> Error {
+    let Custom(status, message) = catch_backend_error(backend_error);
+
+    Error::new(message).extend_with(|_, extensions| extensions.set("status", status.code))
+}
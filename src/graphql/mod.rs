@@ -0,0 +1,5 @@
+pub mod graphql_loaders;
+pub mod graphql_mutation;
+pub mod graphql_objects;
+pub mod graphql_query;
+pub mod graphql_schema;
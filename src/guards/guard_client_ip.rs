@@ -0,0 +1,40 @@
+use std::net::IpAddr;
+
+use rocket::request::{FromRequest, Outcome, Request};
+
+use crate::configs::config_environment::get_trusted_ip_header;
+
+pub struct ClientIpGuard(Option<IpAddr>);
+
+impl ClientIpGuard {
+    pub fn get_ip(&self) -> Option<IpAddr> {
+        self.0
+    }
+}
+
+#[async_trait]
+impl<'r> FromRequest<'r> for ClientIpGuard {
+    type Error = &'static str;
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let remote_ip = req.remote().map(|remote| remote.ip());
+
+        // Client headers are only trusted when a proxy in front of the API is configured to set them
+        let header = match get_trusted_ip_header() {
+            Some(header) => header,
+            None => return Outcome::Success(ClientIpGuard(remote_ip)),
+        };
+
+        // The proxy appends the address it saw, so the last entry is the one it vouches for
+        let header_ip = req
+            .headers()
+            .get_one(&header)
+            .and_then(|value| value.rsplit(',').next())
+            .and_then(|ip| ip.trim().parse::<IpAddr>().ok());
+
+        match header_ip {
+            Some(ip) => Outcome::Success(ClientIpGuard(Some(ip))),
+            None => Outcome::Success(ClientIpGuard(remote_ip)),
+        }
+    }
+}
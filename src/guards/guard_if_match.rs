@@ -0,0 +1,45 @@
+use rocket::{
+    http::Status,
+    request::{FromRequest, Outcome, Request},
+};
+
+pub enum IfMatchGuard {
+    Absent,
+    Any,
+    Version(u64),
+}
+
+#[async_trait]
+impl<'r> FromRequest<'r> for IfMatchGuard {
+    type Error = &'static str;
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let if_match = match req.headers().get_one("If-Match") {
+            Some(if_match) => if_match.trim(),
+            None => return Outcome::Success(IfMatchGuard::Absent),
+        };
+
+        if if_match == "*" {
+            return Outcome::Success(IfMatchGuard::Any);
+        }
+
+        // If-Match uses strong comparison, so a weak tag never matches the strong version ETag
+        if if_match.starts_with("W/") {
+            return Outcome::Error((
+                Status::PreconditionFailed,
+                "O header If-Match não aceita ETags fracos",
+            ));
+        }
+
+        // Versions are sent as strong entity tags, e.g. `"3"`
+        let version = if_match
+            .strip_prefix('"')
+            .and_then(|if_match| if_match.strip_suffix('"'))
+            .and_then(|version| version.parse::<u64>().ok());
+
+        match version {
+            Some(version) => Outcome::Success(IfMatchGuard::Version(version)),
+            None => Outcome::Error((Status::BadRequest, "O header If-Match é inválido")),
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use rocket::request::{FromRequest, Outcome, Request};
+
+use crate::entities::enums::export_enums::ExportLanguage;
+
+pub struct LanguageGuard(ExportLanguage);
+
+impl LanguageGuard {
+    pub fn get_language(&self) -> ExportLanguage {
+        self.0
+    }
+}
+
+#[async_trait]
+impl<'r> FromRequest<'r> for LanguageGuard {
+    type Error = &'static str;
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let language = req
+            .headers()
+            .get_one("Accept-Language")
+            .and_then(|accept_language| accept_language.split(',').next())
+            .map(|language| language.trim().to_lowercase());
+
+        match language {
+            Some(language) if language.starts_with("en") => {
+                Outcome::Success(LanguageGuard(ExportLanguage::English))
+            }
+            _ => Outcome::Success(LanguageGuard(ExportLanguage::Portuguese)),
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use std::marker::PhantomData;
+
+use rocket::{
+    State,
+    http::Status,
+    request::{FromRequest, Outcome, Request},
+};
+use sea_orm::DatabaseConnection;
+
+use crate::{
+    configs::config_jwt,
+    entities::enums::permission_enums::Permission,
+    errors::BackendError,
+    guards::guard_user::AuthenticationGuard,
+    services::{service_role, service_user},
+};
+
+pub struct PermissionGuard<P: Permission>(PhantomData<P>);
+
+pub struct PermissionListGuard(Vec<String>);
+
+impl PermissionListGuard {
+    pub fn into_permissions(self) -> Vec<String> {
+        self.0
+    }
+}
+
+struct RequestPermissions(Result<Vec<String>, (Status, &'static str)>);
+
+#[async_trait]
+impl<'r, P: Permission> FromRequest<'r> for PermissionGuard<P> {
+    type Error = &'static str;
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        match request_permissions(req).await {
+            Outcome::Success(permissions) => match permissions.iter().any(|name| name == P::CODE) {
+                true => Outcome::Success(PermissionGuard(PhantomData)),
+                false => Outcome::Error((
+                    Status::Forbidden,
+                    "O usuário não possuí permissão para realizar a operação",
+                )),
+            },
+            Outcome::Error(error) => Outcome::Error(error),
+            Outcome::Forward(status) => Outcome::Forward(status),
+        }
+    }
+}
+
+#[async_trait]
+impl<'r> FromRequest<'r> for PermissionListGuard {
+    type Error = &'static str;
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        match request_permissions(req).await {
+            Outcome::Success(permissions) => {
+                Outcome::Success(PermissionListGuard(permissions.clone()))
+            }
+            Outcome::Error(error) => Outcome::Error(error),
+            Outcome::Forward(status) => Outcome::Forward(status),
+        }
+    }
+}
+
+async fn request_permissions<'r>(req: &'r Request<'_>) -> Outcome<&'r Vec<String>, &'static str> {
+    let authentication = match req.guard::<AuthenticationGuard>().await {
+        Outcome::Success(authentication) => authentication,
+        Outcome::Error(error) => return Outcome::Error(error),
+        Outcome::Forward(status) => return Outcome::Forward(status),
+    };
+
+    let permissions = req
+        .local_cache_async(async {
+            RequestPermissions(find_request_permissions(req, &authentication).await)
+        })
+        .await;
+
+    match &permissions.0 {
+        Ok(permissions) => Outcome::Success(permissions),
+        Err(error) => Outcome::Error(*error),
+    }
+}
+
+async fn find_request_permissions(
+    req: &Request<'_>,
+    authentication: &AuthenticationGuard,
+) -> Result<Vec<String>, (Status, &'static str)> {
+    let database = match req.guard::<&State<DatabaseConnection>>().await {
+        Outcome::Success(database) => database,
+        _ => return Err((Status::InternalServerError, "Erro no banco de dados")),
+    };
+
+    let result = match authentication {
+        AuthenticationGuard::User(token) => {
+            let email = config_jwt::get_email_by_token(token);
+
+            let user = match service_user::find_by_email(database, &email).await {
+                Ok(user) => user,
+                Err(BackendError::ResourceNotFoundError) => {
+                    return Err((Status::Forbidden, "Token de autenticação inválido"));
+                }
+                Err(_) => return Err((Status::InternalServerError, "Erro interno")),
+            };
+
+            if !user.active {
+                return Err((Status::Forbidden, "O usuário está desativado"));
+            }
+
+            service_role::find_permissions_by_user_id(database, user.id).await
+        }
+        AuthenticationGuard::ApiKey(api_key) => {
+            service_role::find_permissions_by_api_key_id(database, api_key.id).await
+        }
+    };
+
+    match result {
+        Ok(permissions) => Ok(permissions),
+        Err(_) => Err((Status::InternalServerError, "Erro interno")),
+    }
+}
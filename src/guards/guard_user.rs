@@ -6,25 +6,48 @@ use rocket::{
 use sea_orm::DatabaseConnection;
 
 use crate::{
-    configs::config_jwt::{self, valid_token},
-    services::service_user,
+    configs::config_jwt::valid_token, entities::tb_api_key, errors::BackendError,
+    services::service_api_key,
 };
 
-#[derive(Default)]
-pub struct AuthenticationGuard(pub String);
+#[derive(Clone)]
+pub enum AuthenticationGuard {
+    User(String),
+    ApiKey(tb_api_key::Model),
+}
+
+struct RequestApiKey(Result<tb_api_key::Model, (Status, &'static str)>);
 
 #[async_trait]
 impl<'r> FromRequest<'r> for AuthenticationGuard {
     type Error = &'static str;
 
     async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
-        let token = req.headers().get_one("token");
-
-        match token {
-            Some(token) => match valid_token(token) {
-                true => Outcome::Success(AuthenticationGuard(token.to_string())),
+        if let Some(token) = req.headers().get_one("token") {
+            return match valid_token(token) {
+                true => Outcome::Success(AuthenticationGuard::User(token.to_string())),
                 false => Outcome::Error((Status::Forbidden, "Token de autenticação inválido")),
-            },
+            };
+        }
+
+        let api_key = req
+            .headers()
+            .get_one("Authorization")
+            .and_then(|authorization| authorization.strip_prefix("ApiKey "));
+
+        match api_key {
+            Some(api_key) => {
+                let request_api_key = req
+                    .local_cache_async(async {
+                        RequestApiKey(find_request_api_key(req, api_key.trim()).await)
+                    })
+                    .await;
+
+                match &request_api_key.0 {
+                    Ok(model) => Outcome::Success(AuthenticationGuard::ApiKey(model.clone())),
+                    Err(error) => Outcome::Error(*error),
+                }
+            }
             None => Outcome::Error((
                 Status::Forbidden,
                 "Token de autenticação não encontrado, tente realizar o login",
@@ -33,102 +56,20 @@ impl<'r> FromRequest<'r> for AuthenticationGuard {
     }
 }
 
-#[derive(Default)]
-pub struct AdminAuthenticationGuard;
-
-#[async_trait]
-impl<'r> FromRequest<'r> for AdminAuthenticationGuard {
-    type Error = &'static str;
-
-    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
-        let token = req.headers().get_one("token").unwrap();
-
-        let database = req.guard::<&State<DatabaseConnection>>().await;
-
-        let email = config_jwt::get_email_by_token(token);
-
-        match database {
-            Outcome::Success(database) => verify_role(database.inner(), &email, 1).await,
-            _ => Outcome::Error((Status::BadRequest, "Erro no banco de dados")),
+async fn find_request_api_key(
+    req: &Request<'_>,
+    api_key: &str,
+) -> Result<tb_api_key::Model, (Status, &'static str)> {
+    let database = match req.guard::<&State<DatabaseConnection>>().await {
+        Outcome::Success(database) => database,
+        _ => return Err((Status::InternalServerError, "Erro no banco de dados")),
+    };
+
+    match service_api_key::authenticate(database, api_key).await {
+        Ok(model) => Ok(model),
+        Err(BackendError::InvalidCredentialsError) => {
+            Err((Status::Forbidden, "Chave de API inválida"))
         }
-    }
-}
-
-#[derive(Default)]
-pub struct MannagerAuthenticationGuard;
-
-#[async_trait]
-impl<'r> FromRequest<'r> for MannagerAuthenticationGuard {
-    type Error = &'static str;
-
-    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
-        let token = req.headers().get_one("token").unwrap();
-
-        let database = req.guard::<&State<DatabaseConnection>>().await;
-
-        let email = config_jwt::get_email_by_token(token);
-
-        match database {
-            Outcome::Success(database) => verify_role(database.inner(), &email, 2).await,
-            _ => Outcome::Error((Status::InternalServerError, "Erro no banco de dados")),
-        }
-    }
-}
-
-#[derive(Default)]
-pub struct OperatorAuthenticationGuard;
-
-#[async_trait]
-impl<'r> FromRequest<'r> for OperatorAuthenticationGuard {
-    type Error = &'static str;
-
-    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
-        let token = req.headers().get_one("token").unwrap();
-
-        let database = req.guard::<&State<DatabaseConnection>>().await;
-
-        let email = config_jwt::get_email_by_token(token);
-
-        match database {
-            Outcome::Success(database) => verify_role(database.inner(), &email, 3).await,
-            _ => Outcome::Error((Status::InternalServerError, "Erro no banco de dados")),
-        }
-    }
-}
-
-#[derive(Default)]
-pub struct ViewerAuthenticationGuard;
-
-#[async_trait]
-impl<'r> FromRequest<'r> for ViewerAuthenticationGuard {
-    type Error = &'static str;
-
-    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
-        let token = req.headers().get_one("token").unwrap();
-
-        let database = req.guard::<&State<DatabaseConnection>>().await;
-
-        let email = config_jwt::get_email_by_token(token);
-
-        match database {
-            Outcome::Success(database) => verify_role(database.inner(), &email, 4).await,
-            _ => Outcome::Error((Status::InternalServerError, "Erro no banco de dados")),
-        }
-    }
-}
-
-async fn verify_role<T: Default>(
-    database: &DatabaseConnection,
-    email: &str,
-    role: u8,
-) -> Outcome<T, &'static str> {
-    let result = service_user::find_by_email(database, &email).await;
-
-    match result {
-        Ok(user) => match user.role <= role {
-            true => Outcome::Success(T::default()),
-            false => Outcome::Error((Status::Forbidden, "Erro no banco de dados")),
-        },
-        Err(_) => Outcome::Error((Status::InternalServerError, "Erro interno")),
+        Err(_) => Err((Status::InternalServerError, "Erro interno")),
     }
 }
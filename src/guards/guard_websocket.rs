@@ -0,0 +1,40 @@
+use rocket::{
+    http::Status,
+    request::{FromRequest, Outcome, Request},
+};
+use tokio_tungstenite::tungstenite::handshake::derive_accept_key;
+
+pub struct WebSocketGuard(String);
+
+impl WebSocketGuard {
+    pub fn get_accept_key(&self) -> &String {
+        &self.0
+    }
+}
+
+#[async_trait]
+impl<'r> FromRequest<'r> for WebSocketGuard {
+    type Error = &'static str;
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let upgrade = req
+            .headers()
+            .get_one("Upgrade")
+            .is_some_and(|upgrade| upgrade.trim().eq_ignore_ascii_case("websocket"));
+
+        let version = req.headers().get_one("Sec-WebSocket-Version") == Some("13");
+
+        match (
+            upgrade && version,
+            req.headers().get_one("Sec-WebSocket-Key"),
+        ) {
+            (true, Some(key)) => {
+                Outcome::Success(WebSocketGuard(derive_accept_key(key.trim().as_bytes())))
+            }
+            _ => Outcome::Error((
+                Status::UpgradeRequired,
+                "A rota exige uma conexão WebSocket",
+            )),
+        }
+    }
+}
@@ -1 +1,6 @@
+pub mod guard_client_ip;
+pub mod guard_if_match;
+pub mod guard_language;
+pub mod guard_permission;
 pub mod guard_user;
+pub mod guard_websocket;
@@ -1,25 +1,15 @@
 use rocket::{Config, config::LogLevel};
 
 use crate::{
-    configs::config_cors::make_cors,
+    configs::{
+        config_cors::make_cors, config_deprecation::make_deprecation_fairing,
+        config_openapi::make_swagger_ui, config_purge::make_purge_job,
+        config_reservation::make_reservation_release_job,
+    },
+    graphql::graphql_schema::make_schema,
     routes::{
-        route_category::{
-            route_category_create, route_category_delete, route_category_get_all,
-            route_category_get_all_admin, route_category_update,
-        },
-        route_product::{
-            route_product_create, route_product_delete, route_product_get_all,
-            route_product_get_by_id, route_product_informations, route_product_quantity_update,
-            route_product_update,
-        },
-        route_reason::{
-            route_reason_create, route_reason_delete, route_reason_get_all, route_reason_update,
-        },
-        route_report::{route_report_get_all, route_report_update},
-        route_user::{
-            route_login, route_user_create, route_user_delete, route_user_get_all,
-            route_user_update_credentials, route_user_update_informations, route_valid_token,
-        },
+        api_v1::{API_V1_BASE, make_v1_routes},
+        route_graphql::make_graphiql_routes,
     },
 };
 
@@ -29,6 +19,7 @@ extern crate rocket;
 mod configs;
 mod entities;
 mod errors;
+mod graphql;
 mod guards;
 mod routes;
 mod services;
@@ -37,39 +28,18 @@ mod services;
 async fn rocket() -> _ {
     rocket::build()
         .attach(make_cors())
-         .configure(Config {
+        .attach(make_deprecation_fairing())
+        .attach(make_purge_job())
+        .attach(make_reservation_release_job())
+        .configure(Config {
             log_level: LogLevel::Off,
             ..Default::default()
         })
         .manage(configs::config_database::get_database().await)
-        .mount(
-            "/",
-            routes![
-                route_login,
-                route_valid_token,
-                route_user_get_all,
-                route_user_create,
-                route_user_update_informations,
-                route_user_update_credentials,
-                route_user_delete,
-                route_category_get_all,
-                route_category_get_all_admin,
-                route_category_create,
-                route_category_update,
-                route_category_delete,
-                route_product_get_all,
-                route_product_get_by_id,
-                route_product_informations,
-                route_product_create,
-                route_product_update,
-                route_product_delete,
-                route_product_quantity_update,
-                route_reason_get_all,
-                route_reason_create,
-                route_reason_update,
-                route_reason_delete,
-                route_report_get_all,
-                route_report_update
-            ],
-        )
+        .manage(make_schema())
+        .mount(API_V1_BASE, make_v1_routes())
+        // Bare paths kept for clients that still call them, flagged by the deprecation fairing
+        .mount("/", make_v1_routes())
+        .mount(API_V1_BASE, make_graphiql_routes())
+        .mount("/", make_swagger_ui())
 }
@@ -0,0 +1,135 @@
+use rocket::Route;
+
+use crate::routes::{
+    route_analytics::{
+        route_analytics_abc, route_analytics_consumption, route_analytics_forecast,
+        route_analytics_ranking, route_analytics_stock_history,
+    },
+    route_api_key::{route_api_key_create, route_api_key_get_all, route_api_key_revoke},
+    route_audit::route_audit_get_all,
+    route_category::{
+        route_category_create, route_category_delete, route_category_export,
+        route_category_get_all, route_category_get_all_admin, route_category_patch,
+        route_category_restore, route_category_update,
+    },
+    route_event::route_events,
+    route_graphql::route_graphql,
+    route_product::{
+        route_product_create, route_product_delete, route_product_export, route_product_get_all,
+        route_product_get_by_id, route_product_import, route_product_informations,
+        route_product_patch, route_product_quantity_batch, route_product_quantity_update,
+        route_product_restore, route_product_update,
+    },
+    route_reason::{
+        route_reason_create, route_reason_delete, route_reason_get_all, route_reason_restore,
+        route_reason_update,
+    },
+    route_report::{
+        route_report_export, route_report_get_all, route_report_integrity,
+        route_report_pdf_product, route_report_pdf_stock, route_report_reversal,
+    },
+    route_reservation::{
+        route_reservation_create, route_reservation_get_all, route_reservation_release,
+    },
+    route_role::{
+        route_permission_get_all, route_role_create, route_role_delete, route_role_get_all,
+        route_role_update, route_user_permissions_update,
+    },
+    route_scanner::route_scanner,
+    route_stocktake::{
+        route_stocktake_approve, route_stocktake_cancel, route_stocktake_count,
+        route_stocktake_create, route_stocktake_get_all, route_stocktake_get_items,
+    },
+    route_user::{
+        route_login, route_login_two_factor, route_login_two_factor_enrollment, route_user_create,
+        route_user_delete, route_user_export, route_user_get_all, route_user_patch,
+        route_user_reset_password, route_user_two_factor_confirm, route_user_two_factor_enrollment,
+        route_user_two_factor_reset, route_user_unlock, route_user_update,
+        route_user_update_active, route_user_update_credentials, route_user_update_informations,
+        route_valid_token,
+    },
+};
+
+pub const API_V1_BASE: &str = "/api/v1";
+
+// A future version gets its own module with its routes and DTOs, calling the same services
+pub fn make_v1_routes() -> Vec<Route> {
+    routes![
+        route_login,
+        route_login_two_factor,
+        route_login_two_factor_enrollment,
+        route_valid_token,
+        route_user_get_all,
+        route_user_export,
+        route_user_create,
+        route_user_update_informations,
+        route_user_update_credentials,
+        route_user_update,
+        route_user_patch,
+        route_user_reset_password,
+        route_user_update_active,
+        route_user_unlock,
+        route_user_two_factor_enrollment,
+        route_user_two_factor_confirm,
+        route_user_two_factor_reset,
+        route_user_delete,
+        route_category_get_all,
+        route_category_get_all_admin,
+        route_category_export,
+        route_category_create,
+        route_category_update,
+        route_category_patch,
+        route_category_delete,
+        route_category_restore,
+        route_product_get_all,
+        route_product_export,
+        route_product_get_by_id,
+        route_product_informations,
+        route_product_create,
+        route_product_import,
+        route_product_update,
+        route_product_patch,
+        route_product_delete,
+        route_product_restore,
+        route_product_quantity_update,
+        route_product_quantity_batch,
+        route_reason_get_all,
+        route_reason_create,
+        route_reason_update,
+        route_reason_delete,
+        route_reason_restore,
+        route_report_get_all,
+        route_report_export,
+        route_report_pdf_stock,
+        route_report_pdf_product,
+        route_report_integrity,
+        route_report_reversal,
+        route_analytics_stock_history,
+        route_analytics_consumption,
+        route_analytics_ranking,
+        route_analytics_forecast,
+        route_analytics_abc,
+        route_events,
+        route_scanner,
+        route_graphql,
+        route_reservation_get_all,
+        route_reservation_create,
+        route_reservation_release,
+        route_role_get_all,
+        route_permission_get_all,
+        route_role_create,
+        route_role_update,
+        route_role_delete,
+        route_user_permissions_update,
+        route_stocktake_get_all,
+        route_stocktake_get_items,
+        route_stocktake_create,
+        route_stocktake_count,
+        route_stocktake_approve,
+        route_stocktake_cancel,
+        route_api_key_get_all,
+        route_api_key_create,
+        route_api_key_revoke,
+        route_audit_get_all,
+    ]
+}
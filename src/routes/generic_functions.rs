@@ -1,7 +1,41 @@
-use rocket::{http::Status, response::status::Custom};
+use rocket::{
+    Request,
+    http::{ContentType, Header, Status},
+    response::{self, Responder, Response, status::Custom, stream::ByteStream},
+    serde::json::Json,
+};
 use sea_orm::DbErr;
+use utoipa::{
+    ToResponse,
+    openapi::{
+        ContentBuilder, ObjectBuilder, RefOr, Response as OpenApiResponse, ResponseBuilder, Type,
+    },
+};
 
-use crate::errors::BackendError;
+use crate::{
+    entities::enums::{export_enums::ExportFormat, permission_enums::Permission},
+    errors::BackendError,
+    guards::guard_permission::PermissionGuard,
+    services::service_export::{ExportBody, ExportFile},
+};
+
+// Documents the plain text messages returned by catch_backend_error
+pub struct ErrorResponse;
+
+impl<'s> ToResponse<'s> for ErrorResponse {
+    fn response() -> (&'s str, RefOr<OpenApiResponse>) {
+        let content = ContentBuilder::new()
+            .schema(Some(ObjectBuilder::new().schema_type(Type::String)))
+            .build();
+
+        let response = ResponseBuilder::new()
+            .description("Mensagem de erro")
+            .content("text/plain", content)
+            .build();
+
+        ("ErrorResponse", response.into())
+    }
+}
 
 pub fn catch_backend_error(backend_error: BackendError) -> Custom<&'static str> {
     match backend_error {
@@ -24,6 +58,18 @@ pub fn catch_backend_error(backend_error: BackendError) -> Custom<&'static str>
             Status::Conflict,
             "A entidade possuí identificadores presentes em outras entidades já salvas",
         ),
+        BackendError::ResourceLockedError => Custom(
+            Status::Locked,
+            "A entidade está bloqueada por uma contagem de inventário em andamento",
+        ),
+        BackendError::ResourceVersionRequiredError => Custom(
+            Status::PreconditionRequired,
+            "A versão da entidade deve ser enviada no header If-Match ou no campo version",
+        ),
+        BackendError::ResourceStaleVersionError => Custom(
+            Status::Conflict,
+            "A entidade foi alterada por outro usuário desde a última leitura",
+        ),
 
         BackendError::NotEnoughtResourcers => {
             Custom(Status::BadRequest, "Quantidade não disponível")
@@ -32,9 +78,88 @@ pub fn catch_backend_error(backend_error: BackendError) -> Custom<&'static str>
         BackendError::InvalidCredentialsError => {
             Custom(Status::Forbidden, "As credenciais inserídas são inválidas")
         }
+        BackendError::TooManyAttemptsError => Custom(
+            Status::TooManyRequests,
+            "Muitas tentativas de login, tente novamente mais tarde",
+        ),
+        BackendError::InactiveUserError => Custom(Status::Forbidden, "O usuário está desativado"),
+        BackendError::PermissionDeniedError => Custom(
+            Status::Forbidden,
+            "O usuário não possuí permissão para realizar a operação",
+        ),
         BackendError::InvalidValuesError => Custom(
             Status::BadRequest,
             "Um ou mais valores enviados são inválidas",
         ),
+        BackendError::PayloadTooLargeError => Custom(
+            Status::PayloadTooLarge,
+            "O conteúdo enviado excede o tamanho máximo permitido",
+        ),
+
+        BackendError::ExportError => Custom(
+            Status::InternalServerError,
+            "Não foi possível gerar o arquivo de exportação",
+        ),
+    }
+}
+
+// JSON body sent with the entity version as a strong ETag, to be echoed back in If-Match
+#[derive(Responder)]
+pub struct VersionedJson<T> {
+    body: Json<T>,
+    etag: Header<'static>,
+}
+
+impl<T> VersionedJson<T> {
+    pub fn new(body: T, version: u64) -> Self {
+        Self {
+            body: Json(body),
+            etag: Header::new("ETag", format!("\"{version}\"")),
+        }
+    }
+}
+
+#[derive(Responder)]
+pub enum UpdateError<T> {
+    #[response(status = 409)]
+    StaleVersion(VersionedJson<T>),
+    Failure(Custom<&'static str>),
+}
+
+pub fn resolve_include_deleted<P: Permission>(
+    include_deleted: Option<bool>,
+    permission_guard: &Option<PermissionGuard<P>>,
+) -> Result<bool, Custom<&'static str>> {
+    match (include_deleted, permission_guard) {
+        (Some(true), Some(_)) => Ok(true),
+        (Some(true), None) => Err(catch_backend_error(BackendError::PermissionDeniedError)),
+        _ => Ok(false),
+    }
+}
+
+impl<'r> Responder<'r, 'r> for ExportFile<'r> {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'r> {
+        let content_type = match self.get_format() {
+            ExportFormat::Csv => ContentType::CSV,
+            ExportFormat::Xlsx => ContentType::new(
+                "application",
+                "vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+            ),
+        };
+
+        let content_disposition = Header::new(
+            "Content-Disposition",
+            format!("attachment; filename=\"{}\"", self.get_file_name()),
+        );
+
+        let response = match self.into_body() {
+            ExportBody::Stream(stream) => ByteStream(stream).respond_to(request)?,
+            ExportBody::Buffer(buffer) => buffer.respond_to(request)?,
+        };
+
+        Response::build_from(response)
+            .header(content_type)
+            .header(content_disposition)
+            .ok()
     }
 }
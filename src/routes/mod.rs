@@ -1,6 +1,16 @@
+pub mod api_v1;
 pub mod generic_functions;
+pub mod route_analytics;
+pub mod route_api_key;
+pub mod route_audit;
 pub mod route_category;
+pub mod route_event;
+pub mod route_graphql;
 pub mod route_product;
 pub mod route_reason;
 pub mod route_report;
+pub mod route_reservation;
+pub mod route_role;
+pub mod route_scanner;
+pub mod route_stocktake;
 pub mod route_user;
@@ -0,0 +1,149 @@
+use rocket::{State, response::status::Custom, serde::json::Json};
+use sea_orm::DatabaseConnection;
+
+use crate::{
+    entities::dtos::analytics_dtos::{
+        AbcClassificationDTO, ConsumptionDTO, ConsumptionFilterDTO, ForecastDTO, ForecastFilterDTO,
+        RankingDTO, RankingFilterDTO, StockHistoryFilterDTO, StockHistoryPointDTO,
+    },
+    entities::enums::permission_enums::{ProductClassify, ReportRead},
+    guards::guard_permission::PermissionGuard,
+    routes::generic_functions::{ErrorResponse, catch_backend_error},
+    services::service_analytics,
+};
+
+#[utoipa::path(
+    get,
+    path = "/analytics/stock-history",
+    tag = "Analytics",
+    summary = "Returns the stock level of a product over time",
+    params(
+        StockHistoryFilterDTO,
+    ),
+    responses(
+        (status = 200, description = "Stock level per bucket", body = Vec<StockHistoryPointDTO>),
+        (status = 400, response = ErrorResponse),
+        (status = 404, response = ErrorResponse),
+    ),
+)]
+#[get("/analytics/stock-history?<stock_history_filter_dto..>")]
+pub async fn route_analytics_stock_history(
+    database: &State<DatabaseConnection>,
+    _permission_guard: PermissionGuard<ReportRead>,
+    stock_history_filter_dto: StockHistoryFilterDTO,
+) -> Result<Json<Vec<StockHistoryPointDTO>>, Custom<&'static str>> {
+    let result = service_analytics::get_stock_history(database, stock_history_filter_dto).await;
+
+    match result {
+        Ok(history) => Ok(Json(history)),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/analytics/consumption",
+    tag = "Analytics",
+    summary = "Returns the quantity consumed per bucket",
+    params(
+        ConsumptionFilterDTO,
+    ),
+    responses(
+        (status = 200, description = "Consumption per bucket", body = Vec<ConsumptionDTO>),
+        (status = 400, response = ErrorResponse),
+    ),
+)]
+#[get("/analytics/consumption?<consumption_filter_dto..>")]
+pub async fn route_analytics_consumption(
+    database: &State<DatabaseConnection>,
+    _permission_guard: PermissionGuard<ReportRead>,
+    consumption_filter_dto: ConsumptionFilterDTO,
+) -> Result<Json<Vec<ConsumptionDTO>>, Custom<&'static str>> {
+    let result = service_analytics::get_consumption(database, consumption_filter_dto).await;
+
+    match result {
+        Ok(consumption) => Ok(Json(consumption)),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/analytics/ranking",
+    tag = "Analytics",
+    summary = "Returns the top movers and the dead stock",
+    params(
+        RankingFilterDTO,
+    ),
+    responses(
+        (status = 200, description = "Ranking", body = RankingDTO),
+        (status = 400, response = ErrorResponse),
+    ),
+)]
+#[get("/analytics/ranking?<ranking_filter_dto..>")]
+pub async fn route_analytics_ranking(
+    database: &State<DatabaseConnection>,
+    _permission_guard: PermissionGuard<ReportRead>,
+    ranking_filter_dto: RankingFilterDTO,
+) -> Result<Json<RankingDTO>, Custom<&'static str>> {
+    let result = service_analytics::get_ranking(database, ranking_filter_dto).await;
+
+    match result {
+        Ok(ranking) => Ok(Json(ranking)),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/analytics/forecast",
+    tag = "Analytics",
+    summary = "Returns the demand forecast of each product",
+    params(
+        ForecastFilterDTO,
+    ),
+    responses(
+        (status = 200, description = "Forecast ordered by days of cover", body = Vec<ForecastDTO>),
+        (status = 400, response = ErrorResponse),
+    ),
+)]
+#[get("/analytics/forecast?<forecast_filter_dto..>")]
+pub async fn route_analytics_forecast(
+    database: &State<DatabaseConnection>,
+    _permission_guard: PermissionGuard<ReportRead>,
+    forecast_filter_dto: ForecastFilterDTO,
+) -> Result<Json<Vec<ForecastDTO>>, Custom<&'static str>> {
+    let result = service_analytics::get_forecast(database, forecast_filter_dto).await;
+
+    match result {
+        Ok(forecast) => Ok(Json(forecast)),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/analytics/abc",
+    tag = "Analytics",
+    summary = "Recomputes the ABC class of every product",
+    params(
+        ("window_days" = Option<u32>, Query, description = "Number of past days of exits considered"),
+    ),
+    responses(
+        (status = 200, description = "Classification summary", body = AbcClassificationDTO),
+        (status = 400, response = ErrorResponse),
+    ),
+)]
+#[post("/analytics/abc?<window_days>")]
+pub async fn route_analytics_abc(
+    database: &State<DatabaseConnection>,
+    _permission_guard: PermissionGuard<ProductClassify>,
+    window_days: Option<u32>,
+) -> Result<Json<AbcClassificationDTO>, Custom<&'static str>> {
+    let result = service_analytics::classify_products(database, window_days).await;
+
+    match result {
+        Ok(classification) => Ok(Json(classification)),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
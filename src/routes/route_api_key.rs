@@ -0,0 +1,87 @@
+use rocket::{State, http::Status, response::status::Custom, serde::json::Json};
+use sea_orm::DatabaseConnection;
+
+use crate::{
+    entities::dtos::api_key_dtos::{ApiKeyCreateDTO, ApiKeyCreatedDTO, ApiKeyViewDTO},
+    entities::enums::permission_enums::ApiKeyManage,
+    guards::{guard_permission::PermissionGuard, guard_user::AuthenticationGuard},
+    routes::generic_functions::{ErrorResponse, catch_backend_error},
+    services::service_api_key,
+};
+
+#[utoipa::path(
+    get,
+    path = "/apikey",
+    tag = "API keys",
+    summary = "Returns all API keys",
+    responses(
+        (status = 200, description = "API keys", body = Vec<ApiKeyViewDTO>),
+    ),
+)]
+#[get("/apikey")]
+pub async fn route_api_key_get_all(
+    database: &State<DatabaseConnection>,
+    _permission_guard: PermissionGuard<ApiKeyManage>,
+) -> Result<Json<Vec<ApiKeyViewDTO>>, Custom<&'static str>> {
+    let result = service_api_key::get_all_api_keys(database).await;
+
+    match result {
+        Ok(api_keys) => Ok(Json(api_keys)),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/apikey",
+    tag = "API keys",
+    summary = "Creates an API key",
+    request_body = ApiKeyCreateDTO,
+    responses(
+        (status = 201, description = "API key created, the key is only shown here", body = ApiKeyCreatedDTO),
+        (status = 400, response = ErrorResponse),
+    ),
+)]
+#[post("/apikey", data = "<api_key_create_dto>")]
+pub async fn route_api_key_create(
+    database: &State<DatabaseConnection>,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<ApiKeyManage>,
+    api_key_create_dto: Json<ApiKeyCreateDTO>,
+) -> Result<Custom<Json<ApiKeyCreatedDTO>>, Custom<&'static str>> {
+    let result =
+        service_api_key::create_api_key(database, api_key_create_dto.0, authentication).await;
+
+    match result {
+        Ok(api_key) => Ok(Custom(Status::Created, Json(api_key))),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/apikey/{api_key_id}",
+    tag = "API keys",
+    summary = "Revokes an API key",
+    params(
+        ("api_key_id" = u64, Path, description = "API key ID"),
+    ),
+    responses(
+        (status = 200, description = "API key revoked"),
+        (status = 404, response = ErrorResponse),
+    ),
+)]
+#[delete("/apikey/<api_key_id>")]
+pub async fn route_api_key_revoke(
+    database: &State<DatabaseConnection>,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<ApiKeyManage>,
+    api_key_id: u64,
+) -> Result<Status, Custom<&'static str>> {
+    let result = service_api_key::revoke_api_key(database, api_key_id, authentication).await;
+
+    match result {
+        Ok(_) => Ok(Status::Ok),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
@@ -0,0 +1,37 @@
+use rocket::{State, response::status::Custom, serde::json::Json};
+use sea_orm::DatabaseConnection;
+
+use crate::{
+    entities::dtos::audit_dtos::{AuditFilterDTO, AuditViewDTO},
+    entities::enums::permission_enums::AuditRead,
+    guards::guard_permission::PermissionGuard,
+    routes::generic_functions::{ErrorResponse, catch_backend_error},
+    services::service_audit,
+};
+
+#[utoipa::path(
+    get,
+    path = "/audit",
+    tag = "Audit",
+    summary = "Returns the audit log",
+    params(
+        AuditFilterDTO,
+    ),
+    responses(
+        (status = 200, description = "Audit entries", body = Vec<AuditViewDTO>),
+        (status = 400, response = ErrorResponse),
+    ),
+)]
+#[get("/audit?<audit_filter_dto..>")]
+pub async fn route_audit_get_all(
+    database: &State<DatabaseConnection>,
+    _permission_guard: PermissionGuard<AuditRead>,
+    audit_filter_dto: AuditFilterDTO,
+) -> Result<Json<Vec<AuditViewDTO>>, Custom<&'static str>> {
+    let result = service_audit::get_all_audits(database, audit_filter_dto).await;
+
+    match result {
+        Ok(audits) => Ok(Json(audits)),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
@@ -2,21 +2,49 @@ use rocket::{State, http::Status, response::status::Custom, serde::json::Json};
 use sea_orm::DatabaseConnection;
 
 use crate::{
-    entities::dtos::category_dtos::{CategoryCreateDTO, CategoryDTO, CategoryViewDTO},
-    guards::guard_user::{
-        AuthenticationGuard, MannagerAuthenticationGuard, OperatorAuthenticationGuard,
+    entities::dtos::category_dtos::{
+        CategoryCreateDTO, CategoryDTO, CategoryPatchDTO, CategoryViewDTO,
     },
-    routes::generic_functions::catch_backend_error,
-    services::service_category,
+    entities::dtos::generic_dtos::SortDTO,
+    entities::enums::{
+        export_enums::ExportFormat,
+        permission_enums::{CategoryManage, CategoryRead},
+    },
+    errors::BackendError,
+    guards::{
+        guard_if_match::IfMatchGuard, guard_language::LanguageGuard,
+        guard_permission::PermissionGuard, guard_user::AuthenticationGuard,
+    },
+    routes::generic_functions::{
+        ErrorResponse, UpdateError, VersionedJson, catch_backend_error, resolve_include_deleted,
+    },
+    services::{service_category, service_export::ExportFile},
 };
 
-#[get("/category")]
+#[utoipa::path(
+    get,
+    path = "/category",
+    tag = "Categories",
+    summary = "Returns all categories",
+    params(
+        ("include_deleted" = Option<bool>, Query, description = "Also returns deleted entities (requires `category.manage`)"),
+        SortDTO,
+    ),
+    responses(
+        (status = 200, description = "Categories", body = Vec<CategoryDTO>),
+    ),
+)]
+#[get("/category?<include_deleted>&<sort_dto..>")]
 pub async fn route_category_get_all(
     database: &State<DatabaseConnection>,
-    _authentication_guard: AuthenticationGuard,
-    _operator_authentication_guard: OperatorAuthenticationGuard,
+    _permission_guard: PermissionGuard<CategoryRead>,
+    manage_guard: Option<PermissionGuard<CategoryManage>>,
+    include_deleted: Option<bool>,
+    sort_dto: SortDTO,
 ) -> Result<Json<Vec<CategoryDTO>>, Custom<&'static str>> {
-    let result = service_category::get_all_categories(database).await;
+    let include_deleted = resolve_include_deleted(include_deleted, &manage_guard)?;
+
+    let result = service_category::get_all_categories(database, include_deleted, &sort_dto).await;
 
     match result {
         Ok(categories) => Ok(Json(categories)),
@@ -24,13 +52,23 @@ pub async fn route_category_get_all(
     }
 }
 
-#[get("/category/admin")]
+#[utoipa::path(
+    get,
+    path = "/category/admin",
+    tag = "Categories",
+    summary = "Returns categories with their number of products",
+    params(SortDTO),
+    responses(
+        (status = 200, description = "Categories", body = Vec<CategoryViewDTO>),
+    ),
+)]
+#[get("/category/admin?<sort_dto..>")]
 pub async fn route_category_get_all_admin(
     database: &State<DatabaseConnection>,
-    _authentication_guard: AuthenticationGuard,
-    _mannager_authentication_guard: MannagerAuthenticationGuard,
+    _permission_guard: PermissionGuard<CategoryManage>,
+    sort_dto: SortDTO,
 ) -> Result<Json<Vec<CategoryViewDTO>>, Custom<&'static str>> {
-    let result = service_category::get_all_categories_admin(database).await;
+    let result = service_category::get_all_categories_admin(database, &sort_dto).await;
 
     match result {
         Ok(categories) => Ok(Json(categories)),
@@ -38,14 +76,60 @@ pub async fn route_category_get_all_admin(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/category/admin/export",
+    tag = "Categories",
+    summary = "Exports the categories as a file",
+    params(
+        ("format" = Option<ExportFormat>, Query, description = "File format, `csv` by default"),
+        ("Accept-Language" = Option<String>, Header, description = "`en` for English column headers, Portuguese otherwise"),
+    ),
+    responses(
+        (status = 200, description = "Exported file", content((String = "text/csv"), (Vec<u8> = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"))),
+    ),
+)]
+#[get("/category/admin/export?<format>")]
+pub async fn route_category_export(
+    database: &State<DatabaseConnection>,
+    _permission_guard: PermissionGuard<CategoryManage>,
+    language_guard: LanguageGuard,
+    format: Option<ExportFormat>,
+) -> Result<ExportFile<'_>, Custom<&'static str>> {
+    let result = service_category::export_categories_admin(
+        database,
+        format.unwrap_or(ExportFormat::Csv),
+        language_guard.get_language(),
+    )
+    .await;
+
+    match result {
+        Ok(export_file) => Ok(export_file),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/category",
+    tag = "Categories",
+    summary = "Creates a category",
+    request_body = CategoryCreateDTO,
+    responses(
+        (status = 201, description = "Category created"),
+        (status = 400, response = ErrorResponse),
+        (status = 409, response = ErrorResponse),
+    ),
+)]
 #[post("/category", data = "<category_create_dto>")]
 pub async fn route_category_create(
     database: &State<DatabaseConnection>,
-    _authentication_guard: AuthenticationGuard,
-    _mannager_authentication_guard: MannagerAuthenticationGuard,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<CategoryManage>,
     category_create_dto: Json<CategoryCreateDTO>,
 ) -> Result<Status, Custom<&'static str>> {
-    let result = service_category::create_category(database, category_create_dto.0).await;
+    let result =
+        service_category::create_category(database, category_create_dto.0, authentication).await;
 
     match result {
         Ok(_) => Ok(Status::Created),
@@ -53,29 +137,184 @@ pub async fn route_category_create(
     }
 }
 
+#[utoipa::path(
+    put,
+    path = "/category",
+    tag = "Categories",
+    summary = "Updates a category",
+    params(
+        ("If-Match" = Option<String>, Header, description = "ETag of the edited version, or `*` for the current one, required unless `version` is sent"),
+    ),
+    request_body = CategoryDTO,
+    responses(
+        (status = 200, description = "Category updated"),
+        (status = 400, response = ErrorResponse),
+        (status = 404, response = ErrorResponse),
+        (status = 409, description = "Stale version with the current category, or a name used by another category", body = CategoryDTO),
+        (status = 412, response = ErrorResponse),
+        (status = 428, response = ErrorResponse),
+    ),
+)]
 #[put("/category", data = "<category_update_dto>")]
 pub async fn route_category_update(
     database: &State<DatabaseConnection>,
-    _authentication_guard: AuthenticationGuard,
-    _mannager_authentication_guard: MannagerAuthenticationGuard,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<CategoryManage>,
+    if_match_guard: IfMatchGuard,
     category_update_dto: Json<CategoryDTO>,
-) -> Result<Status, Custom<&'static str>> {
-    let result = service_category::update_category(database, category_update_dto.0).await;
+) -> Result<Status, UpdateError<CategoryDTO>> {
+    let mut category_update_dto = category_update_dto.0;
+    let id = *category_update_dto.get_id();
+
+    match resolve_version(database, id, if_match_guard).await {
+        Ok(Some(version)) => category_update_dto.set_version(version),
+        Ok(None) => {}
+        Err(backend_error) => return Err(UpdateError::Failure(catch_backend_error(backend_error))),
+    }
+
+    let result =
+        service_category::update_category(database, category_update_dto, authentication).await;
 
     match result {
         Ok(_) => Ok(Status::Ok),
-        Err(backend_error) => Err(catch_backend_error(backend_error)),
+        Err(backend_error) => Err(catch_update_error(database, id, backend_error).await),
+    }
+}
+
+#[utoipa::path(
+    patch,
+    path = "/category/{category_id}",
+    tag = "Categories",
+    summary = "Partially updates a category with a JSON merge patch",
+    params(
+        ("category_id" = u64, Path, description = "Category ID"),
+        ("If-Match" = Option<String>, Header, description = "ETag of the edited version, or `*` for the current one, required unless `version` is sent"),
+    ),
+    request_body(content = CategoryPatchDTO, content_type = "application/merge-patch+json"),
+    responses(
+        (status = 200, description = "Category updated"),
+        (status = 400, response = ErrorResponse),
+        (status = 404, response = ErrorResponse),
+        (status = 409, description = "Stale version with the current category, or a name used by another category", body = CategoryDTO),
+        (status = 412, response = ErrorResponse),
+        (status = 428, response = ErrorResponse),
+    ),
+)]
+#[patch("/category/<category_id>", data = "<category_patch_dto>")]
+pub async fn route_category_patch(
+    database: &State<DatabaseConnection>,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<CategoryManage>,
+    if_match_guard: IfMatchGuard,
+    category_id: u64,
+    category_patch_dto: Json<CategoryPatchDTO>,
+) -> Result<Status, UpdateError<CategoryDTO>> {
+    let mut category_patch_dto = category_patch_dto.0;
+
+    match resolve_version(database, category_id, if_match_guard).await {
+        Ok(Some(version)) => category_patch_dto.set_version(version),
+        Ok(None) => {}
+        Err(backend_error) => return Err(UpdateError::Failure(catch_backend_error(backend_error))),
+    }
+
+    let result =
+        service_category::patch_category(database, category_id, category_patch_dto, authentication)
+            .await;
+
+    match result {
+        Ok(_) => Ok(Status::Ok),
+        Err(backend_error) => Err(catch_update_error(database, category_id, backend_error).await),
+    }
+}
+
+// `If-Match: *` accepts whatever version of the category is current
+async fn resolve_version(
+    database: &DatabaseConnection,
+    id: u64,
+    if_match_guard: IfMatchGuard,
+) -> Result<Option<u64>, BackendError> {
+    match if_match_guard {
+        IfMatchGuard::Absent => Ok(None),
+        IfMatchGuard::Any => match service_category::get_category_by_id(database, id).await {
+            Ok(category) => Ok(*category.get_version()),
+            Err(backend_error) => Err(backend_error),
+        },
+        IfMatchGuard::Version(version) => Ok(Some(version)),
+    }
+}
+
+// A stale version answers with the current category, so the client can merge and retry
+async fn catch_update_error(
+    database: &DatabaseConnection,
+    id: u64,
+    backend_error: BackendError,
+) -> UpdateError<CategoryDTO> {
+    match backend_error {
+        BackendError::ResourceStaleVersionError => {
+            match service_category::get_category_by_id(database, id).await {
+                Ok(category) => {
+                    let version = category.get_version().unwrap_or_default();
+
+                    UpdateError::StaleVersion(VersionedJson::new(category, version))
+                }
+                Err(backend_error) => UpdateError::Failure(catch_backend_error(backend_error)),
+            }
+        }
+        backend_error => UpdateError::Failure(catch_backend_error(backend_error)),
     }
 }
 
+#[utoipa::path(
+    delete,
+    path = "/category/{category_id}",
+    tag = "Categories",
+    summary = "Deletes a category",
+    params(
+        ("category_id" = u64, Path, description = "Category ID"),
+    ),
+    responses(
+        (status = 200, description = "Category deleted"),
+        (status = 404, response = ErrorResponse),
+        (status = 409, response = ErrorResponse),
+    ),
+)]
 #[delete("/category/<category_id>")]
 pub async fn route_category_delete(
     database: &State<DatabaseConnection>,
-    _authentication_guard: AuthenticationGuard,
-    _mannager_authentication_guard: MannagerAuthenticationGuard,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<CategoryManage>,
+    category_id: u64,
+) -> Result<Status, Custom<&'static str>> {
+    let result = service_category::delete_by_id(database, category_id, authentication).await;
+
+    match result {
+        Ok(_) => Ok(Status::Ok),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/category/{category_id}/restore",
+    tag = "Categories",
+    summary = "Restores a deleted category",
+    params(
+        ("category_id" = u64, Path, description = "Category ID"),
+    ),
+    responses(
+        (status = 200, description = "Category restored"),
+        (status = 404, response = ErrorResponse),
+        (status = 409, response = ErrorResponse),
+    ),
+)]
+#[post("/category/<category_id>/restore")]
+pub async fn route_category_restore(
+    database: &State<DatabaseConnection>,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<CategoryManage>,
     category_id: u64,
 ) -> Result<Status, Custom<&'static str>> {
-    let result = service_category::delete_by_id(database, category_id).await;
+    let result = service_category::restore_by_id(database, category_id, authentication).await;
 
     match result {
         Ok(_) => Ok(Status::Ok),
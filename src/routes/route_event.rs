@@ -0,0 +1,55 @@
+use rocket::{
+    Shutdown,
+    response::stream::{Event, EventStream},
+    tokio::{select, sync::broadcast::error::RecvError},
+};
+
+use crate::{
+    configs::config_events,
+    entities::{dtos::event_dtos::StockEventDTO, enums::permission_enums::ProductRead},
+    guards::guard_permission::PermissionGuard,
+};
+
+#[utoipa::path(
+    get,
+    path = "/events",
+    tag = "Events",
+    summary = "Streams stock events as Server-Sent Events",
+    params(
+        ("category_id" = Option<Vec<u64>>, Query, description = "Only events of these categories, may be repeated"),
+    ),
+    responses(
+        (status = 200, description = "Event stream, each event carrying a product", body = StockEventDTO, content_type = "text/event-stream"),
+    ),
+)]
+#[get("/events?<category_id>")]
+pub async fn route_events(
+    _permission_guard: PermissionGuard<ProductRead>,
+    category_id: Vec<u64>,
+    mut shutdown: Shutdown,
+) -> EventStream![] {
+    let mut receiver = config_events::subscribe();
+
+    EventStream! {
+        loop {
+            let event = select! {
+                event = receiver.recv() => match event {
+                    Ok(event) => event,
+                    Err(RecvError::Closed) => break,
+                    Err(RecvError::Lagged(_)) => {
+                        // Some events were dropped, so the client must reload its data
+                        yield Event::empty().event("stream.lagged");
+                        continue;
+                    }
+                },
+                _ = &mut shutdown => break,
+            };
+
+            if !category_id.is_empty() && !category_id.contains(event.get_category_id()) {
+                continue;
+            }
+
+            yield Event::json(&event).event(event.get_event_type().as_str());
+        }
+    }
+}
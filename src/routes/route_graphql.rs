@@ -0,0 +1,53 @@
+use async_graphql::{Request, Response, http::GraphiQLSource};
+use rocket::{Route, State, response::content::RawHtml, serde::json::Json};
+use sea_orm::DatabaseConnection;
+
+use crate::{
+    graphql::graphql_schema::{GraphQLSchema, prepare_request},
+    guards::{guard_permission::PermissionListGuard, guard_user::AuthenticationGuard},
+    routes::api_v1::API_V1_BASE,
+};
+
+#[utoipa::path(
+    post,
+    path = "/graphql",
+    tag = "GraphQL",
+    summary = "Executes a GraphQL request",
+    request_body = Object,
+    responses(
+        (status = 200, description = "GraphQL response", body = Object),
+    ),
+)]
+#[post("/graphql", data = "<request>")]
+pub async fn route_graphql(
+    database: &State<DatabaseConnection>,
+    schema: &State<GraphQLSchema>,
+    authentication: AuthenticationGuard,
+    permission_list_guard: PermissionListGuard,
+    request: Json<Request>,
+) -> Json<Response> {
+    let request = prepare_request(
+        request.0,
+        database,
+        authentication,
+        permission_list_guard.into_permissions(),
+    );
+
+    Json(schema.execute(request).await)
+}
+
+#[get("/graphql")]
+pub fn route_graphiql() -> RawHtml<String> {
+    RawHtml(
+        GraphiQLSource::build()
+            .endpoint(&format!("{API_V1_BASE}/graphql"))
+            .finish(),
+    )
+}
+
+pub fn make_graphiql_routes() -> Vec<Route> {
+    match cfg!(debug_assertions) {
+        true => routes![route_graphiql],
+        false => Vec::new(),
+    }
+}
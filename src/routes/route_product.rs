@@ -1,25 +1,67 @@
-use rocket::{State, http::Status, response::status::Custom, serde::json::Json};
+use rocket::{
+    State,
+    data::{Data, ToByteUnit},
+    http::Status,
+    response::status::Custom,
+    serde::json::Json,
+};
 use sea_orm::DatabaseConnection;
 
 use crate::{
+    entities::dtos::generic_dtos::SortDTO,
+    entities::dtos::import_dtos::ProductImportResultDTO,
     entities::dtos::product_dtos::{
-        ProductChangeQuantityDTO, ProductCreateDTO, ProductInformationsViewDTO, ProductSummaryDTO,
-        ProductUpdateDTO, ProductViewDTO,
+        ProductBatchQuantityDTO, ProductBatchResultDTO, ProductChangeQuantityDTO, ProductCreateDTO,
+        ProductInformationsViewDTO, ProductPatchDTO, ProductSummaryDTO, ProductUpdateDTO,
+        ProductViewDTO,
+    },
+    entities::enums::{
+        analytics_enums::AbcClass,
+        export_enums::ExportFormat,
+        permission_enums::{
+            ProductCreate, ProductDelete, ProductImport, ProductRead, ProductUpdate, StockMove,
+        },
+    },
+    errors::BackendError,
+    guards::{
+        guard_if_match::IfMatchGuard, guard_language::LanguageGuard,
+        guard_permission::PermissionGuard, guard_user::AuthenticationGuard,
     },
-    guards::guard_user::{
-        AuthenticationGuard, OperatorAuthenticationGuard, ViewerAuthenticationGuard,
+    routes::generic_functions::{
+        ErrorResponse, UpdateError, VersionedJson, catch_backend_error, resolve_include_deleted,
     },
-    routes::generic_functions::catch_backend_error,
-    services::service_product,
+    services::{service_export::ExportFile, service_import, service_product},
 };
 
-#[get("/product")]
+const MAX_IMPORT_MEBIBYTES: u8 = 5;
+
+#[utoipa::path(
+    get,
+    path = "/product",
+    tag = "Products",
+    summary = "Returns all products",
+    params(
+        ("include_deleted" = Option<bool>, Query, description = "Also returns deleted entities (requires `product.delete`)"),
+        ("abc_class" = Option<AbcClass>, Query, description = "Only products of this ABC class"),
+        SortDTO,
+    ),
+    responses(
+        (status = 200, description = "Products", body = Vec<ProductViewDTO>),
+    ),
+)]
+#[get("/product?<include_deleted>&<abc_class>&<sort_dto..>")]
 pub async fn route_product_get_all(
     database: &State<DatabaseConnection>,
-    _authentication_guard: AuthenticationGuard,
-    _viewer_authentication_guard: ViewerAuthenticationGuard,
+    _permission_guard: PermissionGuard<ProductRead>,
+    manage_guard: Option<PermissionGuard<ProductDelete>>,
+    include_deleted: Option<bool>,
+    abc_class: Option<AbcClass>,
+    sort_dto: SortDTO,
 ) -> Result<Json<Vec<ProductViewDTO>>, Custom<&'static str>> {
-    let result = service_product::get_all_products(database).await;
+    let include_deleted = resolve_include_deleted(include_deleted, &manage_guard)?;
+
+    let result =
+        service_product::get_all_products(database, include_deleted, abc_class, &sort_dto).await;
 
     match result {
         Ok(products) => Ok(Json(products)),
@@ -27,11 +69,58 @@ pub async fn route_product_get_all(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/product/export",
+    tag = "Products",
+    summary = "Exports the products as a file",
+    params(
+        ("format" = Option<ExportFormat>, Query, description = "File format, `csv` by default"),
+        ("include_deleted" = Option<bool>, Query, description = "Also returns deleted entities (requires `product.delete`)"),
+        ("Accept-Language" = Option<String>, Header, description = "`en` for English column headers, Portuguese otherwise"),
+    ),
+    responses(
+        (status = 200, description = "Exported file", content((String = "text/csv"), (Vec<u8> = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"))),
+    ),
+)]
+#[get("/product/export?<format>&<include_deleted>")]
+pub async fn route_product_export(
+    database: &State<DatabaseConnection>,
+    _permission_guard: PermissionGuard<ProductRead>,
+    manage_guard: Option<PermissionGuard<ProductDelete>>,
+    language_guard: LanguageGuard,
+    format: Option<ExportFormat>,
+    include_deleted: Option<bool>,
+) -> Result<ExportFile<'_>, Custom<&'static str>> {
+    let include_deleted = resolve_include_deleted(include_deleted, &manage_guard)?;
+
+    let result = service_product::export_products(
+        database,
+        include_deleted,
+        format.unwrap_or(ExportFormat::Csv),
+        language_guard.get_language(),
+    )
+    .await;
+
+    match result {
+        Ok(export_file) => Ok(export_file),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/product/informations",
+    tag = "Products",
+    summary = "Returns general stock data of the products",
+    responses(
+        (status = 200, description = "Stock data", body = ProductInformationsViewDTO),
+    ),
+)]
 #[get("/product/informations")]
 pub async fn route_product_informations(
     database: &State<DatabaseConnection>,
-    _authentication_guard: AuthenticationGuard,
-    _viewer_authentication_guard: ViewerAuthenticationGuard,
+    _permission_guard: PermissionGuard<ProductRead>,
 ) -> Result<Json<ProductInformationsViewDTO>, Custom<&'static str>> {
     let result = service_product::get_products_informations(database).await;
 
@@ -41,29 +130,61 @@ pub async fn route_product_informations(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/product/{id}",
+    tag = "Products",
+    summary = "Returns a product",
+    params(
+        ("id" = u64, Path, description = "Product ID"),
+    ),
+    responses(
+        (status = 200, description = "Product", body = ProductSummaryDTO, headers(
+            ("ETag" = String, description = "Product version, sent back in If-Match on updates"),
+        )),
+        (status = 404, response = ErrorResponse),
+    ),
+)]
 #[get("/product/<id>")]
 pub async fn route_product_get_by_id(
     database: &State<DatabaseConnection>,
-    _authentication_guard: AuthenticationGuard,
-    _viewer_authentication_guard: ViewerAuthenticationGuard,
+    _permission_guard: PermissionGuard<ProductRead>,
     id: u64,
-) -> Result<Json<ProductSummaryDTO>, Custom<&'static str>> {
+) -> Result<VersionedJson<ProductSummaryDTO>, Custom<&'static str>> {
     let result = service_product::get_product_by_id(database, id).await;
 
     match result {
-        Ok(product) => Ok(Json(product)),
+        Ok(product) => {
+            let version = *product.get_version();
+
+            Ok(VersionedJson::new(product, version))
+        }
         Err(backend_error) => Err(catch_backend_error(backend_error)),
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/product",
+    tag = "Products",
+    summary = "Creates a product",
+    request_body = ProductCreateDTO,
+    responses(
+        (status = 201, description = "Product created"),
+        (status = 400, response = ErrorResponse),
+        (status = 404, response = ErrorResponse),
+        (status = 409, response = ErrorResponse),
+    ),
+)]
 #[post("/product", data = "<product_create_dto>")]
 pub async fn route_product_create(
     database: &State<DatabaseConnection>,
-    _authentication_guard: AuthenticationGuard,
-    _operator_authentication_guard: OperatorAuthenticationGuard,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<ProductCreate>,
     product_create_dto: Json<ProductCreateDTO>,
 ) -> Result<Status, Custom<&'static str>> {
-    let result = service_product::create_product(database, product_create_dto.0).await;
+    let result =
+        service_product::create_product(database, product_create_dto.0, authentication).await;
 
     match result {
         Ok(_) => Ok(Status::Created),
@@ -71,29 +192,203 @@ pub async fn route_product_create(
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/product/import",
+    tag = "Products",
+    summary = "Imports products from a CSV file",
+    params(
+        ("dry_run" = Option<bool>, Query, description = "Only validates the file when true, the default"),
+    ),
+    request_body(content = String, content_type = "text/csv"),
+    responses(
+        (status = 200, description = "Import result", body = ProductImportResultDTO),
+        (status = 422, description = "Import refused because of invalid rows", body = ProductImportResultDTO),
+        (status = 400, response = ErrorResponse),
+        (status = 413, response = ErrorResponse),
+    ),
+)]
+#[post("/product/import?<dry_run>", data = "<csv>")]
+pub async fn route_product_import(
+    database: &State<DatabaseConnection>,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<ProductImport>,
+    dry_run: Option<bool>,
+    csv: Data<'_>,
+) -> Result<Custom<Json<ProductImportResultDTO>>, Custom<&'static str>> {
+    let csv = match csv
+        .open(MAX_IMPORT_MEBIBYTES.mebibytes())
+        .into_string()
+        .await
+    {
+        Ok(csv) if csv.is_complete() => csv.into_inner(),
+        Ok(_) => return Err(catch_backend_error(BackendError::PayloadTooLargeError)),
+        Err(_) => return Err(catch_backend_error(BackendError::InvalidValuesError)),
+    };
+
+    let result =
+        service_import::import_products(database, &csv, dry_run.unwrap_or(true), authentication)
+            .await;
+
+    match result {
+        Ok(import_result) => match !import_result.get_dry_run() && !import_result.get_applied() {
+            true => Ok(Custom(Status::UnprocessableEntity, Json(import_result))),
+            false => Ok(Custom(Status::Ok, Json(import_result))),
+        },
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/product",
+    tag = "Products",
+    summary = "Updates a product",
+    params(
+        ("If-Match" = Option<String>, Header, description = "ETag of the edited version, or `*` for the current one, required unless `version` is sent"),
+    ),
+    request_body = ProductUpdateDTO,
+    responses(
+        (status = 200, description = "Product updated"),
+        (status = 400, response = ErrorResponse),
+        (status = 404, response = ErrorResponse),
+        (status = 409, description = "Stale version with the current product, or an identifier used by another product", body = ProductSummaryDTO),
+        (status = 412, response = ErrorResponse),
+        (status = 428, response = ErrorResponse),
+    ),
+)]
 #[put("/product", data = "<product_update_dto>")]
 pub async fn route_product_update(
     database: &State<DatabaseConnection>,
-    _authentication_guard: AuthenticationGuard,
-    _operator_authentication_guard: OperatorAuthenticationGuard,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<ProductUpdate>,
+    if_match_guard: IfMatchGuard,
     product_update_dto: Json<ProductUpdateDTO>,
-) -> Result<Status, Custom<&'static str>> {
-    let result = service_product::update_product(database, product_update_dto.0).await;
+) -> Result<Status, UpdateError<ProductSummaryDTO>> {
+    let mut product_update_dto = product_update_dto.0;
+    let id = *product_update_dto.get_id();
+
+    match resolve_version(database, id, if_match_guard).await {
+        Ok(Some(version)) => product_update_dto.set_version(version),
+        Ok(None) => {}
+        Err(backend_error) => return Err(UpdateError::Failure(catch_backend_error(backend_error))),
+    }
+
+    let result =
+        service_product::update_product(database, product_update_dto, authentication).await;
 
     match result {
         Ok(_) => Ok(Status::Ok),
-        Err(backend_error) => Err(catch_backend_error(backend_error)),
+        Err(backend_error) => Err(catch_update_error(database, id, backend_error).await),
     }
 }
 
+#[utoipa::path(
+    patch,
+    path = "/product/{product_id}",
+    tag = "Products",
+    summary = "Partially updates a product with a JSON merge patch",
+    params(
+        ("product_id" = u64, Path, description = "Product ID"),
+        ("If-Match" = Option<String>, Header, description = "ETag of the edited version, or `*` for the current one, required unless `version` is sent"),
+    ),
+    request_body(content = ProductPatchDTO, content_type = "application/merge-patch+json"),
+    responses(
+        (status = 200, description = "Product updated"),
+        (status = 400, response = ErrorResponse),
+        (status = 404, response = ErrorResponse),
+        (status = 409, description = "Stale version with the current product, or an identifier used by another product", body = ProductSummaryDTO),
+        (status = 412, response = ErrorResponse),
+        (status = 428, response = ErrorResponse),
+    ),
+)]
+#[patch("/product/<product_id>", data = "<product_patch_dto>")]
+pub async fn route_product_patch(
+    database: &State<DatabaseConnection>,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<ProductUpdate>,
+    if_match_guard: IfMatchGuard,
+    product_id: u64,
+    product_patch_dto: Json<ProductPatchDTO>,
+) -> Result<Status, UpdateError<ProductSummaryDTO>> {
+    let mut product_patch_dto = product_patch_dto.0;
+
+    match resolve_version(database, product_id, if_match_guard).await {
+        Ok(Some(version)) => product_patch_dto.set_version(version),
+        Ok(None) => {}
+        Err(backend_error) => return Err(UpdateError::Failure(catch_backend_error(backend_error))),
+    }
+
+    let result =
+        service_product::patch_product(database, product_id, product_patch_dto, authentication)
+            .await;
+
+    match result {
+        Ok(_) => Ok(Status::Ok),
+        Err(backend_error) => Err(catch_update_error(database, product_id, backend_error).await),
+    }
+}
+
+// `If-Match: *` accepts whatever version of the product is current
+async fn resolve_version(
+    database: &DatabaseConnection,
+    id: u64,
+    if_match_guard: IfMatchGuard,
+) -> Result<Option<u64>, BackendError> {
+    match if_match_guard {
+        IfMatchGuard::Absent => Ok(None),
+        IfMatchGuard::Any => match service_product::get_product_by_id(database, id).await {
+            Ok(product) => Ok(Some(*product.get_version())),
+            Err(backend_error) => Err(backend_error),
+        },
+        IfMatchGuard::Version(version) => Ok(Some(version)),
+    }
+}
+
+// A stale version answers with the current product, so the client can merge and retry
+async fn catch_update_error(
+    database: &DatabaseConnection,
+    id: u64,
+    backend_error: BackendError,
+) -> UpdateError<ProductSummaryDTO> {
+    match backend_error {
+        BackendError::ResourceStaleVersionError => {
+            match service_product::get_product_by_id(database, id).await {
+                Ok(product) => {
+                    let version = *product.get_version();
+
+                    UpdateError::StaleVersion(VersionedJson::new(product, version))
+                }
+                Err(backend_error) => UpdateError::Failure(catch_backend_error(backend_error)),
+            }
+        }
+        backend_error => UpdateError::Failure(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/product/quantity",
+    tag = "Products",
+    summary = "Changes the quantity of a product",
+    request_body = ProductChangeQuantityDTO,
+    responses(
+        (status = 200, description = "Quantity changed"),
+        (status = 400, response = ErrorResponse),
+        (status = 404, response = ErrorResponse),
+        (status = 423, response = ErrorResponse),
+    ),
+)]
 #[put("/product/quantity", data = "<product_change_quantity_dto>")]
 pub async fn route_product_quantity_update(
     database: &State<DatabaseConnection>,
-    _authentication_guard: AuthenticationGuard,
-    _operator_authentication_guard: OperatorAuthenticationGuard,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<StockMove>,
     product_change_quantity_dto: Json<ProductChangeQuantityDTO>,
 ) -> Result<Status, Custom<&'static str>> {
-    let result = service_product::change_quantity(database, product_change_quantity_dto.0).await;
+    let result =
+        service_product::change_quantity(database, product_change_quantity_dto.0, authentication)
+            .await;
 
     match result {
         Ok(_) => Ok(Status::Ok),
@@ -101,14 +396,110 @@ pub async fn route_product_quantity_update(
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/product/quantity/batch",
+    tag = "Products",
+    summary = "Applies a list of movements",
+    request_body = ProductBatchQuantityDTO,
+    responses(
+        (status = 200, description = "Result of each movement", body = Vec<ProductBatchResultDTO>),
+        (status = 400, response = ErrorResponse),
+        (status = 404, response = ErrorResponse),
+        (status = 423, response = ErrorResponse),
+    ),
+)]
+#[post("/product/quantity/batch", data = "<product_batch_quantity_dto>")]
+pub async fn route_product_quantity_batch(
+    database: &State<DatabaseConnection>,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<StockMove>,
+    product_batch_quantity_dto: Json<ProductBatchQuantityDTO>,
+) -> Result<Json<Vec<ProductBatchResultDTO>>, Custom<&'static str>> {
+    let product_ids: Vec<u64> = product_batch_quantity_dto
+        .get_movements()
+        .iter()
+        .map(|movement| *movement.get_id())
+        .collect();
+
+    let result = service_product::change_quantity_batch(
+        database,
+        product_batch_quantity_dto.0,
+        authentication,
+    )
+    .await;
+
+    match result {
+        Ok(results) => Ok(Json(
+            results
+                .into_iter()
+                .zip(product_ids)
+                .enumerate()
+                .map(|(index, (result, product_id))| {
+                    ProductBatchResultDTO::new(
+                        index + 1,
+                        product_id,
+                        result
+                            .err()
+                            .map(|backend_error| catch_backend_error(backend_error).1.to_string()),
+                    )
+                })
+                .collect(),
+        )),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/product/{product_id}",
+    tag = "Products",
+    summary = "Deletes a product",
+    params(
+        ("product_id" = u64, Path, description = "Product ID"),
+    ),
+    responses(
+        (status = 200, description = "Product deleted"),
+        (status = 404, response = ErrorResponse),
+    ),
+)]
 #[delete("/product/<product_id>")]
 pub async fn route_product_delete(
     database: &State<DatabaseConnection>,
-    _authentication_guard: AuthenticationGuard,
-    _operator_authentication_guard: OperatorAuthenticationGuard,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<ProductDelete>,
+    product_id: u64,
+) -> Result<Status, Custom<&'static str>> {
+    let result = service_product::delete_by_id(database, product_id, authentication).await;
+
+    match result {
+        Ok(_) => Ok(Status::Ok),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/product/{product_id}/restore",
+    tag = "Products",
+    summary = "Restores a deleted product",
+    params(
+        ("product_id" = u64, Path, description = "Product ID"),
+    ),
+    responses(
+        (status = 200, description = "Product restored"),
+        (status = 404, response = ErrorResponse),
+        (status = 409, response = ErrorResponse),
+    ),
+)]
+#[post("/product/<product_id>/restore")]
+pub async fn route_product_restore(
+    database: &State<DatabaseConnection>,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<ProductDelete>,
     product_id: u64,
 ) -> Result<Status, Custom<&'static str>> {
-    let result = service_product::delete_by_id(database, product_id).await;
+    let result = service_product::restore_by_id(database, product_id, authentication).await;
 
     match result {
         Ok(_) => Ok(Status::Ok),
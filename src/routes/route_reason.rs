@@ -2,21 +2,38 @@ use rocket::{State, http::Status, response::status::Custom, serde::json::Json};
 use sea_orm::DatabaseConnection;
 
 use crate::{
+    entities::dtos::generic_dtos::SortDTO,
     entities::dtos::reason_dtos::{ReasonCreateDTO, ReasonDTO},
-    guards::guard_user::{
-        AuthenticationGuard, MannagerAuthenticationGuard, OperatorAuthenticationGuard,
-    },
-    routes::generic_functions::catch_backend_error,
+    entities::enums::permission_enums::{ReasonManage, ReasonRead},
+    guards::{guard_permission::PermissionGuard, guard_user::AuthenticationGuard},
+    routes::generic_functions::{ErrorResponse, catch_backend_error, resolve_include_deleted},
     services::service_reason,
 };
 
-#[get("/reason")]
+#[utoipa::path(
+    get,
+    path = "/reason",
+    tag = "Reasons",
+    summary = "Returns all reasons",
+    params(
+        ("include_deleted" = Option<bool>, Query, description = "Also returns deleted entities (requires `reason.manage`)"),
+        SortDTO,
+    ),
+    responses(
+        (status = 200, description = "Reasons", body = Vec<ReasonDTO>),
+    ),
+)]
+#[get("/reason?<include_deleted>&<sort_dto..>")]
 pub async fn route_reason_get_all(
     database: &State<DatabaseConnection>,
-    _authentication_guard: AuthenticationGuard,
-    _operator_authentication_guard: OperatorAuthenticationGuard,
+    _permission_guard: PermissionGuard<ReasonRead>,
+    manage_guard: Option<PermissionGuard<ReasonManage>>,
+    include_deleted: Option<bool>,
+    sort_dto: SortDTO,
 ) -> Result<Json<Vec<ReasonDTO>>, Custom<&'static str>> {
-    let result = service_reason::get_all_reason(database).await;
+    let include_deleted = resolve_include_deleted(include_deleted, &manage_guard)?;
+
+    let result = service_reason::get_all_reason(database, include_deleted, &sort_dto).await;
 
     match result {
         Ok(reasons) => Ok(Json(reasons)),
@@ -24,14 +41,26 @@ pub async fn route_reason_get_all(
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/reason",
+    tag = "Reasons",
+    summary = "Creates a reason",
+    request_body = ReasonCreateDTO,
+    responses(
+        (status = 201, description = "Reason created"),
+        (status = 400, response = ErrorResponse),
+        (status = 409, response = ErrorResponse),
+    ),
+)]
 #[post("/reason", data = "<reason_create_dto>")]
 pub async fn route_reason_create(
     database: &State<DatabaseConnection>,
-    _authentication_guard: AuthenticationGuard,
-    _mannager_authentication_guard: MannagerAuthenticationGuard,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<ReasonManage>,
     reason_create_dto: Json<ReasonCreateDTO>,
 ) -> Result<Status, Custom<&'static str>> {
-    let result = service_reason::create_reason(database, reason_create_dto.0).await;
+    let result = service_reason::create_reason(database, reason_create_dto.0, authentication).await;
 
     match result {
         Ok(_) => Ok(Status::Created),
@@ -39,14 +68,27 @@ pub async fn route_reason_create(
     }
 }
 
+#[utoipa::path(
+    put,
+    path = "/reason",
+    tag = "Reasons",
+    summary = "Updates a reason",
+    request_body = ReasonDTO,
+    responses(
+        (status = 200, description = "Reason updated"),
+        (status = 400, response = ErrorResponse),
+        (status = 404, response = ErrorResponse),
+        (status = 409, response = ErrorResponse),
+    ),
+)]
 #[put("/reason", data = "<reason_update_dto>")]
 pub async fn route_reason_update(
     database: &State<DatabaseConnection>,
-    _authentication_guard: AuthenticationGuard,
-    _mannager_authentication_guard: MannagerAuthenticationGuard,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<ReasonManage>,
     reason_update_dto: Json<ReasonDTO>,
 ) -> Result<Status, Custom<&'static str>> {
-    let result = service_reason::update_reason(database, reason_update_dto.0).await;
+    let result = service_reason::update_reason(database, reason_update_dto.0, authentication).await;
 
     match result {
         Ok(_) => Ok(Status::Ok),
@@ -54,14 +96,56 @@ pub async fn route_reason_update(
     }
 }
 
+#[utoipa::path(
+    delete,
+    path = "/reason/{reason_id}",
+    tag = "Reasons",
+    summary = "Deletes a reason",
+    params(
+        ("reason_id" = u64, Path, description = "Reason ID"),
+    ),
+    responses(
+        (status = 200, description = "Reason deleted"),
+        (status = 404, response = ErrorResponse),
+    ),
+)]
 #[delete("/reason/<reason_id>")]
 pub async fn route_reason_delete(
     database: &State<DatabaseConnection>,
-    _authentication_guard: AuthenticationGuard,
-    _mannager_authentication_guard: MannagerAuthenticationGuard,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<ReasonManage>,
+    reason_id: u64,
+) -> Result<Status, Custom<&'static str>> {
+    let result = service_reason::delete_by_id(database, reason_id, authentication).await;
+
+    match result {
+        Ok(_) => Ok(Status::Ok),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/reason/{reason_id}/restore",
+    tag = "Reasons",
+    summary = "Restores a deleted reason",
+    params(
+        ("reason_id" = u64, Path, description = "Reason ID"),
+    ),
+    responses(
+        (status = 200, description = "Reason restored"),
+        (status = 404, response = ErrorResponse),
+        (status = 409, response = ErrorResponse),
+    ),
+)]
+#[post("/reason/<reason_id>/restore")]
+pub async fn route_reason_restore(
+    database: &State<DatabaseConnection>,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<ReasonManage>,
     reason_id: u64,
 ) -> Result<Status, Custom<&'static str>> {
-    let result = service_reason::delete_by_id(database, reason_id).await;
+    let result = service_reason::restore_by_id(database, reason_id, authentication).await;
 
     match result {
         Ok(_) => Ok(Status::Ok),
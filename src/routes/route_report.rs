@@ -1,18 +1,40 @@
-use rocket::{State, http::Status, response::status::Custom, serde::json::Json};
+use rocket::{
+    State,
+    http::{ContentType, Status},
+    response::status::Custom,
+    serde::json::Json,
+};
 use sea_orm::DatabaseConnection;
 
 use crate::{
-    entities::dtos::report_dtos::{ReportUpdateDTO, ReportViewDTO},
-    guards::guard_user::{AuthenticationGuard, OperatorAuthenticationGuard, ViewerAuthenticationGuard},
-    routes::generic_functions::catch_backend_error,
-    services::service_report,
+    entities::dtos::report_dtos::{
+        ProductStatementFilterDTO, ReportIntegrityDTO, ReportReversalDTO, ReportViewDTO,
+    },
+    entities::enums::{
+        export_enums::ExportFormat,
+        permission_enums::{ReportRead, ReportReverse},
+    },
+    guards::{
+        guard_language::LanguageGuard, guard_permission::PermissionGuard,
+        guard_user::AuthenticationGuard,
+    },
+    routes::generic_functions::{ErrorResponse, catch_backend_error},
+    services::{service_export::ExportFile, service_report},
 };
 
+#[utoipa::path(
+    get,
+    path = "/report",
+    tag = "Reports",
+    summary = "Returns all movement reports",
+    responses(
+        (status = 200, description = "Movement reports", body = Vec<ReportViewDTO>),
+    ),
+)]
 #[get("/report")]
 pub async fn route_report_get_all(
     database: &State<DatabaseConnection>,
-    _authentication: AuthenticationGuard,
-    _viewer_authentication_guard: ViewerAuthenticationGuard,
+    _permission_guard: PermissionGuard<ReportRead>,
 ) -> Result<Json<Vec<ReportViewDTO>>, Custom<&'static str>> {
     let result = service_report::get_all_reports(database).await;
 
@@ -22,17 +44,152 @@ pub async fn route_report_get_all(
     }
 }
 
-#[put("/report", data="<report_update_dto>")]
-pub async fn route_report_update(
+#[utoipa::path(
+    get,
+    path = "/report/export",
+    tag = "Reports",
+    summary = "Exports the movement reports as a file",
+    params(
+        ("format" = Option<ExportFormat>, Query, description = "File format, `csv` by default"),
+        ("Accept-Language" = Option<String>, Header, description = "`en` for English column headers, Portuguese otherwise"),
+    ),
+    responses(
+        (status = 200, description = "Exported file", content((String = "text/csv"), (Vec<u8> = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"))),
+    ),
+)]
+#[get("/report/export?<format>")]
+pub async fn route_report_export(
+    database: &State<DatabaseConnection>,
+    _permission_guard: PermissionGuard<ReportRead>,
+    language_guard: LanguageGuard,
+    format: Option<ExportFormat>,
+) -> Result<ExportFile<'_>, Custom<&'static str>> {
+    let result = service_report::export_reports(
+        database,
+        format.unwrap_or(ExportFormat::Csv),
+        language_guard.get_language(),
+    )
+    .await;
+
+    match result {
+        Ok(export_file) => Ok(export_file),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/report/integrity",
+    tag = "Reports",
+    summary = "Lists products whose quantity differs from their movements",
+    responses(
+        (status = 200, description = "Inconsistent products", body = Vec<ReportIntegrityDTO>),
+    ),
+)]
+#[get("/report/integrity")]
+pub async fn route_report_integrity(
+    database: &State<DatabaseConnection>,
+    _permission_guard: PermissionGuard<ReportRead>,
+) -> Result<Json<Vec<ReportIntegrityDTO>>, Custom<&'static str>> {
+    let result = service_report::get_integrity_report(database).await;
+
+    match result {
+        Ok(mismatches) => Ok(Json(mismatches)),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/report/pdf/stock",
+    tag = "Reports",
+    summary = "Returns the stock position as a PDF",
+    responses(
+        (status = 200, description = "PDF document", body = Vec<u8>, content_type = "application/pdf"),
+    ),
+)]
+#[get("/report/pdf/stock")]
+pub async fn route_report_pdf_stock(
     database: &State<DatabaseConnection>,
-    _authentication: AuthenticationGuard,
-    _operator_authentication_guard: OperatorAuthenticationGuard,
-    report_update_dto: Json<ReportUpdateDTO>
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<ReportRead>,
+) -> Result<(ContentType, Vec<u8>), Custom<&'static str>> {
+    let result = service_report::generate_stock_position_pdf(database, authentication).await;
+
+    match result {
+        Ok(pdf) => Ok((ContentType::PDF, pdf)),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/report/pdf/product/{product_id}",
+    tag = "Reports",
+    summary = "Returns the movement statement of a product as a PDF",
+    params(
+        ("product_id" = u64, Path, description = "Product ID"),
+        ProductStatementFilterDTO,
+    ),
+    responses(
+        (status = 200, description = "PDF document", body = Vec<u8>, content_type = "application/pdf"),
+        (status = 400, response = ErrorResponse),
+        (status = 404, response = ErrorResponse),
+    ),
+)]
+#[get("/report/pdf/product/<product_id>?<product_statement_filter_dto..>")]
+pub async fn route_report_pdf_product(
+    database: &State<DatabaseConnection>,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<ReportRead>,
+    product_id: u64,
+    product_statement_filter_dto: ProductStatementFilterDTO,
+) -> Result<(ContentType, Vec<u8>), Custom<&'static str>> {
+    let result = service_report::generate_product_statement_pdf(
+        database,
+        product_id,
+        product_statement_filter_dto,
+        authentication,
+    )
+    .await;
+
+    match result {
+        Ok(pdf) => Ok((ContentType::PDF, pdf)),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/report/{report_id}/reversal",
+    tag = "Reports",
+    summary = "Reverses a movement",
+    params(
+        ("report_id" = u64, Path, description = "Report ID"),
+    ),
+    request_body = ReportReversalDTO,
+    responses(
+        (status = 201, description = "Reversal recorded"),
+        (status = 400, response = ErrorResponse),
+        (status = 404, response = ErrorResponse),
+        (status = 409, response = ErrorResponse),
+        (status = 423, response = ErrorResponse),
+    ),
+)]
+#[post("/report/<report_id>/reversal", data = "<report_reversal_dto>")]
+pub async fn route_report_reversal(
+    database: &State<DatabaseConnection>,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<ReportReverse>,
+    report_id: u64,
+    report_reversal_dto: Json<ReportReversalDTO>,
 ) -> Result<Status, Custom<&'static str>> {
-    let result = service_report::update_report(database, report_update_dto.0).await;
+    let result =
+        service_report::reverse_report(database, report_id, report_reversal_dto.0, authentication)
+            .await;
 
     match result {
-        Ok(_) => Ok(Status::Ok),
+        Ok(_) => Ok(Status::Created),
         Err(backend_error) => Err(catch_backend_error(backend_error)),
     }
-}
\ No newline at end of file
+}
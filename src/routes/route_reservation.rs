@@ -0,0 +1,94 @@
+use rocket::{State, http::Status, response::status::Custom, serde::json::Json};
+use sea_orm::DatabaseConnection;
+
+use crate::{
+    entities::dtos::reservation_dtos::{ReservationCreateDTO, ReservationViewDTO},
+    entities::enums::permission_enums::ReservationManage,
+    guards::{guard_permission::PermissionGuard, guard_user::AuthenticationGuard},
+    routes::generic_functions::{ErrorResponse, catch_backend_error},
+    services::service_reservation,
+};
+
+#[utoipa::path(
+    get,
+    path = "/reservation",
+    tag = "Reservations",
+    summary = "Returns the active reservations",
+    params(
+        ("product_id" = Option<u64>, Query, description = "Only reservations of this product"),
+    ),
+    responses(
+        (status = 200, description = "Active reservations", body = Vec<ReservationViewDTO>),
+    ),
+)]
+#[get("/reservation?<product_id>")]
+pub async fn route_reservation_get_all(
+    database: &State<DatabaseConnection>,
+    _permission_guard: PermissionGuard<ReservationManage>,
+    product_id: Option<u64>,
+) -> Result<Json<Vec<ReservationViewDTO>>, Custom<&'static str>> {
+    let result = service_reservation::get_active_reservations(database, product_id).await;
+
+    match result {
+        Ok(reservations) => Ok(Json(reservations)),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/reservation",
+    tag = "Reservations",
+    summary = "Reserves a quantity of a product",
+    request_body = ReservationCreateDTO,
+    responses(
+        (status = 201, description = "Reservation created"),
+        (status = 400, response = ErrorResponse),
+        (status = 404, response = ErrorResponse),
+    ),
+)]
+#[post("/reservation", data = "<reservation_create_dto>")]
+pub async fn route_reservation_create(
+    database: &State<DatabaseConnection>,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<ReservationManage>,
+    reservation_create_dto: Json<ReservationCreateDTO>,
+) -> Result<Status, Custom<&'static str>> {
+    let result =
+        service_reservation::create_reservation(database, reservation_create_dto.0, authentication)
+            .await;
+
+    match result {
+        Ok(_) => Ok(Status::Created),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/reservation/{reservation_id}",
+    tag = "Reservations",
+    summary = "Releases a reservation",
+    params(
+        ("reservation_id" = u64, Path, description = "Reservation ID"),
+    ),
+    responses(
+        (status = 200, description = "Reservation released"),
+        (status = 404, response = ErrorResponse),
+    ),
+)]
+#[delete("/reservation/<reservation_id>")]
+pub async fn route_reservation_release(
+    database: &State<DatabaseConnection>,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<ReservationManage>,
+    reservation_id: u64,
+) -> Result<Status, Custom<&'static str>> {
+    let result =
+        service_reservation::release_reservation(database, reservation_id, authentication).await;
+
+    match result {
+        Ok(_) => Ok(Status::Ok),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
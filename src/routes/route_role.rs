@@ -0,0 +1,179 @@
+use rocket::{State, http::Status, response::status::Custom, serde::json::Json};
+use sea_orm::DatabaseConnection;
+
+use crate::{
+    entities::dtos::role_dtos::{PermissionDTO, PermissionsUpdateDTO, RoleDTO, RoleViewDTO},
+    entities::enums::permission_enums::RoleManage,
+    guards::{guard_permission::PermissionGuard, guard_user::AuthenticationGuard},
+    routes::generic_functions::{ErrorResponse, catch_backend_error},
+    services::service_role,
+};
+
+#[utoipa::path(
+    get,
+    path = "/role",
+    tag = "Roles and permissions",
+    summary = "Returns all roles",
+    responses(
+        (status = 200, description = "Roles", body = Vec<RoleViewDTO>),
+    ),
+)]
+#[get("/role")]
+pub async fn route_role_get_all(
+    database: &State<DatabaseConnection>,
+    _permission_guard: PermissionGuard<RoleManage>,
+) -> Result<Json<Vec<RoleViewDTO>>, Custom<&'static str>> {
+    let result = service_role::get_all_roles(database).await;
+
+    match result {
+        Ok(roles) => Ok(Json(roles)),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/permission",
+    tag = "Roles and permissions",
+    summary = "Returns all permissions",
+    responses(
+        (status = 200, description = "Permissions", body = Vec<PermissionDTO>),
+    ),
+)]
+#[get("/permission")]
+pub async fn route_permission_get_all(
+    database: &State<DatabaseConnection>,
+    _permission_guard: PermissionGuard<RoleManage>,
+) -> Result<Json<Vec<PermissionDTO>>, Custom<&'static str>> {
+    let result = service_role::get_all_permissions(database).await;
+
+    match result {
+        Ok(permissions) => Ok(Json(permissions)),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/role",
+    tag = "Roles and permissions",
+    summary = "Creates a role",
+    request_body = RoleDTO,
+    responses(
+        (status = 201, description = "Role created"),
+        (status = 400, response = ErrorResponse),
+        (status = 409, response = ErrorResponse),
+    ),
+)]
+#[post("/role", data = "<role_dto>")]
+pub async fn route_role_create(
+    database: &State<DatabaseConnection>,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<RoleManage>,
+    role_dto: Json<RoleDTO>,
+) -> Result<Status, Custom<&'static str>> {
+    let result = service_role::create_role(database, role_dto.0, authentication).await;
+
+    match result {
+        Ok(_) => Ok(Status::Created),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/role/{role_id}",
+    tag = "Roles and permissions",
+    summary = "Updates a role",
+    params(
+        ("role_id" = u8, Path, description = "Role ID"),
+    ),
+    request_body = RoleDTO,
+    responses(
+        (status = 200, description = "Role updated"),
+        (status = 400, response = ErrorResponse),
+        (status = 404, response = ErrorResponse),
+        (status = 409, response = ErrorResponse),
+    ),
+)]
+#[put("/role/<role_id>", data = "<role_dto>")]
+pub async fn route_role_update(
+    database: &State<DatabaseConnection>,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<RoleManage>,
+    role_id: u8,
+    role_dto: Json<RoleDTO>,
+) -> Result<Status, Custom<&'static str>> {
+    let result = service_role::update_role(database, role_id, role_dto.0, authentication).await;
+
+    match result {
+        Ok(_) => Ok(Status::Ok),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/role/{role_id}",
+    tag = "Roles and permissions",
+    summary = "Deletes a role",
+    params(
+        ("role_id" = u8, Path, description = "Role ID"),
+    ),
+    responses(
+        (status = 200, description = "Role deleted"),
+        (status = 404, response = ErrorResponse),
+        (status = 409, response = ErrorResponse),
+    ),
+)]
+#[delete("/role/<role_id>")]
+pub async fn route_role_delete(
+    database: &State<DatabaseConnection>,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<RoleManage>,
+    role_id: u8,
+) -> Result<Status, Custom<&'static str>> {
+    let result = service_role::delete_role(database, role_id, authentication).await;
+
+    match result {
+        Ok(_) => Ok(Status::Ok),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/user/{user_id}/permissions",
+    tag = "Roles and permissions",
+    summary = "Replaces the extra permissions of a user",
+    params(
+        ("user_id" = u64, Path, description = "User ID"),
+    ),
+    request_body = PermissionsUpdateDTO,
+    responses(
+        (status = 200, description = "Permissions updated"),
+        (status = 400, response = ErrorResponse),
+        (status = 404, response = ErrorResponse),
+    ),
+)]
+#[put("/user/<user_id>/permissions", data = "<permissions_update_dto>")]
+pub async fn route_user_permissions_update(
+    database: &State<DatabaseConnection>,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<RoleManage>,
+    user_id: u64,
+    permissions_update_dto: Json<PermissionsUpdateDTO>,
+) -> Result<Status, Custom<&'static str>> {
+    let result = service_role::update_user_permissions(
+        database,
+        user_id,
+        permissions_update_dto.0,
+        authentication,
+    )
+    .await;
+
+    match result {
+        Ok(_) => Ok(Status::Ok),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
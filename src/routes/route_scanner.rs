@@ -0,0 +1,171 @@
+use std::pin::Pin;
+
+use rocket::{
+    Request, State,
+    data::{IoHandler, IoStream},
+    futures::{SinkExt, StreamExt},
+    http::Header,
+    response::{self, Responder, Response},
+    tokio::io,
+};
+use sea_orm::DatabaseConnection;
+use tokio_tungstenite::{
+    WebSocketStream,
+    tungstenite::{Message, protocol::Role, protocol::WebSocketConfig},
+};
+
+use crate::{
+    entities::{
+        dtos::scanner_dtos::{ScannerCommandDTO, ScannerReplyDTO},
+        enums::permission_enums::StockMove,
+    },
+    errors::BackendError,
+    guards::{
+        guard_permission::PermissionGuard, guard_user::AuthenticationGuard,
+        guard_websocket::WebSocketGuard,
+    },
+    routes::generic_functions::{ErrorResponse, catch_backend_error},
+    services::service_scanner,
+};
+
+const MAX_MESSAGE_BYTES: usize = 64 * 1024;
+
+pub struct ScannerSession {
+    database: DatabaseConnection,
+    authentication: AuthenticationGuard,
+    accept_key: String,
+}
+
+#[utoipa::path(
+    get,
+    path = "/scanner",
+    tag = "Scanner",
+    summary = "Opens the WebSocket used by handheld terminals",
+    responses(
+        (status = 101, description = "Switched to the WebSocket protocol"),
+        (status = 426, response = ErrorResponse),
+    ),
+)]
+#[get("/scanner")]
+pub async fn route_scanner(
+    database: &State<DatabaseConnection>,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<StockMove>,
+    websocket_guard: WebSocketGuard,
+) -> ScannerSession {
+    ScannerSession {
+        database: database.inner().clone(),
+        authentication,
+        accept_key: websocket_guard.get_accept_key().clone(),
+    }
+}
+
+impl<'r> Responder<'r, 'static> for ScannerSession {
+    fn respond_to(self, _request: &'r Request<'_>) -> response::Result<'static> {
+        Response::build()
+            .header(Header::new("Sec-WebSocket-Accept", self.accept_key.clone()))
+            .upgrade("websocket", self)
+            .ok()
+    }
+}
+
+#[async_trait]
+impl IoHandler for ScannerSession {
+    async fn io(self: Pin<Box<Self>>, io: IoStream) -> io::Result<()> {
+        let config = WebSocketConfig::default().max_message_size(Some(MAX_MESSAGE_BYTES));
+        let mut socket = WebSocketStream::from_raw_socket(io, Role::Server, Some(config)).await;
+
+        while let Some(message) = socket.next().await {
+            let text = match message {
+                Ok(Message::Text(text)) => text,
+                Ok(Message::Close(_)) | Err(_) => break,
+                // Pings are answered by the socket itself and binary frames are not part of the protocol
+                Ok(_) => continue,
+            };
+
+            // A revoked key, a deactivated user or a lost permission ends the session
+            let (reply, authorized) =
+                match service_scanner::authorize(&self.database, &self.authentication).await {
+                    Ok(authentication) => (
+                        self.handle_command(text.as_str(), authentication).await,
+                        true,
+                    ),
+                    Err(backend_error) => (error_reply(None, backend_error), false),
+                };
+
+            let reply = match serde_json::to_string(&reply) {
+                Ok(reply) => reply,
+                Err(_) => continue,
+            };
+
+            if socket.send(Message::text(reply)).await.is_err() || !authorized {
+                break;
+            }
+        }
+
+        let _ = socket.close(None).await;
+
+        Ok(())
+    }
+}
+
+impl ScannerSession {
+    async fn handle_command(
+        &self,
+        text: &str,
+        authentication: AuthenticationGuard,
+    ) -> ScannerReplyDTO {
+        let command = match serde_json::from_str::<ScannerCommandDTO>(text) {
+            Ok(command) => command,
+            Err(_) => return error_reply(None, BackendError::InvalidValuesError),
+        };
+
+        match command {
+            ScannerCommandDTO::Scan {
+                request_id,
+                barcode,
+            } => match service_scanner::scan(&self.database, &barcode).await {
+                Ok(product) => ScannerReplyDTO::Product {
+                    request_id,
+                    product,
+                },
+                Err(backend_error) => error_reply(request_id, backend_error),
+            },
+            ScannerCommandDTO::Movement {
+                request_id,
+                barcode,
+                quantity,
+                direction,
+                reason_id,
+            } => {
+                let result = service_scanner::move_stock(
+                    &self.database,
+                    &barcode,
+                    quantity,
+                    direction,
+                    reason_id,
+                    authentication,
+                )
+                .await;
+
+                match result {
+                    Ok(product) => ScannerReplyDTO::Ack {
+                        request_id,
+                        product,
+                    },
+                    Err(backend_error) => error_reply(request_id, backend_error),
+                }
+            }
+        }
+    }
+}
+
+fn error_reply(request_id: Option<String>, backend_error: BackendError) -> ScannerReplyDTO {
+    let error = catch_backend_error(backend_error);
+
+    ScannerReplyDTO::Error {
+        request_id,
+        status: error.0.code,
+        message: error.1,
+    }
+}
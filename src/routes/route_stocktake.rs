@@ -0,0 +1,185 @@
+use rocket::{State, http::Status, response::status::Custom, serde::json::Json};
+use sea_orm::DatabaseConnection;
+
+use crate::{
+    entities::dtos::stocktake_dtos::{
+        StocktakeCountDTO, StocktakeCreateDTO, StocktakeItemViewDTO, StocktakeViewDTO,
+    },
+    entities::enums::permission_enums::{StocktakeCount, StocktakeManage},
+    guards::{guard_permission::PermissionGuard, guard_user::AuthenticationGuard},
+    routes::generic_functions::{ErrorResponse, catch_backend_error},
+    services::service_stocktake,
+};
+
+#[utoipa::path(
+    get,
+    path = "/stocktake",
+    tag = "Stocktakes",
+    summary = "Returns all stocktakes",
+    responses(
+        (status = 200, description = "Stocktakes", body = Vec<StocktakeViewDTO>),
+    ),
+)]
+#[get("/stocktake")]
+pub async fn route_stocktake_get_all(
+    database: &State<DatabaseConnection>,
+    _permission_guard: PermissionGuard<StocktakeCount>,
+) -> Result<Json<Vec<StocktakeViewDTO>>, Custom<&'static str>> {
+    let result = service_stocktake::get_all_stocktakes(database).await;
+
+    match result {
+        Ok(stocktakes) => Ok(Json(stocktakes)),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/stocktake/{stocktake_id}",
+    tag = "Stocktakes",
+    summary = "Returns the products of a stocktake",
+    params(
+        ("stocktake_id" = u64, Path, description = "Stocktake ID"),
+    ),
+    responses(
+        (status = 200, description = "Stocktake products", body = Vec<StocktakeItemViewDTO>),
+        (status = 404, response = ErrorResponse),
+    ),
+)]
+#[get("/stocktake/<stocktake_id>")]
+pub async fn route_stocktake_get_items(
+    database: &State<DatabaseConnection>,
+    _permission_guard: PermissionGuard<StocktakeCount>,
+    stocktake_id: u64,
+) -> Result<Json<Vec<StocktakeItemViewDTO>>, Custom<&'static str>> {
+    let result = service_stocktake::get_stocktake_items(database, stocktake_id).await;
+
+    match result {
+        Ok(items) => Ok(Json(items)),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/stocktake",
+    tag = "Stocktakes",
+    summary = "Opens a stocktake",
+    request_body = StocktakeCreateDTO,
+    responses(
+        (status = 201, description = "Stocktake opened"),
+        (status = 400, response = ErrorResponse),
+        (status = 404, response = ErrorResponse),
+        (status = 423, response = ErrorResponse),
+    ),
+)]
+#[post("/stocktake", data = "<stocktake_create_dto>")]
+pub async fn route_stocktake_create(
+    database: &State<DatabaseConnection>,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<StocktakeManage>,
+    stocktake_create_dto: Json<StocktakeCreateDTO>,
+) -> Result<Status, Custom<&'static str>> {
+    let result =
+        service_stocktake::create_stocktake(database, stocktake_create_dto.0, authentication).await;
+
+    match result {
+        Ok(_) => Ok(Status::Created),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/stocktake/{stocktake_id}/count",
+    tag = "Stocktakes",
+    summary = "Records a counted quantity",
+    params(
+        ("stocktake_id" = u64, Path, description = "Stocktake ID"),
+    ),
+    request_body = StocktakeCountDTO,
+    responses(
+        (status = 200, description = "Count recorded"),
+        (status = 400, response = ErrorResponse),
+        (status = 404, response = ErrorResponse),
+    ),
+)]
+#[put("/stocktake/<stocktake_id>/count", data = "<stocktake_count_dto>")]
+pub async fn route_stocktake_count(
+    database: &State<DatabaseConnection>,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<StocktakeCount>,
+    stocktake_id: u64,
+    stocktake_count_dto: Json<StocktakeCountDTO>,
+) -> Result<Status, Custom<&'static str>> {
+    let result = service_stocktake::register_count(
+        database,
+        stocktake_id,
+        stocktake_count_dto.0,
+        authentication,
+    )
+    .await;
+
+    match result {
+        Ok(_) => Ok(Status::Ok),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/stocktake/{stocktake_id}/approve",
+    tag = "Stocktakes",
+    summary = "Approves a stocktake",
+    params(
+        ("stocktake_id" = u64, Path, description = "Stocktake ID"),
+    ),
+    responses(
+        (status = 200, description = "Stocktake approved"),
+        (status = 400, response = ErrorResponse),
+        (status = 404, response = ErrorResponse),
+    ),
+)]
+#[post("/stocktake/<stocktake_id>/approve")]
+pub async fn route_stocktake_approve(
+    database: &State<DatabaseConnection>,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<StocktakeManage>,
+    stocktake_id: u64,
+) -> Result<Status, Custom<&'static str>> {
+    let result = service_stocktake::approve_stocktake(database, stocktake_id, authentication).await;
+
+    match result {
+        Ok(_) => Ok(Status::Ok),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/stocktake/{stocktake_id}/cancel",
+    tag = "Stocktakes",
+    summary = "Cancels a stocktake",
+    params(
+        ("stocktake_id" = u64, Path, description = "Stocktake ID"),
+    ),
+    responses(
+        (status = 200, description = "Stocktake cancelled"),
+        (status = 400, response = ErrorResponse),
+        (status = 404, response = ErrorResponse),
+    ),
+)]
+#[post("/stocktake/<stocktake_id>/cancel")]
+pub async fn route_stocktake_cancel(
+    database: &State<DatabaseConnection>,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<StocktakeManage>,
+    stocktake_id: u64,
+) -> Result<Status, Custom<&'static str>> {
+    let result = service_stocktake::cancel_stocktake(database, stocktake_id, authentication).await;
+
+    match result {
+        Ok(_) => Ok(Status::Ok),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
@@ -2,22 +2,45 @@ use rocket::{State, http::Status, response::status::Custom, serde::json::Json};
 use sea_orm::DatabaseConnection;
 
 use crate::{
+    entities::dtos::generic_dtos::SortDTO,
     entities::dtos::user_dtos::{
-        AuthenticationDTO, LoginDTO, UserCreateDTO, UserCredentialsUpdateDTO,
-        UserInformationsUpdateDTO, UserSummaryForAdminDTO, ValidedTokenDTO,
+        AuthenticationDTO, LoginDTO, LoginResultDTO, TwoFactorChallengeDTO, TwoFactorCodeDTO,
+        TwoFactorEnrollmentDTO, TwoFactorLoginDTO, UserActiveUpdateDTO, UserAdminUpdateDTO,
+        UserCreateDTO, UserCredentialsUpdateDTO, UserInformationsUpdateDTO, UserPasswordResetDTO,
+        UserPatchDTO, UserSummaryForAdminDTO, ValidedTokenDTO,
+    },
+    entities::enums::{
+        export_enums::ExportFormat,
+        permission_enums::{UserManage, UserSecurity},
+    },
+    guards::{
+        guard_client_ip::ClientIpGuard, guard_language::LanguageGuard,
+        guard_permission::PermissionGuard, guard_user::AuthenticationGuard,
+    },
+    routes::generic_functions::{ErrorResponse, catch_backend_error},
+    services::{
+        service_export::ExportFile,
+        service_user::{self},
     },
-    guards::guard_user::{AuthenticationGuard, MannagerAuthenticationGuard},
-    routes::generic_functions::catch_backend_error,
-    services::service_user::{self},
 };
 
-#[get("/user")]
+#[utoipa::path(
+    get,
+    path = "/user",
+    tag = "Users",
+    summary = "Returns all users",
+    params(SortDTO),
+    responses(
+        (status = 200, description = "Users", body = Vec<UserSummaryForAdminDTO>),
+    ),
+)]
+#[get("/user?<sort_dto..>")]
 pub async fn route_user_get_all(
     database: &State<DatabaseConnection>,
-    _authentication: AuthenticationGuard,
-    _mannager_authentication_guard: MannagerAuthenticationGuard,
+    _permission_guard: PermissionGuard<UserManage>,
+    sort_dto: SortDTO,
 ) -> Result<Json<Vec<UserSummaryForAdminDTO>>, Custom<&'static str>> {
-    let result = service_user::get_all_users(database).await;
+    let result = service_user::get_all_users(database, &sort_dto).await;
 
     match result {
         Ok(users) => Ok(Json(users)),
@@ -25,12 +48,86 @@ pub async fn route_user_get_all(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/user/export",
+    tag = "Users",
+    summary = "Exports the users as a file",
+    params(
+        ("format" = Option<ExportFormat>, Query, description = "File format, `csv` by default"),
+        ("Accept-Language" = Option<String>, Header, description = "`en` for English column headers, Portuguese otherwise"),
+    ),
+    responses(
+        (status = 200, description = "Exported file", content((String = "text/csv"), (Vec<u8> = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"))),
+    ),
+)]
+#[get("/user/export?<format>")]
+pub async fn route_user_export(
+    database: &State<DatabaseConnection>,
+    _permission_guard: PermissionGuard<UserManage>,
+    language_guard: LanguageGuard,
+    format: Option<ExportFormat>,
+) -> Result<ExportFile<'_>, Custom<&'static str>> {
+    let result = service_user::export_users(
+        database,
+        format.unwrap_or(ExportFormat::Csv),
+        language_guard.get_language(),
+    )
+    .await;
+
+    match result {
+        Ok(export_file) => Ok(export_file),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/login",
+    tag = "Authentication",
+    summary = "Logs in with e-mail and password",
+    request_body = LoginDTO,
+    responses(
+        (status = 200, description = "Authentication token or two-factor challenge", body = LoginResultDTO),
+        (status = 429, response = ErrorResponse),
+    ),
+    security(()),
+)]
 #[post("/login", data = "<login_dto>")]
 pub async fn route_login(
     database: &State<DatabaseConnection>,
+    client_ip_guard: ClientIpGuard,
     login_dto: Json<LoginDTO>,
+) -> Result<Json<LoginResultDTO>, Custom<&'static str>> {
+    let result = service_user::login(database, login_dto.0, client_ip_guard.get_ip()).await;
+
+    match result {
+        Ok(login_result) => Ok(Json(login_result)),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/login/2fa",
+    tag = "Authentication",
+    summary = "Completes a login with a two-factor code",
+    request_body = TwoFactorLoginDTO,
+    responses(
+        (status = 200, description = "Authentication token", body = AuthenticationDTO),
+        (status = 429, response = ErrorResponse),
+    ),
+    security(()),
+)]
+#[post("/login/2fa", data = "<two_factor_login_dto>")]
+pub async fn route_login_two_factor(
+    database: &State<DatabaseConnection>,
+    client_ip_guard: ClientIpGuard,
+    two_factor_login_dto: Json<TwoFactorLoginDTO>,
 ) -> Result<Json<AuthenticationDTO>, Custom<&'static str>> {
-    let result = service_user::login(database, login_dto.0).await;
+    let result =
+        service_user::login_two_factor(database, two_factor_login_dto.0, client_ip_guard.get_ip())
+            .await;
 
     match result {
         Ok(token) => Ok(Json(token)),
@@ -38,6 +135,43 @@ pub async fn route_login(
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/login/2fa/enrollment",
+    tag = "Authentication",
+    summary = "Starts a required two-factor enrollment",
+    request_body = TwoFactorChallengeDTO,
+    responses(
+        (status = 200, description = "Enrollment data", body = TwoFactorEnrollmentDTO),
+    ),
+    security(()),
+)]
+#[post("/login/2fa/enrollment", data = "<two_factor_challenge_dto>")]
+pub async fn route_login_two_factor_enrollment(
+    database: &State<DatabaseConnection>,
+    two_factor_challenge_dto: Json<TwoFactorChallengeDTO>,
+) -> Result<Json<TwoFactorEnrollmentDTO>, Custom<&'static str>> {
+    let result = service_user::start_two_factor_enrollment_by_challenge(
+        database,
+        two_factor_challenge_dto.0,
+    )
+    .await;
+
+    match result {
+        Ok(two_factor_enrollment) => Ok(Json(two_factor_enrollment)),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/login/valid",
+    tag = "Authentication",
+    summary = "Checks if the current token is valid",
+    responses(
+        (status = 200, description = "Token state", body = ValidedTokenDTO),
+    ),
+)]
 #[get("/login/valid")]
 pub async fn route_valid_token(
     database: &State<DatabaseConnection>,
@@ -51,13 +185,26 @@ pub async fn route_valid_token(
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/user",
+    tag = "Users",
+    summary = "Creates a user",
+    request_body = UserCreateDTO,
+    responses(
+        (status = 201, description = "User created"),
+        (status = 400, response = ErrorResponse),
+        (status = 409, response = ErrorResponse),
+    ),
+)]
 #[post("/user", data = "<user_create_dto>")]
 pub async fn route_user_create(
     database: &State<DatabaseConnection>,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<UserManage>,
     user_create_dto: Json<UserCreateDTO>,
-    _mannager_authentication_guard: MannagerAuthenticationGuard,
 ) -> Result<Status, Custom<&'static str>> {
-    let result = service_user::create_user(database, user_create_dto.0).await;
+    let result = service_user::create_user(database, user_create_dto.0, authentication).await;
 
     match result {
         Ok(_) => Ok(Status::Created),
@@ -65,6 +212,18 @@ pub async fn route_user_create(
     }
 }
 
+#[utoipa::path(
+    put,
+    path = "/user/informations",
+    tag = "Users",
+    summary = "Updates the logged user information",
+    request_body = UserInformationsUpdateDTO,
+    responses(
+        (status = 200, description = "User updated"),
+        (status = 400, response = ErrorResponse),
+        (status = 409, response = ErrorResponse),
+    ),
+)]
 #[put("/user/informations", data = "<user_update_dto>")]
 pub async fn route_user_update_informations(
     database: &State<DatabaseConnection>,
@@ -80,6 +239,17 @@ pub async fn route_user_update_informations(
     }
 }
 
+#[utoipa::path(
+    put,
+    path = "/user/credentials",
+    tag = "Users",
+    summary = "Updates the logged user password",
+    request_body = UserCredentialsUpdateDTO,
+    responses(
+        (status = 200, description = "Password updated"),
+        (status = 400, response = ErrorResponse),
+    ),
+)]
 #[put("/user/credentials", data = "<user_update_dto>")]
 pub async fn route_user_update_credentials(
     database: &State<DatabaseConnection>,
@@ -95,14 +265,275 @@ pub async fn route_user_update_credentials(
     }
 }
 
+#[utoipa::path(
+    put,
+    path = "/user/{user_id}",
+    tag = "Users",
+    summary = "Updates another user",
+    params(
+        ("user_id" = u64, Path, description = "User ID"),
+    ),
+    request_body = UserAdminUpdateDTO,
+    responses(
+        (status = 200, description = "User updated"),
+        (status = 400, response = ErrorResponse),
+        (status = 404, response = ErrorResponse),
+        (status = 409, response = ErrorResponse),
+    ),
+)]
+#[put("/user/<user_id>", data = "<user_update_dto>")]
+pub async fn route_user_update(
+    database: &State<DatabaseConnection>,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<UserManage>,
+    user_id: u64,
+    user_update_dto: Json<UserAdminUpdateDTO>,
+) -> Result<Status, Custom<&'static str>> {
+    let result =
+        service_user::update_user(database, user_id, user_update_dto.0, authentication).await;
+
+    match result {
+        Ok(_) => Ok(Status::Ok),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    patch,
+    path = "/user/{user_id}",
+    tag = "Users",
+    summary = "Partially updates another user with a JSON merge patch",
+    params(
+        ("user_id" = u64, Path, description = "User ID"),
+    ),
+    request_body(content = UserPatchDTO, content_type = "application/merge-patch+json"),
+    responses(
+        (status = 200, description = "User updated"),
+        (status = 400, response = ErrorResponse),
+        (status = 404, response = ErrorResponse),
+        (status = 409, response = ErrorResponse),
+    ),
+)]
+#[patch("/user/<user_id>", data = "<user_patch_dto>")]
+pub async fn route_user_patch(
+    database: &State<DatabaseConnection>,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<UserManage>,
+    user_id: u64,
+    user_patch_dto: Json<UserPatchDTO>,
+) -> Result<Status, Custom<&'static str>> {
+    let result =
+        service_user::patch_user(database, user_id, user_patch_dto.0, authentication).await;
+
+    match result {
+        Ok(_) => Ok(Status::Ok),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/user/{user_id}/password",
+    tag = "Users",
+    summary = "Resets the password of a user",
+    params(
+        ("user_id" = u64, Path, description = "User ID"),
+    ),
+    request_body = UserPasswordResetDTO,
+    responses(
+        (status = 200, description = "Password reset"),
+        (status = 400, response = ErrorResponse),
+        (status = 404, response = ErrorResponse),
+    ),
+)]
+#[put("/user/<user_id>/password", data = "<user_password_reset_dto>")]
+pub async fn route_user_reset_password(
+    database: &State<DatabaseConnection>,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<UserSecurity>,
+    user_id: u64,
+    user_password_reset_dto: Json<UserPasswordResetDTO>,
+) -> Result<Status, Custom<&'static str>> {
+    let result = service_user::reset_user_password(
+        database,
+        user_id,
+        user_password_reset_dto.0,
+        authentication,
+    )
+    .await;
+
+    match result {
+        Ok(_) => Ok(Status::Ok),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/user/{user_id}/active",
+    tag = "Users",
+    summary = "Activates or deactivates a user",
+    params(
+        ("user_id" = u64, Path, description = "User ID"),
+    ),
+    request_body = UserActiveUpdateDTO,
+    responses(
+        (status = 200, description = "User updated"),
+        (status = 404, response = ErrorResponse),
+    ),
+)]
+#[put("/user/<user_id>/active", data = "<user_active_update_dto>")]
+pub async fn route_user_update_active(
+    database: &State<DatabaseConnection>,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<UserManage>,
+    user_id: u64,
+    user_active_update_dto: Json<UserActiveUpdateDTO>,
+) -> Result<Status, Custom<&'static str>> {
+    let result = service_user::update_user_active(
+        database,
+        user_id,
+        user_active_update_dto.0,
+        authentication,
+    )
+    .await;
+
+    match result {
+        Ok(_) => Ok(Status::Ok),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/user/{user_id}/unlock",
+    tag = "Users",
+    summary = "Unlocks a user locked by failed logins",
+    params(
+        ("user_id" = u64, Path, description = "User ID"),
+    ),
+    responses(
+        (status = 200, description = "User unlocked"),
+        (status = 404, response = ErrorResponse),
+    ),
+)]
+#[put("/user/<user_id>/unlock")]
+pub async fn route_user_unlock(
+    database: &State<DatabaseConnection>,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<UserSecurity>,
+    user_id: u64,
+) -> Result<Status, Custom<&'static str>> {
+    let result = service_user::unlock_user(database, user_id, authentication).await;
+
+    match result {
+        Ok(_) => Ok(Status::Ok),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/user/2fa",
+    tag = "Users",
+    summary = "Starts the two-factor enrollment of the logged user",
+    responses(
+        (status = 200, description = "Enrollment data", body = TwoFactorEnrollmentDTO),
+        (status = 409, response = ErrorResponse),
+    ),
+)]
+#[post("/user/2fa")]
+pub async fn route_user_two_factor_enrollment(
+    database: &State<DatabaseConnection>,
+    authentication: AuthenticationGuard,
+) -> Result<Json<TwoFactorEnrollmentDTO>, Custom<&'static str>> {
+    let result = service_user::start_two_factor_enrollment(database, authentication).await;
+
+    match result {
+        Ok(two_factor_enrollment) => Ok(Json(two_factor_enrollment)),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/user/2fa",
+    tag = "Users",
+    summary = "Confirms the two-factor enrollment",
+    request_body = TwoFactorCodeDTO,
+    responses(
+        (status = 200, description = "Two-factor authentication enabled"),
+        (status = 400, response = ErrorResponse),
+    ),
+)]
+#[put("/user/2fa", data = "<two_factor_code_dto>")]
+pub async fn route_user_two_factor_confirm(
+    database: &State<DatabaseConnection>,
+    authentication: AuthenticationGuard,
+    two_factor_code_dto: Json<TwoFactorCodeDTO>,
+) -> Result<Status, Custom<&'static str>> {
+    let result = service_user::confirm_two_factor_enrollment(
+        database,
+        two_factor_code_dto.0,
+        authentication,
+    )
+    .await;
+
+    match result {
+        Ok(_) => Ok(Status::Ok),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/user/{user_id}/2fa",
+    tag = "Users",
+    summary = "Removes the two-factor authentication of a user",
+    params(
+        ("user_id" = u64, Path, description = "User ID"),
+    ),
+    responses(
+        (status = 200, description = "Two-factor authentication removed"),
+        (status = 404, response = ErrorResponse),
+    ),
+)]
+#[delete("/user/<user_id>/2fa")]
+pub async fn route_user_two_factor_reset(
+    database: &State<DatabaseConnection>,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<UserSecurity>,
+    user_id: u64,
+) -> Result<Status, Custom<&'static str>> {
+    let result = service_user::reset_two_factor(database, user_id, authentication).await;
+
+    match result {
+        Ok(_) => Ok(Status::Ok),
+        Err(backend_error) => Err(catch_backend_error(backend_error)),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/user/{user_id}",
+    tag = "Users",
+    summary = "Deletes a user",
+    params(
+        ("user_id" = u64, Path, description = "User ID"),
+    ),
+    responses(
+        (status = 200, description = "User deleted"),
+        (status = 404, response = ErrorResponse),
+    ),
+)]
 #[delete("/user/<user_id>")]
 pub async fn route_user_delete(
     database: &State<DatabaseConnection>,
-    _authentication: AuthenticationGuard,
-    _mannager_authentication_guard: MannagerAuthenticationGuard,
+    authentication: AuthenticationGuard,
+    _permission_guard: PermissionGuard<UserManage>,
     user_id: u64,
 ) -> Result<Status, Custom<&'static str>> {
-    let result = service_user::delete_user_by_id(database, user_id).await;
+    let result = service_user::delete_user_by_id(database, user_id, authentication).await;
 
     match result {
         Ok(_) => Ok(Status::Ok),
@@ -1,5 +1,18 @@
+pub mod service_analytics;
+pub mod service_api_key;
+pub mod service_audit;
 pub mod service_category;
+pub mod service_event;
+pub mod service_export;
+pub mod service_import;
+pub mod service_login_attempt;
+pub mod service_pdf;
 pub mod service_product;
+pub mod service_purge;
 pub mod service_reason;
 pub mod service_report;
+pub mod service_reservation;
+pub mod service_role;
+pub mod service_scanner;
+pub mod service_stocktake;
 pub mod service_user;
@@ -0,0 +1,689 @@
+use std::collections::HashMap;
+
+use chrono::{Datelike, Days, Duration, Months, NaiveDate, NaiveDateTime, Utc};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait, FromQueryResult,
+    QueryFilter, Statement, TransactionTrait, Value, sea_query::Expr,
+};
+
+use crate::{
+    configs::config_environment::{
+        get_abc_class_percents, get_abc_window_days, get_forecast_lead_time_days,
+        get_forecast_method, get_forecast_smoothing_alpha, get_forecast_window_days,
+    },
+    entities::{
+        dtos::analytics_dtos::{
+            AbcClassificationDTO, BucketMovementDTO, ConsumptionDTO, ConsumptionFilterDTO,
+            CoverDTO, DailyConsumptionDTO, DeadStockDTO, ForecastDTO, ForecastFilterDTO,
+            ForecastProductDTO, MoverDTO, ProductVolumeDTO, RankingDTO, RankingFilterDTO,
+            StockHistoryFilterDTO, StockHistoryPointDTO,
+        },
+        enums::analytics_enums::{AbcClass, AnalyticsBucket, AnalyticsGroup, ForecastMethod},
+        tb_product,
+    },
+    errors::BackendError,
+    services::{
+        service_report,
+        service_reservation::{AVAILABLE_QUANTITY, RESERVED_QUANTITY_JOIN},
+    },
+};
+
+const DEFAULT_PERIOD_DAYS: i64 = 30;
+const MAX_BUCKETS: usize = 1000;
+const DEFAULT_RANKING_LIMIT: u64 = 10;
+const MAX_RANKING_LIMIT: u64 = 100;
+const MAX_FORECAST_WINDOW_DAYS: u32 = 365;
+const MAX_ABC_WINDOW_DAYS: u32 = 730;
+const ABC_UPDATE_CHUNK: usize = 1000;
+
+pub const CONSUMPTION_CONDITION: &str = r#"
+    tb_report.change_type = FALSE
+        AND tb_report.reversal_of IS NULL
+        AND NOT EXISTS (
+            SELECT 1
+            FROM tb_report AS reversal
+            WHERE reversal.reversal_of = tb_report.id
+        )
+"#;
+
+pub async fn get_stock_history(
+    database: &DatabaseConnection,
+    stock_history_filter_dto: StockHistoryFilterDTO,
+) -> Result<Vec<StockHistoryPointDTO>, BackendError> {
+    let product_id = *stock_history_filter_dto.get_product_id();
+    let bucket = stock_history_filter_dto.get_bucket();
+
+    match tb_product::Entity::find_by_id(product_id)
+        .one(database)
+        .await
+    {
+        Ok(Some(_)) => {}
+        Ok(None) => return Err(BackendError::ResourceNotFoundError),
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    }
+
+    let (from, to) = resolve_period(
+        stock_history_filter_dto.get_from(),
+        stock_history_filter_dto.get_to(),
+    )?;
+
+    let buckets = list_buckets(bucket, from, to)?;
+
+    let opening_balance =
+        service_report::find_balance_before(database, product_id, start_of_day(from)).await?;
+
+    let stmt = Statement::from_sql_and_values(
+        DbBackend::MySql,
+        format!(
+            r#"
+            SELECT
+                {} AS bucket,
+                CAST(
+                    SUM(CASE WHEN tb_report.change_type THEN tb_report.quantity ELSE 0 END)
+                    AS UNSIGNED
+                ) AS entries,
+                CAST(
+                    SUM(CASE WHEN tb_report.change_type THEN 0 ELSE tb_report.quantity END)
+                    AS UNSIGNED
+                ) AS exits
+            FROM tb_report
+            WHERE tb_report.product_id = ?
+                AND tb_report.date >= ?
+                AND tb_report.date < ?
+            GROUP BY bucket
+        "#,
+            bucket_expression(bucket)
+        ),
+        [
+            product_id.into(),
+            start_of_day(from).into(),
+            start_of_day(to + Duration::days(1)).into(),
+        ],
+    );
+
+    let movements = match BucketMovementDTO::find_by_statement(stmt)
+        .all(database)
+        .await
+    {
+        Ok(movements) => movements,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let movements: HashMap<&String, &BucketMovementDTO> = movements
+        .iter()
+        .map(|movement| (movement.get_bucket(), movement))
+        .collect();
+
+    let mut quantity = opening_balance;
+
+    Ok(buckets
+        .into_iter()
+        .map(|bucket| {
+            let (entries, exits) = match movements.get(&bucket) {
+                Some(movement) => (*movement.get_entries(), *movement.get_exits()),
+                None => (0, 0),
+            };
+
+            quantity += entries as i64 - exits as i64;
+
+            StockHistoryPointDTO::new(bucket, entries, exits, quantity)
+        })
+        .collect())
+}
+
+pub async fn get_consumption(
+    database: &DatabaseConnection,
+    consumption_filter_dto: ConsumptionFilterDTO,
+) -> Result<Vec<ConsumptionDTO>, BackendError> {
+    let (from, to) = resolve_period(
+        consumption_filter_dto.get_from(),
+        consumption_filter_dto.get_to(),
+    )?;
+
+    let mut conditions = vec![
+        CONSUMPTION_CONDITION,
+        "tb_report.date >= ?",
+        "tb_report.date < ?",
+    ];
+    let mut values: Vec<Value> = vec![
+        start_of_day(from).into(),
+        start_of_day(to + Duration::days(1)).into(),
+    ];
+
+    if let Some(product_id) = consumption_filter_dto.get_product_id() {
+        conditions.push("tb_report.product_id = ?");
+        values.push((*product_id).into());
+    }
+
+    if let Some(category_id) = consumption_filter_dto.get_category_id() {
+        conditions.push("tb_product.category_id = ?");
+        values.push((*category_id).into());
+    }
+
+    let (id, name) = match consumption_filter_dto.get_group_by() {
+        AnalyticsGroup::Product => ("tb_product.id", "tb_product.name"),
+        AnalyticsGroup::Category => ("tb_category.id", "tb_category.name"),
+    };
+
+    let stmt = Statement::from_sql_and_values(
+        DbBackend::MySql,
+        format!(
+            r#"
+            SELECT
+                {} AS bucket,
+                {id} AS id,
+                {name} AS name,
+                CAST(SUM(tb_report.quantity) AS UNSIGNED) AS quantity
+            FROM tb_report
+            JOIN tb_product
+                ON tb_product.id = tb_report.product_id
+            JOIN tb_category
+                ON tb_category.id = tb_product.category_id
+            WHERE {}
+            GROUP BY bucket, {id}, {name}
+            ORDER BY bucket, quantity DESC
+        "#,
+            bucket_expression(consumption_filter_dto.get_bucket()),
+            conditions.join(" AND ")
+        ),
+        values,
+    );
+
+    let result = ConsumptionDTO::find_by_statement(stmt).all(database).await;
+
+    match result {
+        Ok(consumption) => Ok(consumption),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn get_ranking(
+    database: &DatabaseConnection,
+    ranking_filter_dto: RankingFilterDTO,
+) -> Result<RankingDTO, BackendError> {
+    let (from, to) = resolve_period(ranking_filter_dto.get_from(), ranking_filter_dto.get_to())?;
+
+    let limit = match ranking_filter_dto.get_limit() {
+        Some(limit) if (1..=MAX_RANKING_LIMIT).contains(limit) => *limit,
+        Some(_) => return Err(BackendError::InvalidValuesError),
+        None => DEFAULT_RANKING_LIMIT,
+    };
+
+    let start = start_of_day(from);
+    let end = start_of_day(to + Duration::days(1));
+
+    let stmt = Statement::from_sql_and_values(
+        DbBackend::MySql,
+        format!(
+            r#"
+            SELECT
+                tb_product.id AS product_id,
+                tb_product.name AS product,
+                tb_category.name AS category,
+                CAST(SUM(tb_report.quantity) AS UNSIGNED) AS quantity
+            FROM tb_report
+            JOIN tb_product
+                ON tb_product.id = tb_report.product_id
+            JOIN tb_category
+                ON tb_category.id = tb_product.category_id
+            WHERE {CONSUMPTION_CONDITION}
+                AND tb_report.date >= ?
+                AND tb_report.date < ?
+            GROUP BY tb_product.id, tb_product.name, tb_category.name
+            ORDER BY quantity DESC
+            LIMIT ?
+        "#
+        ),
+        [start.into(), end.into(), limit.into()],
+    );
+
+    let top_movers = match MoverDTO::find_by_statement(stmt).all(database).await {
+        Ok(top_movers) => top_movers,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let stmt = Statement::from_sql_and_values(
+        DbBackend::MySql,
+        format!(
+            r#"
+            SELECT
+                tb_product.id AS product_id,
+                tb_product.name AS product,
+                tb_category.name AS category,
+                tb_product.quantity,
+                CAST(last_exits.last_exit AS CHAR) AS last_exit
+            FROM tb_product
+            JOIN tb_category
+                ON tb_category.id = tb_product.category_id
+            LEFT JOIN (
+                SELECT
+                    tb_report.product_id,
+                    MAX(tb_report.date) AS last_exit
+                FROM tb_report
+                WHERE {CONSUMPTION_CONDITION}
+                GROUP BY tb_report.product_id
+            ) AS last_exits
+                ON last_exits.product_id = tb_product.id
+            WHERE tb_product.deleted_at IS NULL
+                AND tb_product.quantity > 0
+                AND NOT EXISTS (
+                    SELECT 1
+                    FROM tb_report
+                    WHERE tb_report.product_id = tb_product.id
+                        AND {CONSUMPTION_CONDITION}
+                        AND tb_report.date >= ?
+                        AND tb_report.date < ?
+                )
+            ORDER BY last_exits.last_exit IS NOT NULL, last_exits.last_exit, tb_product.quantity DESC
+            LIMIT ?
+        "#
+        ),
+        [start.into(), end.into(), limit.into()],
+    );
+
+    let dead_stock = match DeadStockDTO::find_by_statement(stmt).all(database).await {
+        Ok(dead_stock) => dead_stock,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    Ok(RankingDTO::new(top_movers, dead_stock))
+}
+
+pub async fn get_forecast(
+    database: &DatabaseConnection,
+    forecast_filter_dto: ForecastFilterDTO,
+) -> Result<Vec<ForecastDTO>, BackendError> {
+    let method = forecast_filter_dto
+        .get_method()
+        .unwrap_or_else(get_forecast_method);
+    let window_days = forecast_filter_dto
+        .get_window_days()
+        .unwrap_or_else(get_forecast_window_days);
+    let lead_time_days = forecast_filter_dto
+        .get_lead_time_days()
+        .unwrap_or_else(get_forecast_lead_time_days);
+
+    let mut values: Vec<Value> = Vec::new();
+
+    let filter = match forecast_filter_dto.get_category_id() {
+        Some(category_id) => {
+            values.push((*category_id).into());
+            "AND tb_product.category_id = ?"
+        }
+        None => "",
+    };
+
+    let stmt = Statement::from_sql_and_values(
+        DbBackend::MySql,
+        format!(
+            r#"
+            SELECT
+                tb_product.id AS product_id,
+                tb_product.name AS product,
+                tb_category.name AS category,
+                {AVAILABLE_QUANTITY}
+            FROM tb_product
+            JOIN tb_category
+                ON tb_category.id = tb_product.category_id
+            {RESERVED_QUANTITY_JOIN}
+            WHERE tb_product.deleted_at IS NULL
+                {filter}
+        "#
+        ),
+        values,
+    );
+
+    let products = match ForecastProductDTO::find_by_statement(stmt)
+        .all(database)
+        .await
+    {
+        Ok(products) => products,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let demands = find_daily_demands(database, method, window_days, None).await?;
+
+    let mut forecasts: Vec<ForecastDTO> = products
+        .into_iter()
+        .map(|product| {
+            let daily_demand = demands
+                .get(product.get_product_id())
+                .copied()
+                .unwrap_or(0.0);
+            let cover = estimate_cover(*product.get_available(), daily_demand);
+            let at_risk = cover
+                .get_days_of_cover()
+                .is_some_and(|days| days <= lead_time_days as f64);
+
+            ForecastDTO::new(product, cover, at_risk)
+        })
+        .filter(|forecast| !forecast_filter_dto.get_at_risk_only() || *forecast.get_at_risk())
+        .collect();
+
+    forecasts.sort_by(|first, second| {
+        let first = first
+            .get_cover()
+            .get_days_of_cover()
+            .unwrap_or(f64::INFINITY);
+        let second = second
+            .get_cover()
+            .get_days_of_cover()
+            .unwrap_or(f64::INFINITY);
+
+        first.total_cmp(&second)
+    });
+
+    Ok(forecasts)
+}
+
+pub async fn find_daily_demands<C: ConnectionTrait>(
+    connection: &C,
+    method: ForecastMethod,
+    window_days: u32,
+    product_id: Option<u64>,
+) -> Result<HashMap<u64, f64>, BackendError> {
+    if !(1..=MAX_FORECAST_WINDOW_DAYS).contains(&window_days) {
+        return Err(BackendError::InvalidValuesError);
+    }
+
+    // Only complete days are considered, so today's partial exits do not skew the estimate
+    let to = Utc::now().date_naive();
+    let from = to - Duration::days(window_days as i64);
+
+    let mut values: Vec<Value> = vec![start_of_day(from).into(), start_of_day(to).into()];
+
+    let filter = match product_id {
+        Some(product_id) => {
+            values.push(product_id.into());
+            "AND tb_report.product_id = ?"
+        }
+        None => "",
+    };
+
+    let stmt = Statement::from_sql_and_values(
+        DbBackend::MySql,
+        format!(
+            r#"
+            SELECT
+                tb_report.product_id,
+                CAST(DATE(tb_report.date) AS CHAR) AS day,
+                CAST(SUM(tb_report.quantity) AS UNSIGNED) AS quantity
+            FROM tb_report
+            WHERE {CONSUMPTION_CONDITION}
+                AND tb_report.date >= ?
+                AND tb_report.date < ?
+                {filter}
+            GROUP BY tb_report.product_id, day
+        "#
+        ),
+        values,
+    );
+
+    let consumption = match DailyConsumptionDTO::find_by_statement(stmt)
+        .all(connection)
+        .await
+    {
+        Ok(consumption) => consumption,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let mut series: HashMap<u64, HashMap<&String, u64>> = HashMap::new();
+
+    for daily in &consumption {
+        series
+            .entry(*daily.get_product_id())
+            .or_default()
+            .insert(daily.get_day(), *daily.get_quantity());
+    }
+
+    let days: Vec<String> = (0..window_days as i64)
+        .map(|offset| {
+            (from + Duration::days(offset))
+                .format("%Y-%m-%d")
+                .to_string()
+        })
+        .collect();
+
+    Ok(series
+        .into_iter()
+        .map(|(product_id, quantities)| {
+            let mut daily_quantities = days
+                .iter()
+                .map(|day| quantities.get(day).copied().unwrap_or(0) as f64);
+
+            let demand = match method {
+                ForecastMethod::MovingAverage => daily_quantities.sum::<f64>() / window_days as f64,
+                ForecastMethod::ExponentialSmoothing => {
+                    let alpha = get_forecast_smoothing_alpha();
+                    let first = daily_quantities.next().unwrap_or(0.0);
+
+                    daily_quantities.fold(first, |level, quantity| {
+                        alpha * quantity + (1.0 - alpha) * level
+                    })
+                }
+            };
+
+            (product_id, demand)
+        })
+        .collect())
+}
+
+pub async fn find_default_daily_demands<C: ConnectionTrait>(
+    connection: &C,
+    product_id: Option<u64>,
+) -> Result<HashMap<u64, f64>, BackendError> {
+    find_daily_demands(
+        connection,
+        get_forecast_method(),
+        get_forecast_window_days(),
+        product_id,
+    )
+    .await
+}
+
+pub fn estimate_cover(available: u64, daily_demand: f64) -> CoverDTO {
+    if daily_demand <= 0.0 {
+        return CoverDTO::new(0.0, None, None);
+    }
+
+    let days_of_cover = available as f64 / daily_demand;
+
+    let stockout_date = Utc::now()
+        .date_naive()
+        .checked_add_days(Days::new(days_of_cover.floor() as u64))
+        .map(|date| date.format("%Y-%m-%d").to_string());
+
+    CoverDTO::new(
+        round(daily_demand, 100.0),
+        Some(round(days_of_cover, 10.0)),
+        stockout_date,
+    )
+}
+
+pub async fn classify_products(
+    database: &DatabaseConnection,
+    window_days: Option<u32>,
+) -> Result<AbcClassificationDTO, BackendError> {
+    let window_days = window_days.unwrap_or_else(get_abc_window_days);
+
+    if !(1..=MAX_ABC_WINDOW_DAYS).contains(&window_days) {
+        return Err(BackendError::InvalidValuesError);
+    }
+
+    let (class_a_percent, class_b_percent) = get_abc_class_percents();
+
+    let to = Utc::now().date_naive();
+    let from = to - Duration::days(window_days as i64);
+
+    let stmt = Statement::from_sql_and_values(
+        DbBackend::MySql,
+        format!(
+            r#"
+            SELECT
+                tb_product.id AS product_id,
+                CAST(COALESCE(SUM(consumption.quantity), 0) AS UNSIGNED) AS volume
+            FROM tb_product
+            LEFT JOIN (
+                SELECT
+                    tb_report.product_id,
+                    tb_report.quantity
+                FROM tb_report
+                WHERE {CONSUMPTION_CONDITION}
+                    AND tb_report.date >= ?
+                    AND tb_report.date < ?
+            ) AS consumption
+                ON consumption.product_id = tb_product.id
+            WHERE tb_product.deleted_at IS NULL
+            GROUP BY tb_product.id
+            ORDER BY volume DESC, tb_product.id
+        "#
+        ),
+        [start_of_day(from).into(), start_of_day(to).into()],
+    );
+
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let volumes = match ProductVolumeDTO::find_by_statement(stmt)
+        .all(&transaction)
+        .await
+    {
+        Ok(volumes) => volumes,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let total_volume: u64 = volumes.iter().map(|volume| *volume.get_volume()).sum();
+
+    let mut classes: HashMap<&'static str, Vec<u64>> = HashMap::new();
+    let mut accumulated: u128 = 0;
+
+    // A product belongs to the class in which its cumulative share of the volume starts
+    for volume in &volumes {
+        let share_before = accumulated * 100;
+
+        let class = match *volume.get_volume() {
+            0 => AbcClass::C,
+            _ if share_before < class_a_percent as u128 * total_volume as u128 => AbcClass::A,
+            _ if share_before < class_b_percent as u128 * total_volume as u128 => AbcClass::B,
+            _ => AbcClass::C,
+        };
+
+        accumulated += *volume.get_volume() as u128;
+
+        classes
+            .entry(class.as_str())
+            .or_default()
+            .push(*volume.get_product_id());
+    }
+
+    for (class, ids) in &classes {
+        for chunk in ids.chunks(ABC_UPDATE_CHUNK) {
+            let result = tb_product::Entity::update_many()
+                .col_expr(tb_product::Column::AbcClass, Expr::value(*class))
+                .filter(tb_product::Column::Id.is_in(chunk.to_vec()))
+                .exec(&transaction)
+                .await;
+
+            if let Err(db_err) = result {
+                return Err(BackendError::DatabaseError(db_err));
+            }
+        }
+    }
+
+    let count = |class: AbcClass| classes.get(class.as_str()).map_or(0, Vec::len);
+
+    let classification = AbcClassificationDTO::new(
+        window_days,
+        total_volume,
+        count(AbcClass::A),
+        count(AbcClass::B),
+        count(AbcClass::C),
+    );
+
+    match transaction.commit().await {
+        Ok(_) => Ok(classification),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+fn bucket_expression(bucket: AnalyticsBucket) -> &'static str {
+    match bucket {
+        AnalyticsBucket::Day => "CAST(DATE(tb_report.date) AS CHAR)",
+        AnalyticsBucket::Week => {
+            "CAST(DATE_SUB(DATE(tb_report.date), INTERVAL WEEKDAY(tb_report.date) DAY) AS CHAR)"
+        }
+        AnalyticsBucket::Month => "DATE_FORMAT(tb_report.date, '%Y-%m-01')",
+    }
+}
+
+fn list_buckets(
+    bucket: AnalyticsBucket,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<String>, BackendError> {
+    let mut current = match bucket {
+        AnalyticsBucket::Day => from,
+        AnalyticsBucket::Week => {
+            from - Duration::days(from.weekday().num_days_from_monday() as i64)
+        }
+        AnalyticsBucket::Month => from - Duration::days(from.day0() as i64),
+    };
+
+    let mut buckets = Vec::new();
+
+    while current <= to {
+        if buckets.len() == MAX_BUCKETS {
+            return Err(BackendError::InvalidValuesError);
+        }
+
+        buckets.push(current.format("%Y-%m-%d").to_string());
+
+        current = match bucket {
+            AnalyticsBucket::Day => current + Duration::days(1),
+            AnalyticsBucket::Week => current + Duration::days(7),
+            AnalyticsBucket::Month => match current.checked_add_months(Months::new(1)) {
+                Some(next) => next,
+                None => return Err(BackendError::InvalidValuesError),
+            },
+        };
+    }
+
+    Ok(buckets)
+}
+
+fn resolve_period(
+    from: &Option<String>,
+    to: &Option<String>,
+) -> Result<(NaiveDate, NaiveDate), BackendError> {
+    let to = match to {
+        Some(to) => parse_date(to)?,
+        None => Utc::now().date_naive(),
+    };
+
+    let from = match from {
+        Some(from) => parse_date(from)?,
+        None => to - Duration::days(DEFAULT_PERIOD_DAYS - 1),
+    };
+
+    match from <= to {
+        true => Ok((from, to)),
+        false => Err(BackendError::InvalidValuesError),
+    }
+}
+
+fn parse_date(date: &str) -> Result<NaiveDate, BackendError> {
+    match NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d") {
+        Ok(date) => Ok(date),
+        Err(_) => Err(BackendError::InvalidValuesError),
+    }
+}
+
+fn start_of_day(date: NaiveDate) -> NaiveDateTime {
+    date.and_time(chrono::NaiveTime::MIN)
+}
+
+fn round(value: f64, precision: f64) -> f64 {
+    (value * precision).round() / precision
+}
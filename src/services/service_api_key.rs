@@ -0,0 +1,290 @@
+use chrono::Utc;
+use sea_orm::{
+    ActiveValue, ColumnTrait, DatabaseConnection, DbBackend, EntityTrait, FromQueryResult,
+    QueryFilter, Statement, TransactionTrait,
+};
+
+use serde_json::json;
+
+use crate::{
+    configs::config_api_key::{generate_api_key, hash_api_key},
+    entities::{
+        dtos::{
+            api_key_dtos::{
+                ApiKeyCreateDTO, ApiKeyCreatedDTO, ApiKeyPermissionQueryDTO, ApiKeyViewDTO,
+            },
+            generic_dtos::ActorDTO,
+        },
+        enums::audit_enums::{AuditAction, AuditEntity},
+        tb_api_key::{self, ActiveModel, Model},
+        tb_api_key_permission, tb_user,
+    },
+    errors::BackendError,
+    guards::guard_user::AuthenticationGuard,
+    services::{service_audit, service_role, service_user},
+};
+
+pub async fn get_all_api_keys(
+    database: &DatabaseConnection,
+) -> Result<Vec<ApiKeyViewDTO>, BackendError> {
+    let stmt = Statement::from_string(
+        DbBackend::MySql,
+        r#"
+            SELECT
+                tb_api_key.id,
+                tb_api_key.name,
+                tb_api_key.prefix,
+                tb_user.username AS created_by,
+                CAST(tb_api_key.created_at AS CHAR) AS created_at,
+                CAST(tb_api_key.last_used_at AS CHAR) AS last_used_at,
+                CAST(tb_api_key.revoked_at AS CHAR) AS revoked_at,
+                tb_permission.name AS permission
+            FROM tb_api_key
+            LEFT JOIN tb_user
+                ON tb_user.id = tb_api_key.created_by
+            LEFT JOIN tb_api_key_permission
+                ON tb_api_key_permission.api_key_id = tb_api_key.id
+            LEFT JOIN tb_permission
+                ON tb_permission.id = tb_api_key_permission.permission_id
+            ORDER BY tb_api_key.id, tb_permission.name
+        "#,
+    );
+
+    let result = ApiKeyPermissionQueryDTO::find_by_statement(stmt)
+        .all(database)
+        .await;
+
+    match result {
+        Ok(rows) => Ok(ApiKeyViewDTO::from_rows(rows)),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn create_api_key(
+    database: &DatabaseConnection,
+    api_key_create_dto: ApiKeyCreateDTO,
+    authentication: AuthenticationGuard,
+) -> Result<ApiKeyCreatedDTO, BackendError> {
+    if api_key_create_dto.get_name().trim().is_empty() {
+        return Err(BackendError::InvalidValuesError);
+    }
+
+    let logged_user = match service_user::find_logged_user(database, &authentication).await {
+        Ok(model) => model,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let logged_permissions =
+        match service_role::find_permissions_by_user_id(database, logged_user.id).await {
+            Ok(permissions) => permissions,
+            Err(backend_error) => return Err(backend_error),
+        };
+
+    if api_key_create_dto
+        .get_permissions()
+        .iter()
+        .any(|permission| !logged_permissions.contains(permission))
+    {
+        return Err(BackendError::PermissionDeniedError);
+    }
+
+    let permission_ids =
+        match service_role::find_permission_ids(database, api_key_create_dto.get_permissions())
+            .await
+        {
+            Ok(permission_ids) => permission_ids,
+            Err(backend_error) => return Err(backend_error),
+        };
+
+    let key = generate_api_key();
+
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let api_key = ActiveModel {
+        name: ActiveValue::Set(api_key_create_dto.get_name().clone()),
+        prefix: ActiveValue::Set(key[..12].to_string()),
+        key_hash: ActiveValue::Set(hash_api_key(&key)),
+        created_by: ActiveValue::Set(Some(logged_user.id)),
+        created_at: ActiveValue::Set(Utc::now().naive_utc()),
+        ..Default::default()
+    };
+
+    let result = tb_api_key::Entity::insert(api_key)
+        .exec_with_returning(&transaction)
+        .await;
+
+    let api_key = match result {
+        Ok(model) => model,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let api_key_id = api_key.id;
+
+    if !permission_ids.is_empty() {
+        let api_key_permissions =
+            permission_ids
+                .into_iter()
+                .map(|permission_id| tb_api_key_permission::ActiveModel {
+                    api_key_id: ActiveValue::Set(api_key_id),
+                    permission_id: ActiveValue::Set(permission_id),
+                });
+
+        let result = tb_api_key_permission::Entity::insert_many(api_key_permissions)
+            .exec_without_returning(&transaction)
+            .await;
+
+        if let Err(db_err) = result {
+            return Err(BackendError::DatabaseError(db_err));
+        }
+    }
+
+    service_audit::register_audit(
+        &transaction,
+        &ActorDTO::new(Some(logged_user.id), None),
+        AuditEntity::ApiKey,
+        api_key_id,
+        AuditAction::Create,
+        None,
+        Some(json!({
+            "api_key": api_key,
+            "permissions": api_key_create_dto.get_permissions(),
+        })),
+    )
+    .await?;
+
+    match transaction.commit().await {
+        Ok(_) => Ok(ApiKeyCreatedDTO::new(api_key_id, key)),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn revoke_api_key(
+    database: &DatabaseConnection,
+    id: u64,
+    authentication: AuthenticationGuard,
+) -> Result<(), BackendError> {
+    let actor = match service_user::find_actor(database, &authentication).await {
+        Ok(actor) => actor,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let api_key = match tb_api_key::Entity::find_by_id(id).one(database).await {
+        Ok(Some(model)) => model,
+        Ok(None) => return Err(BackendError::ResourceNotFoundError),
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    if api_key.revoked_at.is_some() {
+        return Ok(());
+    }
+
+    let revoked_api_key = ActiveModel {
+        id: ActiveValue::Set(id),
+        revoked_at: ActiveValue::Set(Some(Utc::now().naive_utc())),
+        ..Default::default()
+    };
+
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let result = tb_api_key::Entity::update(revoked_api_key)
+        .exec(&transaction)
+        .await;
+
+    let revoked_api_key = match result {
+        Ok(model) => model,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    service_audit::register_audit(
+        &transaction,
+        &actor,
+        AuditEntity::ApiKey,
+        id,
+        AuditAction::Revoke,
+        service_audit::snapshot(&api_key),
+        service_audit::snapshot(&revoked_api_key),
+    )
+    .await?;
+
+    match transaction.commit().await {
+        Ok(_) => Ok(()),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn authenticate(
+    database: &DatabaseConnection,
+    api_key: &str,
+) -> Result<Model, BackendError> {
+    let result = tb_api_key::Entity::find()
+        .filter(tb_api_key::Column::KeyHash.eq(hash_api_key(api_key)))
+        .filter(tb_api_key::Column::RevokedAt.is_null())
+        .one(database)
+        .await;
+
+    let api_key = match result {
+        Ok(Some(model)) => model,
+        Ok(None) => return Err(BackendError::InvalidCredentialsError),
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    verify_creator(database, &api_key).await?;
+
+    let used_api_key = ActiveModel {
+        id: ActiveValue::Set(api_key.id),
+        last_used_at: ActiveValue::Set(Some(Utc::now().naive_utc())),
+        ..Default::default()
+    };
+
+    match tb_api_key::Entity::update(used_api_key)
+        .exec(database)
+        .await
+    {
+        Ok(model) => Ok(model),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+// Re-validates a key that was authenticated earlier, for connections that outlive a request
+pub async fn find_active_by_id(
+    database: &DatabaseConnection,
+    id: u64,
+) -> Result<Model, BackendError> {
+    let result = tb_api_key::Entity::find_by_id(id)
+        .filter(tb_api_key::Column::RevokedAt.is_null())
+        .one(database)
+        .await;
+
+    let api_key = match result {
+        Ok(Some(model)) => model,
+        Ok(None) => return Err(BackendError::InvalidCredentialsError),
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    verify_creator(database, &api_key).await?;
+
+    Ok(api_key)
+}
+
+// A key stops working with its creator, whose id is cleared when the user is deleted
+async fn verify_creator(
+    database: &DatabaseConnection,
+    api_key: &Model,
+) -> Result<(), BackendError> {
+    let creator = match api_key.created_by {
+        Some(created_by) => tb_user::Entity::find_by_id(created_by).one(database).await,
+        None => return Err(BackendError::InvalidCredentialsError),
+    };
+
+    match creator {
+        Ok(Some(user)) if user.active => Ok(()),
+        Ok(_) => Err(BackendError::InvalidCredentialsError),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
@@ -0,0 +1,136 @@
+use chrono::{Duration, NaiveDate, Utc};
+use sea_orm::{
+    ActiveValue, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait, FromQueryResult,
+    JsonValue, Statement, Value,
+};
+use serde::Serialize;
+
+use crate::{
+    entities::{
+        dtos::{
+            audit_dtos::{AuditFilterDTO, AuditViewDTO},
+            generic_dtos::ActorDTO,
+        },
+        enums::audit_enums::{AuditAction, AuditEntity},
+        tb_audit::{self, ActiveModel},
+    },
+    errors::BackendError,
+};
+
+pub async fn get_all_audits(
+    database: &DatabaseConnection,
+    audit_filter_dto: AuditFilterDTO,
+) -> Result<Vec<AuditViewDTO>, BackendError> {
+    let mut conditions: Vec<&str> = Vec::new();
+    let mut values: Vec<Value> = Vec::new();
+
+    if let Some(entity) = audit_filter_dto.get_entity() {
+        conditions.push("tb_audit.entity = ?");
+        values.push(entity.clone().into());
+    }
+
+    if let Some(user_id) = audit_filter_dto.get_user_id() {
+        conditions.push("tb_audit.user_id = ?");
+        values.push((*user_id).into());
+    }
+
+    if let Some(api_key_id) = audit_filter_dto.get_api_key_id() {
+        conditions.push("tb_audit.api_key_id = ?");
+        values.push((*api_key_id).into());
+    }
+
+    if let Some(start_date) = audit_filter_dto.get_start_date() {
+        let start_date = match parse_date(start_date) {
+            Some(date) => date,
+            None => return Err(BackendError::InvalidValuesError),
+        };
+
+        conditions.push("tb_audit.created_at >= ?");
+        values.push(start_date.and_hms_opt(0, 0, 0).unwrap().into());
+    }
+
+    if let Some(end_date) = audit_filter_dto.get_end_date() {
+        let end_date = match parse_date(end_date) {
+            Some(date) => date + Duration::days(1),
+            None => return Err(BackendError::InvalidValuesError),
+        };
+
+        conditions.push("tb_audit.created_at < ?");
+        values.push(end_date.and_hms_opt(0, 0, 0).unwrap().into());
+    }
+
+    let where_clause = match conditions.is_empty() {
+        true => String::new(),
+        false => format!("WHERE {}", conditions.join(" AND ")),
+    };
+
+    let stmt = Statement::from_sql_and_values(
+        DbBackend::MySql,
+        format!(
+            "
+            SELECT
+                tb_audit.id,
+                tb_user.username AS user,
+                tb_api_key.name AS api_key,
+                tb_audit.entity,
+                tb_audit.entity_id,
+                tb_audit.action,
+                tb_audit.before_data,
+                tb_audit.after_data,
+                CAST(tb_audit.created_at AS CHAR) AS created_at
+            FROM tb_audit
+            LEFT JOIN tb_user
+                ON tb_user.id = tb_audit.user_id
+            LEFT JOIN tb_api_key
+                ON tb_api_key.id = tb_audit.api_key_id
+            {where_clause}
+            ORDER BY tb_audit.id DESC
+        "
+        ),
+        values,
+    );
+
+    let result = AuditViewDTO::find_by_statement(stmt).all(database).await;
+
+    match result {
+        Ok(audits) => Ok(audits),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn register_audit<C: ConnectionTrait>(
+    database: &C,
+    actor: &ActorDTO,
+    entity: AuditEntity,
+    entity_id: u64,
+    action: AuditAction,
+    before_data: Option<JsonValue>,
+    after_data: Option<JsonValue>,
+) -> Result<(), BackendError> {
+    let audit = ActiveModel {
+        user_id: ActiveValue::Set(*actor.get_user_id()),
+        api_key_id: ActiveValue::Set(*actor.get_api_key_id()),
+        entity: ActiveValue::Set(entity.as_str().to_string()),
+        entity_id: ActiveValue::Set(entity_id),
+        action: ActiveValue::Set(action.as_str().to_string()),
+        before_data: ActiveValue::Set(before_data),
+        after_data: ActiveValue::Set(after_data),
+        created_at: ActiveValue::Set(Utc::now().naive_utc()),
+        ..Default::default()
+    };
+
+    let result = tb_audit::Entity::insert(audit).exec(database).await;
+
+    match result {
+        Ok(_) => Ok(()),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub fn snapshot<T: Serialize>(value: &T) -> Option<JsonValue> {
+    serde_json::to_value(value).ok()
+}
+
+fn parse_date(date: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").ok()
+}
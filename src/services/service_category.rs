@@ -1,52 +1,99 @@
+use chrono::{NaiveDateTime, Utc};
 use sea_orm::{
-    ActiveValue, ColumnTrait, DatabaseConnection, DbBackend, DbErr, EntityTrait, FromQueryResult,
-    QueryFilter, Statement,
+    ActiveModelBehavior, ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, DbBackend,
+    DbErr, EntityTrait, FromQueryResult, PaginatorTrait, QueryFilter, Statement, TransactionTrait,
+    sea_query::{Expr, Query},
 };
 
 use crate::{
     entities::{
         dtos::{
-            category_dtos::{CategoryCreateDTO, CategoryDTO, CategoryViewDTO},
-            generic_dtos::ExistsDTO,
+            category_dtos::{CategoryCreateDTO, CategoryDTO, CategoryPatchDTO, CategoryViewDTO},
+            generic_dtos::{ActorDTO, ExistsDTO, SortDTO},
+        },
+        enums::{
+            audit_enums::{AuditAction, AuditEntity},
+            export_enums::{ExportFormat, ExportLanguage},
         },
         tb_category::{self, ActiveModel, Model},
+        tb_product, tb_stocktake,
     },
     errors::BackendError,
+    guards::guard_user::AuthenticationGuard,
+    services::{
+        service_audit,
+        service_export::{self, ExportFile},
+        service_user,
+    },
 };
 
 pub async fn get_all_categories(
     database: &DatabaseConnection,
+    include_deleted: bool,
+    sort_dto: &SortDTO,
 ) -> Result<Vec<CategoryDTO>, BackendError> {
-    let result = tb_category::Entity::find().all(database).await;
+    let filter = match include_deleted {
+        true => String::new(),
+        false => "WHERE tb_category.deleted_at IS NULL".to_string(),
+    };
+
+    let result = CategoryDTO::find_by_statement(categories_statement(&filter, sort_dto))
+        .all(database)
+        .await;
 
     match result {
-        Ok(categories) => Ok(categories
-            .into_iter()
-            .map(|category| CategoryDTO::new(category.id, category.name))
-            .collect()),
+        Ok(categories) => Ok(categories),
         Err(db_err) => Err(BackendError::DatabaseError(db_err)),
     }
 }
 
-pub async fn get_all_categories_admin(
+pub async fn get_category_by_id(
     database: &DatabaseConnection,
-) -> Result<Vec<CategoryViewDTO>, BackendError> {
-    let stmt = Statement::from_string(
-        DbBackend::MySql,
-        r#"
-            SELECT
-                id,
-                name,
-                CAST(
-                    (
-                        SELECT COUNT(*) FROM tb_product WHERE tb_product.category_id = tb_category.id 
-                    ) AS UNSIGNED
-                ) AS quantity
-            FROM tb_category;
-        "#,
+    id: u64,
+) -> Result<CategoryDTO, BackendError> {
+    let filter = format!(
+        "
+            WHERE tb_category.id = (\"{id}\")
+                AND tb_category.deleted_at IS NULL
+        "
     );
 
-    let result = CategoryViewDTO::find_by_statement(stmt).all(database).await;
+    let result = CategoryDTO::find_by_statement(categories_statement(&filter, &SortDTO::default()))
+        .one(database)
+        .await;
+
+    match result {
+        Ok(Some(category)) => Ok(category),
+        Ok(None) => Err(BackendError::ResourceNotFoundError),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn find_all(
+    database: &DatabaseConnection,
+    include_deleted: bool,
+) -> Result<Vec<Model>, BackendError> {
+    let mut query = tb_category::Entity::find();
+
+    if !include_deleted {
+        query = query.filter(tb_category::Column::DeletedAt.is_null());
+    }
+
+    let result = query.all(database).await;
+
+    match result {
+        Ok(categories) => Ok(categories),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn get_all_categories_admin(
+    database: &DatabaseConnection,
+    sort_dto: &SortDTO,
+) -> Result<Vec<CategoryViewDTO>, BackendError> {
+    let result = CategoryViewDTO::find_by_statement(all_categories_admin_statement(sort_dto))
+        .all(database)
+        .await;
 
     match result {
         Ok(categories) => Ok(categories),
@@ -54,10 +101,34 @@ pub async fn get_all_categories_admin(
     }
 }
 
+pub async fn export_categories_admin(
+    database: &DatabaseConnection,
+    format: ExportFormat,
+    language: ExportLanguage,
+) -> Result<ExportFile<'_>, BackendError> {
+    let result =
+        CategoryViewDTO::find_by_statement(all_categories_admin_statement(&SortDTO::default()))
+            .stream(database)
+            .await;
+
+    match result {
+        Ok(categories) => {
+            service_export::export_rows(categories, "categories", format, language).await
+        }
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
 pub async fn create_category(
     database: &DatabaseConnection,
     category_create_dto: CategoryCreateDTO,
+    authentication: AuthenticationGuard,
 ) -> Result<(), BackendError> {
+    let actor = match service_user::find_actor(database, &authentication).await {
+        Ok(actor) => actor,
+        Err(backend_error) => return Err(backend_error),
+    };
+
     match exists_by_name(database, category_create_dto.get_name()).await {
         Ok(boolean) => {
             if boolean {
@@ -68,13 +139,35 @@ pub async fn create_category(
     }
 
     let category = ActiveModel {
-        id: ActiveValue::NotSet,
         name: ActiveValue::Set(category_create_dto.get_name().clone()),
+        updated_by: ActiveValue::Set(*actor.get_user_id()),
+        ..Default::default()
     };
 
-    let result = tb_category::Entity::insert(category).exec(database).await;
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
 
-    match result {
+    let result = category.insert(&transaction).await;
+
+    let category = match result {
+        Ok(model) => model,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    service_audit::register_audit(
+        &transaction,
+        &actor,
+        AuditEntity::Category,
+        category.id,
+        AuditAction::Create,
+        None,
+        service_audit::snapshot(&category),
+    )
+    .await?;
+
+    match transaction.commit().await {
         Ok(_) => Ok(()),
         Err(db_err) => Err(BackendError::DatabaseError(db_err)),
     }
@@ -83,66 +176,232 @@ pub async fn create_category(
 pub async fn update_category(
     database: &DatabaseConnection,
     category_update_dto: CategoryDTO,
+    authentication: AuthenticationGuard,
 ) -> Result<(), BackendError> {
-    match exists_by_id(database, category_update_dto.get_id()).await {
-        Ok(boolean) => {
-            if !boolean {
-                return Err(BackendError::ResourceNotFoundError);
-            }
-        }
-        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    let id = *category_update_dto.get_id();
+
+    patch_category(database, id, category_update_dto.into(), authentication).await
+}
+
+pub async fn patch_category(
+    database: &DatabaseConnection,
+    id: u64,
+    category_patch_dto: CategoryPatchDTO,
+    authentication: AuthenticationGuard,
+) -> Result<(), BackendError> {
+    let actor = match service_user::find_actor(database, &authentication).await {
+        Ok(actor) => actor,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let version = match category_patch_dto.get_version() {
+        Some(version) => *version,
+        None => return Err(BackendError::ResourceVersionRequiredError),
+    };
+
+    let old_category = match find_by_id(database, id).await {
+        Ok(model) => model,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    if old_category.deleted_at.is_some() {
+        return Err(BackendError::ResourceNotFoundError);
     }
 
-    if let Ok(old_category) = find_by_name(database, category_update_dto.get_name()).await {
-        if &old_category.id != category_update_dto.get_id() {
+    if old_category.version != version {
+        return Err(BackendError::ResourceStaleVersionError);
+    }
+
+    if let Some(name) = category_patch_dto.get_name() {
+        if name.trim().is_empty() {
+            return Err(BackendError::InvalidValuesError);
+        }
+
+        if let Ok(category) = find_by_name(database, name).await
+            && category.id != id
+        {
             return Err(BackendError::ResourceConflitUpdateError);
         }
     }
 
-    let category = create_update_active_model(category_update_dto);
+    let category = create_update_active_model(id, category_patch_dto, version, &actor);
 
-    let result = tb_category::Entity::update(category).exec(database).await;
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
 
-    match result {
+    // ActiveModel::update takes no filter, so the hook runs here before the versioned update
+    let category = match category.before_save(&transaction, false).await {
+        Ok(category) => category,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    // The version filter rejects writes that raced with another update after the check above
+    let result = tb_category::Entity::update(category)
+        .filter(tb_category::Column::Version.eq(version))
+        .exec(&transaction)
+        .await;
+
+    let category = match result {
+        Ok(model) => model,
+        Err(DbErr::RecordNotUpdated) => return Err(BackendError::ResourceStaleVersionError),
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    service_audit::register_audit(
+        &transaction,
+        &actor,
+        AuditEntity::Category,
+        category.id,
+        AuditAction::Update,
+        service_audit::snapshot(&old_category),
+        service_audit::snapshot(&category),
+    )
+    .await?;
+
+    match transaction.commit().await {
         Ok(_) => Ok(()),
         Err(db_err) => Err(BackendError::DatabaseError(db_err)),
     }
 }
 
-pub async fn delete_by_id(database: &DatabaseConnection, id: u64) -> Result<(), BackendError> {
-    match exists_by_id(database, &id).await {
-        Ok(boolean) => {
-            if !boolean {
-                return Err(BackendError::ResourceNotFoundError);
-            }
-        }
+pub async fn delete_by_id(
+    database: &DatabaseConnection,
+    id: u64,
+    authentication: AuthenticationGuard,
+) -> Result<(), BackendError> {
+    let actor = match service_user::find_actor(database, &authentication).await {
+        Ok(actor) => actor,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let category = match find_by_id(database, id).await {
+        Ok(model) => model,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    if category.deleted_at.is_some() {
+        return Err(BackendError::ResourceNotFoundError);
+    }
+
+    let products = tb_product::Entity::find()
+        .filter(tb_product::Column::CategoryId.eq(id))
+        .filter(tb_product::Column::DeletedAt.is_null())
+        .count(database)
+        .await;
+
+    match products {
+        Ok(0) => {}
+        Ok(_) => return Err(BackendError::ResourceConflitUpdateError),
         Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
     }
 
-    let result = tb_category::Entity::delete_by_id(id).exec(database).await;
+    update_deleted_at(
+        database,
+        category,
+        Some(Utc::now().naive_utc()),
+        actor,
+        AuditAction::Delete,
+    )
+    .await
+}
 
-    match result {
-        Ok(_) => Ok(()),
-        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+pub async fn restore_by_id(
+    database: &DatabaseConnection,
+    id: u64,
+    authentication: AuthenticationGuard,
+) -> Result<(), BackendError> {
+    let actor = match service_user::find_actor(database, &authentication).await {
+        Ok(actor) => actor,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let category = match find_by_id(database, id).await {
+        Ok(model) => model,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    if category.deleted_at.is_none() {
+        return Ok(());
     }
+
+    update_deleted_at(database, category, None, actor, AuditAction::Restore).await
 }
 
-async fn find_by_name(database: &DatabaseConnection, name: &str) -> Result<Model, BackendError> {
+pub async fn purge_deleted(
+    database: &DatabaseConnection,
+    deleted_before: NaiveDateTime,
+) -> Result<(), BackendError> {
     let result = tb_category::Entity::find()
-        .filter(tb_category::Column::Name.eq(name))
-        .one(database)
+        .filter(tb_category::Column::DeletedAt.lt(deleted_before))
+        // Rows still referenced by a RESTRICT foreign key would abort the whole transaction
+        .filter(
+            Expr::exists(
+                Query::select()
+                    .expr(Expr::val(1))
+                    .from(tb_product::Entity)
+                    .and_where(
+                        Expr::col((tb_product::Entity, tb_product::Column::CategoryId))
+                            .equals((tb_category::Entity, tb_category::Column::Id)),
+                    )
+                    .to_owned(),
+            )
+            .not(),
+        )
+        .filter(
+            Expr::exists(
+                Query::select()
+                    .expr(Expr::val(1))
+                    .from(tb_stocktake::Entity)
+                    .and_where(
+                        Expr::col((tb_stocktake::Entity, tb_stocktake::Column::CategoryId))
+                            .equals((tb_category::Entity, tb_category::Column::Id)),
+                    )
+                    .to_owned(),
+            )
+            .not(),
+        )
+        .all(database)
         .await;
 
-    match result {
-        Ok(model_opt) => match model_opt {
-            Some(model) => Ok(model),
-            None => Err(BackendError::ResourceNotFoundError),
-        },
+    let categories = match result {
+        Ok(categories) => categories,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    for category in categories {
+        if let Err(db_err) = tb_category::Entity::delete_by_id(category.id)
+            .exec(&transaction)
+            .await
+        {
+            return Err(BackendError::DatabaseError(db_err));
+        }
+
+        service_audit::register_audit(
+            &transaction,
+            &ActorDTO::new(None, None),
+            AuditEntity::Category,
+            category.id,
+            AuditAction::Purge,
+            service_audit::snapshot(&category),
+            None,
+        )
+        .await?;
+    }
+
+    match transaction.commit().await {
+        Ok(_) => Ok(()),
         Err(db_err) => Err(BackendError::DatabaseError(db_err)),
     }
 }
 
-async fn exists_by_id(database: &DatabaseConnection, id: &u64) -> Result<bool, DbErr> {
+pub async fn exists_by_id(database: &DatabaseConnection, id: &u64) -> Result<bool, DbErr> {
     let stmt = Statement::from_string(
         DbBackend::MySql,
         format!(
@@ -152,6 +411,7 @@ async fn exists_by_id(database: &DatabaseConnection, id: &u64) -> Result<bool, D
                     SELECT 1
                     FROM tb_category
                     WHERE tb_category.id = (\"{id}\")
+                        AND tb_category.deleted_at IS NULL
                 ) AS 'exist'
         "
         ),
@@ -168,6 +428,127 @@ async fn exists_by_id(database: &DatabaseConnection, id: &u64) -> Result<bool, D
     }
 }
 
+fn categories_statement(filter: &str, sort_dto: &SortDTO) -> Statement {
+    Statement::from_string(
+        DbBackend::MySql,
+        format!(
+            r#"
+            SELECT
+                tb_category.id,
+                tb_category.name,
+                tb_category.version,
+                {},
+                CAST(tb_category.deleted_at AS CHAR) AS deleted_at
+            FROM tb_category
+            {}
+            {filter}
+            {}
+        "#,
+            service_user::authorship_columns("tb_category"),
+            service_user::authorship_join("tb_category"),
+            service_user::authorship_order_by("tb_category", sort_dto)
+        ),
+    )
+}
+
+fn all_categories_admin_statement(sort_dto: &SortDTO) -> Statement {
+    Statement::from_string(
+        DbBackend::MySql,
+        format!(
+            r#"
+            SELECT
+                tb_category.id,
+                tb_category.name,
+                tb_category.version,
+                CAST(
+                    (
+                        SELECT COUNT(*)
+                        FROM tb_product
+                        WHERE tb_product.category_id = tb_category.id
+                            AND tb_product.deleted_at IS NULL
+                    ) AS UNSIGNED
+                ) AS quantity,
+                {}
+            FROM tb_category
+            {}
+            WHERE tb_category.deleted_at IS NULL
+            {}
+        "#,
+            service_user::authorship_columns("tb_category"),
+            service_user::authorship_join("tb_category"),
+            service_user::authorship_order_by("tb_category", sort_dto)
+        ),
+    )
+}
+
+async fn update_deleted_at(
+    database: &DatabaseConnection,
+    category: Model,
+    deleted_at: Option<NaiveDateTime>,
+    actor: ActorDTO,
+    action: AuditAction,
+) -> Result<(), BackendError> {
+    let updated_category = ActiveModel {
+        id: ActiveValue::Set(category.id),
+        deleted_at: ActiveValue::Set(deleted_at),
+        updated_by: ActiveValue::Set(*actor.get_user_id()),
+        ..Default::default()
+    };
+
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let updated_category = match updated_category.update(&transaction).await {
+        Ok(model) => model,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    service_audit::register_audit(
+        &transaction,
+        &actor,
+        AuditEntity::Category,
+        category.id,
+        action,
+        service_audit::snapshot(&category),
+        service_audit::snapshot(&updated_category),
+    )
+    .await?;
+
+    match transaction.commit().await {
+        Ok(_) => Ok(()),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+async fn find_by_id(database: &DatabaseConnection, id: u64) -> Result<Model, BackendError> {
+    let result = tb_category::Entity::find_by_id(id).one(database).await;
+
+    match result {
+        Ok(model_opt) => match model_opt {
+            Some(model) => Ok(model),
+            None => Err(BackendError::ResourceNotFoundError),
+        },
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+async fn find_by_name(database: &DatabaseConnection, name: &str) -> Result<Model, BackendError> {
+    let result = tb_category::Entity::find()
+        .filter(tb_category::Column::Name.eq(name))
+        .one(database)
+        .await;
+
+    match result {
+        Ok(model_opt) => match model_opt {
+            Some(model) => Ok(model),
+            None => Err(BackendError::ResourceNotFoundError),
+        },
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
 async fn exists_by_name(database: &DatabaseConnection, name: &str) -> Result<bool, DbErr> {
     let stmt = Statement::from_string(
         DbBackend::MySql,
@@ -194,14 +575,20 @@ async fn exists_by_name(database: &DatabaseConnection, name: &str) -> Result<boo
     }
 }
 
-fn create_update_active_model(category_update_dto: CategoryDTO) -> ActiveModel {
-    let active_model = ActiveModel {
-        id: ActiveValue::Set(*category_update_dto.get_id()),
-        name: match category_update_dto.get_name().trim().is_empty() {
-            true => ActiveValue::NotSet,
-            false => ActiveValue::Set(category_update_dto.get_name().clone()),
+fn create_update_active_model(
+    id: u64,
+    category_patch_dto: CategoryPatchDTO,
+    version: u64,
+    actor: &ActorDTO,
+) -> ActiveModel {
+    ActiveModel {
+        id: ActiveValue::Set(id),
+        name: match category_patch_dto.get_name() {
+            Some(name) => ActiveValue::Set(name.clone()),
+            None => ActiveValue::NotSet,
         },
-    };
-
-    active_model
+        version: ActiveValue::Set(version + 1),
+        updated_by: ActiveValue::Set(*actor.get_user_id()),
+        ..Default::default()
+    }
 }
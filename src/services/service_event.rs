@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+
+use crate::{
+    configs::config_events,
+    entities::{
+        dtos::event_dtos::{MovementEventDTO, StockEventDTO},
+        enums::event_enums::StockEventType,
+        tb_product,
+    },
+};
+
+pub fn publish_product(event_type: StockEventType, product: &tb_product::Model) {
+    config_events::publish(StockEventDTO::new(event_type, product, None));
+
+    if product.deleted_at.is_none() && product.quantity < product.min_quantity {
+        config_events::publish(StockEventDTO::new(
+            StockEventType::StockBelowMinimum,
+            product,
+            None,
+        ));
+    }
+}
+
+pub async fn publish_movements(database: &DatabaseConnection, movements: Vec<MovementEventDTO>) {
+    if movements.is_empty() || !config_events::has_subscribers() {
+        return;
+    }
+
+    let product_ids: Vec<u64> = movements
+        .iter()
+        .map(|movement| *movement.get_product_id())
+        .collect();
+
+    let result = tb_product::Entity::find()
+        .filter(tb_product::Column::Id.is_in(product_ids))
+        .all(database)
+        .await;
+
+    // The movements are already committed, so a failed lookup only skips the notifications
+    let products: HashMap<u64, tb_product::Model> = match result {
+        Ok(products) => products
+            .into_iter()
+            .map(|product| (product.id, product))
+            .collect(),
+        Err(_) => return,
+    };
+
+    for movement in movements {
+        let product = match products.get(movement.get_product_id()) {
+            Some(product) => product,
+            None => continue,
+        };
+
+        config_events::publish(StockEventDTO::new(
+            StockEventType::MovementCreated,
+            product,
+            Some(movement),
+        ));
+
+        if product.quantity < product.min_quantity {
+            config_events::publish(StockEventDTO::new(
+                StockEventType::StockBelowMinimum,
+                product,
+                None,
+            ));
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use std::{future::ready, pin::pin};
+
+use csv::WriterBuilder;
+use rocket::futures::{
+    Stream, StreamExt,
+    stream::{self, BoxStream},
+};
+use rust_xlsxwriter::{Format, Workbook};
+use sea_orm::DbErr;
+
+use crate::{
+    entities::{
+        dtos::export_dtos::{ExportCell, ExportRow},
+        enums::export_enums::{ExportFormat, ExportLanguage},
+    },
+    errors::BackendError,
+};
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+pub enum ExportBody<'a> {
+    Stream(BoxStream<'a, Vec<u8>>),
+    Buffer(Vec<u8>),
+}
+
+pub struct ExportFile<'a> {
+    file_name: String,
+    format: ExportFormat,
+    body: ExportBody<'a>,
+}
+
+impl<'a> ExportFile<'a> {
+    pub fn get_file_name(&self) -> &String {
+        &self.file_name
+    }
+
+    pub fn get_format(&self) -> &ExportFormat {
+        &self.format
+    }
+
+    pub fn into_body(self) -> ExportBody<'a> {
+        self.body
+    }
+}
+
+pub async fn export_rows<'a, T, S>(
+    rows: S,
+    name: &str,
+    format: ExportFormat,
+    language: ExportLanguage,
+) -> Result<ExportFile<'a>, BackendError>
+where
+    T: ExportRow + Send + 'a,
+    S: Stream<Item = Result<T, DbErr>> + Send + 'a,
+{
+    let headers: Vec<&str> = T::HEADERS
+        .iter()
+        .map(|(portuguese, english)| language.translate(portuguese, english))
+        .collect();
+
+    let body = match format {
+        ExportFormat::Csv => ExportBody::Stream(stream_csv(rows, headers, language)),
+        ExportFormat::Xlsx => ExportBody::Buffer(write_xlsx(rows, headers, language).await?),
+    };
+
+    Ok(ExportFile {
+        file_name: format!("{name}.{}", format.get_extension()),
+        format,
+        body,
+    })
+}
+
+fn stream_csv<'a, T, S>(
+    rows: S,
+    headers: Vec<&str>,
+    language: ExportLanguage,
+) -> BoxStream<'a, Vec<u8>>
+where
+    T: ExportRow + Send + 'a,
+    S: Stream<Item = Result<T, DbErr>> + Send + 'a,
+{
+    let mut header_line = UTF8_BOM.to_vec();
+    header_line.extend(write_csv_line(headers.into_iter().map(str::to_string)));
+
+    let lines = rows
+        .take_while(|row| ready(row.is_ok()))
+        .filter_map(|row| ready(row.ok()))
+        .map(move |row| {
+            write_csv_line(row.into_cells(language).into_iter().map(|cell| match cell {
+                ExportCell::Number(number) => number.to_string(),
+                ExportCell::Text(text) => text,
+            }))
+        });
+
+    stream::once(ready(header_line)).chain(lines).boxed()
+}
+
+fn write_csv_line(fields: impl Iterator<Item = String>) -> Vec<u8> {
+    let mut writer = WriterBuilder::new().from_writer(Vec::new());
+
+    match writer.write_record(fields) {
+        Ok(_) => writer.into_inner().unwrap_or_default(),
+        Err(_) => Vec::new(),
+    }
+}
+
+async fn write_xlsx<T, S>(
+    rows: S,
+    headers: Vec<&str>,
+    language: ExportLanguage,
+) -> Result<Vec<u8>, BackendError>
+where
+    T: ExportRow,
+    S: Stream<Item = Result<T, DbErr>>,
+{
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet_with_constant_memory();
+    let header_format = Format::new().set_bold();
+
+    for (column, header) in headers.into_iter().enumerate() {
+        if worksheet
+            .write_string_with_format(0, column as u16, header, &header_format)
+            .is_err()
+        {
+            return Err(BackendError::ExportError);
+        }
+    }
+
+    let mut rows = pin!(rows);
+    let mut line: u32 = 1;
+
+    while let Some(row) = rows.next().await {
+        let row = match row {
+            Ok(row) => row,
+            Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+        };
+
+        for (column, cell) in row.into_cells(language).into_iter().enumerate() {
+            let result = match cell {
+                ExportCell::Number(number) => {
+                    worksheet.write_number(line, column as u16, number as f64)
+                }
+                ExportCell::Text(text) => worksheet.write_string(line, column as u16, text),
+            };
+
+            if result.is_err() {
+                return Err(BackendError::ExportError);
+            }
+        }
+
+        line += 1;
+    }
+
+    match workbook.save_to_buffer() {
+        Ok(buffer) => Ok(buffer),
+        Err(_) => Err(BackendError::ExportError),
+    }
+}
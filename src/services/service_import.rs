@@ -0,0 +1,426 @@
+use std::collections::{HashMap, HashSet};
+
+use csv::{ReaderBuilder, StringRecord, Trim};
+use sea_orm::{
+    ActiveModelBehavior, ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait,
+    DatabaseConnection, DbErr, EntityTrait, QueryFilter, TransactionTrait,
+};
+
+use crate::{
+    configs::config_environment::get_import_reason,
+    entities::{
+        dtos::{
+            event_dtos::MovementEventDTO,
+            generic_dtos::ActorDTO,
+            import_dtos::{ProductImportLineDTO, ProductImportResultDTO, ProductImportRowDTO},
+            product_dtos::ProductChangeQuantityDTO,
+        },
+        enums::{
+            audit_enums::{AuditAction, AuditEntity},
+            event_enums::StockEventType,
+            import_enums::ImportAction,
+        },
+        tb_category, tb_product,
+    },
+    errors::BackendError,
+    guards::guard_user::AuthenticationGuard,
+    services::{
+        service_audit, service_event, service_product, service_reason, service_report, service_user,
+    },
+};
+
+const MAX_IMPORT_ROWS: usize = 5000;
+const REQUIRED_HEADERS: [&str; 3] = ["name", "category", "min_quantity"];
+
+enum CategoryPlan {
+    Existing(u64),
+    New(String),
+}
+
+struct PlannedRow {
+    row: ProductImportRowDTO,
+    product: Option<tb_product::Model>,
+    category: CategoryPlan,
+}
+
+pub async fn import_products(
+    database: &DatabaseConnection,
+    csv: &str,
+    dry_run: bool,
+    authentication: AuthenticationGuard,
+) -> Result<ProductImportResultDTO, BackendError> {
+    let actor = match service_user::find_actor(database, &authentication).await {
+        Ok(actor) => actor,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let mut reader = ReaderBuilder::new()
+        .trim(Trim::All)
+        .from_reader(csv.as_bytes());
+
+    let headers = match reader.headers() {
+        Ok(headers) => headers.clone(),
+        Err(_) => return Err(BackendError::InvalidValuesError),
+    };
+
+    if REQUIRED_HEADERS
+        .iter()
+        .any(|required| !headers.iter().any(|header| header == *required))
+    {
+        return Err(BackendError::InvalidValuesError);
+    }
+
+    let records: Vec<(u64, Result<StringRecord, csv::Error>)> = reader
+        .records()
+        .map(|record| {
+            let line = match &record {
+                Ok(record) => record.position().map_or(0, |position| position.line()),
+                Err(csv_err) => csv_err.position().map_or(0, |position| position.line()),
+            };
+
+            (line, record)
+        })
+        .collect();
+
+    if records.is_empty() || records.len() > MAX_IMPORT_ROWS {
+        return Err(BackendError::InvalidValuesError);
+    }
+
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let mut lines = Vec::with_capacity(records.len());
+    let mut planned_rows = Vec::new();
+    let mut product_names = HashSet::new();
+    let mut category_names = HashSet::new();
+
+    for (line, record) in records {
+        let row = match record
+            .and_then(|record| record.deserialize::<ProductImportRowDTO>(Some(&headers)))
+        {
+            Ok(row) => row,
+            Err(_) => {
+                lines.push(ProductImportLineDTO::failed(
+                    line,
+                    None,
+                    "Linha com formato inválido",
+                ));
+                continue;
+            }
+        };
+
+        match plan_row(&transaction, row, &mut product_names).await {
+            Ok(Ok(planned_row)) => {
+                let create_category = match &planned_row.category {
+                    CategoryPlan::New(name) => category_names.insert(name.to_lowercase()),
+                    CategoryPlan::Existing(_) => false,
+                };
+
+                let action = match planned_row.product {
+                    Some(_) => ImportAction::Update,
+                    None => ImportAction::Create,
+                };
+
+                lines.push(ProductImportLineDTO::planned(
+                    line,
+                    &planned_row.row,
+                    action.as_str(),
+                    create_category,
+                ));
+                planned_rows.push(planned_row);
+            }
+            Ok(Err((row, error))) => {
+                lines.push(ProductImportLineDTO::failed(line, Some(&row), error));
+            }
+            Err(backend_error) => return Err(backend_error),
+        }
+    }
+
+    let created = planned_rows
+        .iter()
+        .filter(|planned_row| planned_row.product.is_none())
+        .count();
+    let updated = planned_rows.len() - created;
+
+    if dry_run || lines.iter().any(|line| line.is_failed()) {
+        return Ok(ProductImportResultDTO::new(
+            dry_run,
+            false,
+            created,
+            updated,
+            category_names.len(),
+            lines,
+        ));
+    }
+
+    let reason = match service_reason::find_by_name(database, &get_import_reason()).await {
+        Ok(model) => model,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let mut category_ids: HashMap<String, u64> = HashMap::new();
+    let mut products = Vec::with_capacity(planned_rows.len());
+    let mut movements = Vec::new();
+
+    for planned_row in planned_rows {
+        let category_id = match &planned_row.category {
+            CategoryPlan::Existing(id) => *id,
+            CategoryPlan::New(name) => match category_ids.get(&name.to_lowercase()) {
+                Some(id) => *id,
+                None => {
+                    let id = create_category(&transaction, name, &actor).await?;
+
+                    category_ids.insert(name.to_lowercase(), id);
+
+                    id
+                }
+            },
+        };
+
+        let product = match planned_row.product {
+            Some(product) => {
+                update_product(&transaction, product, &planned_row.row, category_id, &actor).await?
+            }
+            None => {
+                create_product(
+                    &transaction,
+                    &planned_row.row,
+                    category_id,
+                    reason.id,
+                    &actor,
+                )
+                .await?
+            }
+        };
+
+        if planned_row.row.get_quantity() > 0 {
+            movements.push(MovementEventDTO::new(
+                product.id,
+                true,
+                planned_row.row.get_quantity(),
+            ));
+        }
+
+        products.push(product);
+    }
+
+    match transaction.commit().await {
+        Ok(_) => {
+            for product in &products {
+                service_event::publish_product(StockEventType::ProductUpdated, product);
+            }
+
+            service_event::publish_movements(database, movements).await;
+
+            Ok(ProductImportResultDTO::new(
+                dry_run,
+                true,
+                created,
+                updated,
+                category_names.len(),
+                lines,
+            ))
+        }
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+async fn plan_row<C: ConnectionTrait>(
+    connection: &C,
+    row: ProductImportRowDTO,
+    product_names: &mut HashSet<String>,
+) -> Result<Result<PlannedRow, (ProductImportRowDTO, &'static str)>, BackendError> {
+    if row.get_name().is_empty() || row.get_name().chars().count() > 100 {
+        return Ok(Err((row, "Nome do produto inválido")));
+    }
+
+    if row.get_category().is_empty() || row.get_category().chars().count() > 100 {
+        return Ok(Err((row, "Nome da categoria inválido")));
+    }
+
+    if let Some(description) = row.get_description()
+        && description.chars().count() > 5000
+    {
+        return Ok(Err((row, "Descrição muito longa")));
+    }
+
+    if !product_names.insert(row.get_name().to_lowercase()) {
+        return Ok(Err((row, "Produto repetido no arquivo")));
+    }
+
+    let result = tb_product::Entity::find()
+        .filter(tb_product::Column::Name.eq(row.get_name()))
+        .one(connection)
+        .await;
+
+    let product = match result {
+        Ok(product) => product,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    if let Some(product) = &product {
+        if product.deleted_at.is_some() {
+            return Ok(Err((row, "O produto está removido")));
+        }
+
+        if row.get_quantity() > 0 {
+            return Ok(Err((
+                row,
+                "A quantidade inicial só pode ser informada para novos produtos",
+            )));
+        }
+    }
+
+    let result = tb_category::Entity::find()
+        .filter(tb_category::Column::Name.eq(row.get_category()))
+        .one(connection)
+        .await;
+
+    let category = match result {
+        Ok(Some(category)) if category.deleted_at.is_some() => {
+            return Ok(Err((row, "A categoria está removida")));
+        }
+        Ok(Some(category)) => CategoryPlan::Existing(category.id),
+        Ok(None) => CategoryPlan::New(row.get_category().clone()),
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    Ok(Ok(PlannedRow {
+        row,
+        product,
+        category,
+    }))
+}
+
+async fn create_category<C: ConnectionTrait>(
+    connection: &C,
+    name: &str,
+    actor: &ActorDTO,
+) -> Result<u64, BackendError> {
+    let category = tb_category::ActiveModel {
+        name: ActiveValue::Set(name.to_string()),
+        updated_by: ActiveValue::Set(*actor.get_user_id()),
+        ..Default::default()
+    };
+
+    let category = match category.insert(connection).await {
+        Ok(model) => model,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    service_audit::register_audit(
+        connection,
+        actor,
+        AuditEntity::Category,
+        category.id,
+        AuditAction::Create,
+        None,
+        service_audit::snapshot(&category),
+    )
+    .await?;
+
+    Ok(category.id)
+}
+
+async fn create_product<C: ConnectionTrait>(
+    connection: &C,
+    row: &ProductImportRowDTO,
+    category_id: u64,
+    reason_id: u64,
+    actor: &ActorDTO,
+) -> Result<tb_product::Model, BackendError> {
+    let product = tb_product::ActiveModel {
+        name: ActiveValue::Set(row.get_name().clone()),
+        min_quantity: ActiveValue::Set(*row.get_min_quantity()),
+        category_id: ActiveValue::Set(category_id),
+        quantity: ActiveValue::Set(0),
+        description: ActiveValue::Set(row.get_description().clone()),
+        updated_by: ActiveValue::Set(*actor.get_user_id()),
+        ..Default::default()
+    };
+
+    let product = match product.insert(connection).await {
+        Ok(model) => model,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    service_audit::register_audit(
+        connection,
+        actor,
+        AuditEntity::Product,
+        product.id,
+        AuditAction::Create,
+        None,
+        service_audit::snapshot(&product),
+    )
+    .await?;
+
+    if row.get_quantity() == 0 {
+        return Ok(product);
+    }
+
+    service_product::apply_quantity_change(connection, product.id, true, row.get_quantity())
+        .await?;
+
+    service_report::create_report(
+        connection,
+        ProductChangeQuantityDTO::new(product.id, true, row.get_quantity(), reason_id),
+        actor,
+    )
+    .await?;
+
+    Ok(product)
+}
+
+async fn update_product<C: ConnectionTrait>(
+    connection: &C,
+    old_product: tb_product::Model,
+    row: &ProductImportRowDTO,
+    category_id: u64,
+    actor: &ActorDTO,
+) -> Result<tb_product::Model, BackendError> {
+    let product = tb_product::ActiveModel {
+        id: ActiveValue::Set(old_product.id),
+        min_quantity: ActiveValue::Set(*row.get_min_quantity()),
+        category_id: ActiveValue::Set(category_id),
+        description: match row.get_description() {
+            Some(description) => ActiveValue::Set(Some(description.clone())),
+            None => ActiveValue::NotSet,
+        },
+        version: ActiveValue::Set(old_product.version + 1),
+        updated_by: ActiveValue::Set(*actor.get_user_id()),
+        ..Default::default()
+    };
+
+    // ActiveModel::update takes no filter, so the hook runs here before the versioned update
+    let product = match product.before_save(connection, false).await {
+        Ok(product) => product,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let result = tb_product::Entity::update(product)
+        .filter(tb_product::Column::Version.eq(old_product.version))
+        .exec(connection)
+        .await;
+
+    let product = match result {
+        Ok(model) => model,
+        Err(DbErr::RecordNotUpdated) => return Err(BackendError::ResourceStaleVersionError),
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    service_audit::register_audit(
+        connection,
+        actor,
+        AuditEntity::Product,
+        product.id,
+        AuditAction::Update,
+        service_audit::snapshot(&old_product),
+        service_audit::snapshot(&product),
+    )
+    .await?;
+
+    Ok(product)
+}
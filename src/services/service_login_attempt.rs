@@ -0,0 +1,117 @@
+use std::net::IpAddr;
+
+use chrono::{Duration, NaiveDateTime, Utc};
+use sea_orm::{
+    ActiveValue, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait, Statement,
+    TransactionTrait,
+};
+
+use crate::{
+    configs::config_environment::{
+        get_login_ip_max_attempts, get_login_ip_window_seconds, get_login_lockout_seconds,
+        get_login_max_lockout_seconds,
+    },
+    entities::tb_login_attempt::{self, ActiveModel},
+    errors::BackendError,
+};
+
+pub async fn is_ip_locked(
+    database: &DatabaseConnection,
+    ip: &IpAddr,
+) -> Result<bool, BackendError> {
+    let result = tb_login_attempt::Entity::find_by_id(ip.to_string())
+        .one(database)
+        .await;
+
+    match result {
+        Ok(Some(login_attempt)) => Ok(is_locked(&login_attempt.locked_until)),
+        Ok(None) => Ok(false),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn register_ip_failure(
+    database: &DatabaseConnection,
+    ip: &IpAddr,
+) -> Result<(), BackendError> {
+    let now = Utc::now().naive_utc();
+    let window_start = now - Duration::seconds(get_login_ip_window_seconds() as i64);
+
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    // The upsert counts the failure atomically and keeps the row locked until the lockout is
+    // written, so parallel attempts cannot lose failures. Failures after the window start over
+    let stmt = Statement::from_sql_and_values(
+        DbBackend::MySql,
+        r#"
+            INSERT INTO tb_login_attempt (ip, failed_attempts, last_attempt_at)
+            VALUES (?, 1, ?)
+            ON DUPLICATE KEY UPDATE
+                failed_attempts = IF(last_attempt_at >= ?, failed_attempts + 1, 1),
+                last_attempt_at = ?
+        "#,
+        [
+            ip.to_string().into(),
+            now.into(),
+            window_start.into(),
+            now.into(),
+        ],
+    );
+
+    if let Err(db_err) = transaction.execute(stmt).await {
+        return Err(BackendError::DatabaseError(db_err));
+    }
+
+    let result = tb_login_attempt::Entity::find_by_id(ip.to_string())
+        .one(&transaction)
+        .await;
+
+    let login_attempt = match result {
+        Ok(Some(login_attempt)) => login_attempt,
+        Ok(None) => return Err(BackendError::ResourceNotFoundError),
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let active_model = ActiveModel {
+        ip: ActiveValue::Set(login_attempt.ip),
+        locked_until: ActiveValue::Set(calculate_locked_until(
+            login_attempt.failed_attempts,
+            get_login_ip_max_attempts(),
+        )),
+        ..Default::default()
+    };
+
+    if let Err(db_err) = tb_login_attempt::Entity::update(active_model)
+        .exec(&transaction)
+        .await
+    {
+        return Err(BackendError::DatabaseError(db_err));
+    }
+
+    match transaction.commit().await {
+        Ok(_) => Ok(()),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub fn calculate_locked_until(failed_attempts: u32, max_attempts: u32) -> Option<NaiveDateTime> {
+    if failed_attempts < max_attempts {
+        return None;
+    }
+
+    let exponent = (failed_attempts - max_attempts).min(16);
+    let lockout_seconds = (get_login_lockout_seconds() as u64 * 2u64.pow(exponent))
+        .min(get_login_max_lockout_seconds() as u64);
+
+    Some(Utc::now().naive_utc() + Duration::seconds(lockout_seconds as i64))
+}
+
+pub fn is_locked(locked_until: &Option<NaiveDateTime>) -> bool {
+    match locked_until {
+        Some(locked_until) => locked_until > &Utc::now().naive_utc(),
+        None => false,
+    }
+}
@@ -0,0 +1,354 @@
+use printpdf::{
+    BuiltinFont, Color, IndirectFontRef, Line, Mm, PdfDocument, PdfDocumentReference,
+    PdfLayerReference, Point, Rect, Rgb, path::PaintMode,
+};
+
+use crate::{
+    entities::dtos::report_dtos::{ProductStatementLineDTO, StockPositionDTO},
+    errors::BackendError,
+};
+
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN: f32 = 15.0;
+const LINE_HEIGHT: f32 = 6.0;
+const TEXT_SIZE: f32 = 9.0;
+const TITLE_SIZE: f32 = 14.0;
+
+type Columns = &'static [(f32, usize)];
+
+const STOCK_POSITION_COLUMNS: Columns = &[(0.0, 60), (110.0, 14), (135.0, 14), (160.0, 20)];
+const STATEMENT_COLUMNS: Columns = &[
+    (0.0, 19),
+    (33.0, 8),
+    (48.0, 10),
+    (66.0, 10),
+    (84.0, 22),
+    (122.0, 16),
+    (150.0, 18),
+];
+
+struct PdfWriter {
+    document: PdfDocumentReference,
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+    layer: PdfLayerReference,
+    y: f32,
+    table: Option<(Columns, Vec<String>)>,
+}
+
+impl PdfWriter {
+    fn new(title: &str) -> Result<Self, BackendError> {
+        let (document, page, layer) =
+            PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Página");
+
+        let regular = match document.add_builtin_font(BuiltinFont::Helvetica) {
+            Ok(font) => font,
+            Err(_) => return Err(BackendError::ExportError),
+        };
+
+        let bold = match document.add_builtin_font(BuiltinFont::HelveticaBold) {
+            Ok(font) => font,
+            Err(_) => return Err(BackendError::ExportError),
+        };
+
+        let layer = document.get_page(page).get_layer(layer);
+
+        Ok(Self {
+            document,
+            regular,
+            bold,
+            layer,
+            y: PAGE_HEIGHT - MARGIN,
+            table: None,
+        })
+    }
+
+    fn reserve(&mut self, height: f32) {
+        if self.y - height >= MARGIN {
+            return;
+        }
+
+        let (page, layer) = self
+            .document
+            .add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Página");
+
+        self.layer = self.document.get_page(page).get_layer(layer);
+        self.y = PAGE_HEIGHT - MARGIN;
+
+        if let Some((columns, headers)) = self.table.take() {
+            self.write_row(columns, &headers, true, false);
+            self.table = Some((columns, headers));
+        }
+    }
+
+    fn title(&mut self, text: &str) {
+        self.reserve(LINE_HEIGHT * 2.0);
+
+        self.layer.use_text(
+            text,
+            TITLE_SIZE,
+            Mm(MARGIN),
+            Mm(self.y - LINE_HEIGHT),
+            &self.bold,
+        );
+
+        self.y -= LINE_HEIGHT * 2.0;
+    }
+
+    fn paragraph(&mut self, text: &str, bold: bool) {
+        self.reserve(LINE_HEIGHT);
+
+        let font = match bold {
+            true => &self.bold,
+            false => &self.regular,
+        };
+
+        self.layer
+            .use_text(text, TEXT_SIZE, Mm(MARGIN), Mm(self.y - 4.2), font);
+
+        self.y -= LINE_HEIGHT;
+    }
+
+    fn space(&mut self) {
+        self.y -= LINE_HEIGHT / 2.0;
+    }
+
+    fn start_table(&mut self, columns: Columns, headers: &[&str]) {
+        let headers: Vec<String> = headers.iter().map(|header| header.to_string()).collect();
+
+        self.reserve(LINE_HEIGHT * 2.0);
+        self.write_row(columns, &headers, true, false);
+        self.table = Some((columns, headers));
+    }
+
+    fn end_table(&mut self) {
+        self.table = None;
+        self.space();
+    }
+
+    fn row(&mut self, values: &[String], highlight: bool) {
+        let columns = match &self.table {
+            Some((columns, _)) => *columns,
+            None => return,
+        };
+
+        self.reserve(LINE_HEIGHT);
+        self.write_row(columns, values, false, highlight);
+    }
+
+    fn write_row(
+        &mut self,
+        columns: &[(f32, usize)],
+        values: &[String],
+        header: bool,
+        highlight: bool,
+    ) {
+        if highlight {
+            self.layer
+                .set_fill_color(Color::Rgb(Rgb::new(1.0, 0.85, 0.85, None)));
+            self.layer.add_rect(
+                Rect::new(
+                    Mm(MARGIN),
+                    Mm(self.y - LINE_HEIGHT),
+                    Mm(PAGE_WIDTH - MARGIN),
+                    Mm(self.y),
+                )
+                .with_mode(PaintMode::Fill),
+            );
+            self.layer
+                .set_fill_color(Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None)));
+        }
+
+        let font = match header {
+            true => &self.bold,
+            false => &self.regular,
+        };
+
+        for ((x, max_chars), value) in columns.iter().zip(values) {
+            self.layer.use_text(
+                fit(value, *max_chars),
+                TEXT_SIZE,
+                Mm(MARGIN + x),
+                Mm(self.y - 4.2),
+                font,
+            );
+        }
+
+        self.y -= LINE_HEIGHT;
+
+        if header {
+            self.layer.add_line(Line {
+                points: vec![
+                    (Point::new(Mm(MARGIN), Mm(self.y + 0.8)), false),
+                    (Point::new(Mm(PAGE_WIDTH - MARGIN), Mm(self.y + 0.8)), false),
+                ],
+                is_closed: false,
+            });
+        }
+    }
+
+    fn signatures(&mut self) {
+        self.reserve(LINE_HEIGHT * 6.0);
+        self.space();
+        self.space();
+        self.paragraph(
+            "Responsável: ________________________________    Assinatura: ________________________________",
+            false,
+        );
+        self.space();
+        self.paragraph("Data: ____/____/________", false);
+    }
+
+    fn finish(self) -> Result<Vec<u8>, BackendError> {
+        match self.document.save_to_bytes() {
+            Ok(bytes) => Ok(bytes),
+            Err(_) => Err(BackendError::ExportError),
+        }
+    }
+}
+
+pub fn render_stock_position(
+    positions: &[StockPositionDTO],
+    generated_at: &str,
+    generated_by: &str,
+) -> Result<Vec<u8>, BackendError> {
+    let mut writer = PdfWriter::new("Posição de estoque")?;
+
+    writer.title("Posição de estoque");
+    writer.paragraph(
+        &format!("Gerado em {generated_at} por {generated_by}"),
+        false,
+    );
+    writer.paragraph(
+        &format!(
+            "Produtos abaixo do mínimo: {}",
+            positions
+                .iter()
+                .filter(|position| position.get_quantity() < position.get_min_quantity())
+                .count()
+        ),
+        false,
+    );
+    writer.space();
+
+    let mut category: Option<&String> = None;
+
+    for position in positions {
+        if category != Some(position.get_category()) {
+            if category.is_some() {
+                writer.end_table();
+            }
+
+            category = Some(position.get_category());
+
+            writer.reserve(LINE_HEIGHT * 3.0);
+            writer.paragraph(position.get_category(), true);
+            writer.start_table(
+                STOCK_POSITION_COLUMNS,
+                &["Produto", "Quantidade", "Mínimo", "Situação"],
+            );
+        }
+
+        let below_minimum = position.get_quantity() < position.get_min_quantity();
+
+        writer.row(
+            &[
+                position.get_product().clone(),
+                position.get_quantity().to_string(),
+                position.get_min_quantity().to_string(),
+                match below_minimum {
+                    true => "Abaixo do mínimo".to_string(),
+                    false => String::new(),
+                },
+            ],
+            below_minimum,
+        );
+    }
+
+    if category.is_some() {
+        writer.end_table();
+    }
+
+    writer.signatures();
+    writer.finish()
+}
+
+pub fn render_product_statement(
+    product: &str,
+    category: &str,
+    period: &str,
+    opening_balance: i64,
+    lines: &[ProductStatementLineDTO],
+    generated_at: &str,
+    generated_by: &str,
+) -> Result<Vec<u8>, BackendError> {
+    let mut writer = PdfWriter::new("Extrato de movimentações")?;
+
+    writer.title("Extrato de movimentações");
+    writer.paragraph(&format!("Produto: {product}"), true);
+    writer.paragraph(&format!("Categoria: {category}"), false);
+    writer.paragraph(&format!("Período: {period}"), false);
+    writer.paragraph(
+        &format!("Gerado em {generated_at} por {generated_by}"),
+        false,
+    );
+    writer.space();
+    writer.paragraph(&format!("Saldo inicial: {opening_balance}"), true);
+    writer.start_table(
+        STATEMENT_COLUMNS,
+        &[
+            "Data",
+            "Tipo",
+            "Quantidade",
+            "Saldo",
+            "Motivo",
+            "Documento",
+            "Responsável",
+        ],
+    );
+
+    let mut balance = opening_balance;
+
+    for line in lines {
+        let quantity = *line.get_quantity() as i64;
+
+        balance += match line.get_change_type() {
+            true => quantity,
+            false => -quantity,
+        };
+
+        writer.row(
+            &[
+                line.get_date().clone(),
+                match line.get_change_type() {
+                    true => "Entrada".to_string(),
+                    false => "Saída".to_string(),
+                },
+                line.get_quantity().to_string(),
+                balance.to_string(),
+                line.get_reason().clone(),
+                line.get_document().clone().unwrap_or_default(),
+                line.get_actor().cloned().unwrap_or_default(),
+            ],
+            false,
+        );
+    }
+
+    writer.end_table();
+    writer.paragraph(&format!("Saldo final: {balance}"), true);
+    writer.signatures();
+    writer.finish()
+}
+
+fn fit(text: &str, max_chars: usize) -> String {
+    match text.chars().count() > max_chars {
+        true => format!(
+            "{}...",
+            text.chars()
+                .take(max_chars.saturating_sub(3))
+                .collect::<String>()
+        ),
+        false => text.to_string(),
+    }
+}
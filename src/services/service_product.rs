@@ -1,46 +1,95 @@
+use std::collections::HashMap;
+
+use chrono::{NaiveDateTime, Utc};
 use sea_orm::{
-    ActiveValue, ColumnTrait, DatabaseConnection, DbBackend, DbErr, EntityTrait, FromQueryResult,
-    QueryFilter, Statement,
+    ActiveModelBehavior, ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait,
+    DatabaseConnection, DatabaseTransaction, DbBackend, DbErr, EntityTrait, FromQueryResult,
+    QueryFilter, QueryOrder, QuerySelect, Statement, TransactionTrait, Value,
+    sea_query::{Expr, Query},
 };
 
 use crate::{
     entities::{
         dtos::{
-            generic_dtos::ExistsDTO,
+            event_dtos::MovementEventDTO,
+            generic_dtos::{ActorDTO, ExistsDTO, SortDTO},
             product_dtos::{
-                ProductChangeQuantityDTO, ProductCreateDTO, ProductInformationsGetDTO,
-                ProductInformationsViewDTO, ProductSummaryDTO, ProductUpdateDTO, ProductViewDTO,
+                ProductAvailableDTO, ProductBatchQuantityDTO, ProductChangeQuantityDTO,
+                ProductCreateDTO, ProductInformationsGetDTO, ProductInformationsViewDTO,
+                ProductPatchDTO, ProductSummaryDTO, ProductUpdateDTO, ProductViewDTO,
             },
         },
+        enums::{
+            analytics_enums::AbcClass,
+            audit_enums::{AuditAction, AuditEntity},
+            event_enums::StockEventType,
+            export_enums::{ExportFormat, ExportLanguage},
+        },
         tb_product::{self, ActiveModel, Model},
+        tb_report, tb_reservation, tb_stocktake_item,
     },
     errors::BackendError,
-    services::service_report,
+    guards::guard_user::AuthenticationGuard,
+    services::{
+        service_analytics, service_audit, service_category, service_event,
+        service_export::{self, ExportFile},
+        service_reason, service_report, service_reservation,
+        service_reservation::{AVAILABLE_QUANTITY, RESERVED_QUANTITY_JOIN},
+        service_stocktake, service_user,
+    },
 };
 
+const MAX_BATCH_MOVEMENTS: usize = 1000;
+
 pub async fn get_all_products(
     database: &DatabaseConnection,
+    include_deleted: bool,
+    abc_class: Option<AbcClass>,
+    sort_dto: &SortDTO,
 ) -> Result<Vec<ProductViewDTO>, BackendError> {
-    let stmt = Statement::from_string(
-        DbBackend::MySql,
-        r#"
-            SELECT
-                tb_product.id,
-                tb_product.name,
-                tb_product.quantity,
-                tb_product.min_quantity,
-                tb_category.name as category,
-                tb_product.description
-            FROM tb_product
-            JOIN tb_category
-                ON tb_category.id = tb_product.category_id
-        "#,
-    );
+    let result = ProductViewDTO::find_by_statement(all_products_statement(
+        include_deleted,
+        abc_class,
+        sort_dto,
+    ))
+    .all(database)
+    .await;
 
-    let result = ProductViewDTO::find_by_statement(stmt).all(database).await;
+    let mut products = match result {
+        Ok(products) => products,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let demands = service_analytics::find_default_daily_demands(database, None).await?;
+
+    for product in &mut products {
+        let daily_demand = demands.get(product.get_id()).copied().unwrap_or(0.0);
+
+        product.set_cover(service_analytics::estimate_cover(
+            *product.get_available(),
+            daily_demand,
+        ));
+    }
+
+    Ok(products)
+}
+
+pub async fn export_products(
+    database: &DatabaseConnection,
+    include_deleted: bool,
+    format: ExportFormat,
+    language: ExportLanguage,
+) -> Result<ExportFile<'_>, BackendError> {
+    let result = ProductViewDTO::find_by_statement(all_products_statement(
+        include_deleted,
+        None,
+        &SortDTO::default(),
+    ))
+    .stream(database)
+    .await;
 
     match result {
-        Ok(products) => Ok(products),
+        Ok(products) => service_export::export_rows(products, "products", format, language).await,
         Err(db_err) => Err(BackendError::DatabaseError(db_err)),
     }
 }
@@ -56,6 +105,7 @@ pub async fn get_products_informations(
                 CAST(SUM(quantity) AS UNSIGNED) AS total,
                 CAST(SUM(quantity < min_quantity) AS UNSIGNED) AS warnings
             FROM tb_product
+            WHERE deleted_at IS NULL
         "#,
     );
 
@@ -82,16 +132,26 @@ pub async fn get_product_by_id(
             "
             SELECT
                 tb_product.name,
+                tb_product.barcode,
                 tb_product.quantity,
+                {AVAILABLE_QUANTITY},
                 tb_product.min_quantity,
                 tb_category.name AS category,
-                tb_product.description
+                tb_product.description,
+                tb_product.abc_class,
+                tb_product.version,
+                {}
             FROM tb_product
             JOIN tb_category
                 ON tb_category.id = tb_product.category_id
+            {RESERVED_QUANTITY_JOIN}
+            {}
             WHERE
                 tb_product.id = (\"{id}\")
-        "
+                AND tb_product.deleted_at IS NULL
+        ",
+            service_user::authorship_columns("tb_product"),
+            service_user::authorship_join("tb_product")
         ),
     );
 
@@ -99,19 +159,33 @@ pub async fn get_product_by_id(
         .one(database)
         .await;
 
-    match result {
-        Ok(product_opt) => match product_opt {
-            Some(product) => Ok(product),
-            None => Err(BackendError::ResourceNotFoundError),
-        },
-        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
-    }
+    let mut product = match result {
+        Ok(Some(product)) => product,
+        Ok(None) => return Err(BackendError::ResourceNotFoundError),
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let demands = service_analytics::find_default_daily_demands(database, Some(id)).await?;
+    let daily_demand = demands.get(&id).copied().unwrap_or(0.0);
+
+    product.set_cover(service_analytics::estimate_cover(
+        *product.get_available(),
+        daily_demand,
+    ));
+
+    Ok(product)
 }
 
 pub async fn create_product(
     database: &DatabaseConnection,
     product_create_dto: ProductCreateDTO,
+    authentication: AuthenticationGuard,
 ) -> Result<(), BackendError> {
+    let actor = match service_user::find_actor(database, &authentication).await {
+        Ok(actor) => actor,
+        Err(backend_error) => return Err(backend_error),
+    };
+
     match exists_by_name(database, product_create_dto.get_name()).await {
         Ok(boolean) => {
             if boolean {
@@ -121,19 +195,66 @@ pub async fn create_product(
         Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
     }
 
+    let barcode = match normalize_barcode(product_create_dto.get_barcode()) {
+        Ok(barcode) => barcode,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    if let Some(barcode) = &barcode
+        && find_by_barcode(database, barcode).await.is_ok()
+    {
+        return Err(BackendError::ResourceAlreadyInsertedError);
+    }
+
+    match service_category::exists_by_id(database, product_create_dto.get_category_id()).await {
+        Ok(boolean) => {
+            if !boolean {
+                return Err(BackendError::ResourceNotFoundError);
+            }
+        }
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    }
+
     let product = ActiveModel {
         name: ActiveValue::Set(product_create_dto.get_name().clone()),
+        barcode: ActiveValue::Set(barcode),
         min_quantity: ActiveValue::Set(*product_create_dto.get_min_quantity()),
         category_id: ActiveValue::Set(*product_create_dto.get_category_id()),
         quantity: ActiveValue::Set(0),
         description: ActiveValue::Set(product_create_dto.get_description().clone()),
+        updated_by: ActiveValue::Set(*actor.get_user_id()),
         ..Default::default()
     };
 
-    let result = tb_product::Entity::insert(product).exec(database).await;
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
 
-    match result {
-        Ok(_) => Ok(()),
+    let result = product.insert(&transaction).await;
+
+    let product = match result {
+        Ok(model) => model,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    service_audit::register_audit(
+        &transaction,
+        &actor,
+        AuditEntity::Product,
+        product.id,
+        AuditAction::Create,
+        None,
+        service_audit::snapshot(&product),
+    )
+    .await?;
+
+    match transaction.commit().await {
+        Ok(_) => {
+            service_event::publish_product(StockEventType::ProductUpdated, &product);
+
+            Ok(())
+        }
         Err(db_err) => Err(BackendError::DatabaseError(db_err)),
     }
 }
@@ -141,34 +262,185 @@ pub async fn create_product(
 pub async fn update_product(
     database: &DatabaseConnection,
     product_update_dto: ProductUpdateDTO,
+    authentication: AuthenticationGuard,
 ) -> Result<(), BackendError> {
-    match exists_by_id(database, product_update_dto.get_id()).await {
-        Ok(boolean) => {
-            if !boolean {
-                return Err(BackendError::ResourceNotFoundError);
+    let id = *product_update_dto.get_id();
+
+    patch_product(database, id, product_update_dto.into(), authentication).await
+}
+
+pub async fn patch_product(
+    database: &DatabaseConnection,
+    id: u64,
+    product_patch_dto: ProductPatchDTO,
+    authentication: AuthenticationGuard,
+) -> Result<(), BackendError> {
+    let actor = match service_user::find_actor(database, &authentication).await {
+        Ok(actor) => actor,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let version = match product_patch_dto.get_version() {
+        Some(version) => *version,
+        None => return Err(BackendError::ResourceVersionRequiredError),
+    };
+
+    let old_product = match find_product_by_id(database, id).await {
+        Ok(model) => model,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    if old_product.deleted_at.is_some() {
+        return Err(BackendError::ResourceNotFoundError);
+    }
+
+    if old_product.version != version {
+        return Err(BackendError::ResourceStaleVersionError);
+    }
+
+    if let Some(name) = product_patch_dto.get_name()
+        && name.trim().is_empty()
+    {
+        return Err(BackendError::InvalidValuesError);
+    }
+
+    if let Some(Some(description)) = product_patch_dto.get_description()
+        && description.trim().is_empty()
+    {
+        return Err(BackendError::InvalidValuesError);
+    }
+
+    if let Some(category_id) = product_patch_dto.get_category_id() {
+        match service_category::exists_by_id(database, category_id).await {
+            Ok(boolean) => {
+                if !boolean {
+                    return Err(BackendError::ResourceNotFoundError);
+                }
             }
+            Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
         }
-        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
     }
 
-    if let Ok(old_product) = find_by_name(database, product_update_dto.get_name()).await {
-        if &old_product.id != product_update_dto.get_id() {
-            return Err(BackendError::ResourceConflitUpdateError);
-        }
+    if let Some(name) = product_patch_dto.get_name()
+        && let Ok(product) = find_by_name(database, name).await
+        && product.id != id
+    {
+        return Err(BackendError::ResourceConflitUpdateError);
     }
 
-    let product = create_update_active_model(product_update_dto);
+    // A blank barcode would read as a removal, which the patch asks for with null
+    let barcode = match product_patch_dto.get_barcode() {
+        Some(Some(barcode)) => match normalize_barcode(&Some(barcode.clone())) {
+            Ok(Some(barcode)) => ActiveValue::Set(Some(barcode)),
+            Ok(None) => return Err(BackendError::InvalidValuesError),
+            Err(backend_error) => return Err(backend_error),
+        },
+        Some(None) => ActiveValue::Set(None),
+        None => ActiveValue::NotSet,
+    };
+
+    if let ActiveValue::Set(Some(barcode)) = &barcode
+        && let Ok(product) = find_by_barcode(database, barcode).await
+        && product.id != id
+    {
+        return Err(BackendError::ResourceConflitUpdateError);
+    }
 
-    let result = tb_product::Entity::update(product).exec(database).await;
+    let product = create_update_active_model(id, product_patch_dto, barcode, version, &actor);
 
-    match result {
-        Ok(_) => Ok(()),
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    // ActiveModel::update takes no filter, so the hook runs here before the versioned update
+    let product = match product.before_save(&transaction, false).await {
+        Ok(product) => product,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    // The version filter rejects writes that raced with another update after the check above
+    let result = tb_product::Entity::update(product)
+        .filter(tb_product::Column::Version.eq(version))
+        .exec(&transaction)
+        .await;
+
+    let product = match result {
+        Ok(model) => model,
+        Err(DbErr::RecordNotUpdated) => return Err(BackendError::ResourceStaleVersionError),
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    service_audit::register_audit(
+        &transaction,
+        &actor,
+        AuditEntity::Product,
+        product.id,
+        AuditAction::Update,
+        service_audit::snapshot(&old_product),
+        service_audit::snapshot(&product),
+    )
+    .await?;
+
+    match transaction.commit().await {
+        Ok(_) => {
+            service_event::publish_product(StockEventType::ProductUpdated, &product);
+
+            Ok(())
+        }
         Err(db_err) => Err(BackendError::DatabaseError(db_err)),
     }
 }
 
-pub async fn delete_by_id(database: &DatabaseConnection, id: u64) -> Result<(), BackendError> {
-    match exists_by_id(database, &id).await {
+pub async fn delete_by_id(
+    database: &DatabaseConnection,
+    id: u64,
+    authentication: AuthenticationGuard,
+) -> Result<(), BackendError> {
+    let actor = match service_user::find_actor(database, &authentication).await {
+        Ok(actor) => actor,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let product = match find_product_by_id(database, id).await {
+        Ok(model) => model,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    if product.deleted_at.is_some() {
+        return Err(BackendError::ResourceNotFoundError);
+    }
+
+    update_deleted_at(
+        database,
+        product,
+        Some(Utc::now().naive_utc()),
+        actor,
+        AuditAction::Delete,
+    )
+    .await
+}
+
+pub async fn restore_by_id(
+    database: &DatabaseConnection,
+    id: u64,
+    authentication: AuthenticationGuard,
+) -> Result<(), BackendError> {
+    let actor = match service_user::find_actor(database, &authentication).await {
+        Ok(actor) => actor,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let product = match find_product_by_id(database, id).await {
+        Ok(model) => model,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    if product.deleted_at.is_none() {
+        return Ok(());
+    }
+
+    match service_category::exists_by_id(database, &product.category_id).await {
         Ok(boolean) => {
             if !boolean {
                 return Err(BackendError::ResourceNotFoundError);
@@ -177,9 +449,92 @@ pub async fn delete_by_id(database: &DatabaseConnection, id: u64) -> Result<(),
         Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
     }
 
-    let result = tb_product::Entity::delete_by_id(id).exec(database).await;
+    update_deleted_at(database, product, None, actor, AuditAction::Restore).await
+}
+
+pub async fn purge_deleted(
+    database: &DatabaseConnection,
+    deleted_before: NaiveDateTime,
+) -> Result<(), BackendError> {
+    let result = tb_product::Entity::find()
+        .filter(tb_product::Column::DeletedAt.lt(deleted_before))
+        // Rows still referenced by a RESTRICT foreign key would abort the whole transaction
+        .filter(
+            Expr::exists(
+                Query::select()
+                    .expr(Expr::val(1))
+                    .from(tb_report::Entity)
+                    .and_where(
+                        Expr::col((tb_report::Entity, tb_report::Column::ProductId))
+                            .equals((tb_product::Entity, tb_product::Column::Id)),
+                    )
+                    .to_owned(),
+            )
+            .not(),
+        )
+        .filter(
+            Expr::exists(
+                Query::select()
+                    .expr(Expr::val(1))
+                    .from(tb_reservation::Entity)
+                    .and_where(
+                        Expr::col((tb_reservation::Entity, tb_reservation::Column::ProductId))
+                            .equals((tb_product::Entity, tb_product::Column::Id)),
+                    )
+                    .to_owned(),
+            )
+            .not(),
+        )
+        .filter(
+            Expr::exists(
+                Query::select()
+                    .expr(Expr::val(1))
+                    .from(tb_stocktake_item::Entity)
+                    .and_where(
+                        Expr::col((
+                            tb_stocktake_item::Entity,
+                            tb_stocktake_item::Column::ProductId,
+                        ))
+                        .equals((tb_product::Entity, tb_product::Column::Id)),
+                    )
+                    .to_owned(),
+            )
+            .not(),
+        )
+        .all(database)
+        .await;
 
-    match result {
+    let products = match result {
+        Ok(products) => products,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    for product in products {
+        if let Err(db_err) = tb_product::Entity::delete_by_id(product.id)
+            .exec(&transaction)
+            .await
+        {
+            return Err(BackendError::DatabaseError(db_err));
+        }
+
+        service_audit::register_audit(
+            &transaction,
+            &ActorDTO::new(None, None),
+            AuditEntity::Product,
+            product.id,
+            AuditAction::Purge,
+            service_audit::snapshot(&product),
+            None,
+        )
+        .await?;
+    }
+
+    match transaction.commit().await {
         Ok(_) => Ok(()),
         Err(db_err) => Err(BackendError::DatabaseError(db_err)),
     }
@@ -188,8 +543,15 @@ pub async fn delete_by_id(database: &DatabaseConnection, id: u64) -> Result<(),
 pub async fn change_quantity(
     database: &DatabaseConnection,
     product_change_quantity_dto: ProductChangeQuantityDTO,
+    authentication: AuthenticationGuard,
 ) -> Result<(), BackendError> {
-    match exists_by_id(database, product_change_quantity_dto.get_id()).await {
+    let actor = match service_user::find_actor(database, &authentication).await {
+        Ok(actor) => actor,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    match service_reason::exists_by_id(database, product_change_quantity_dto.get_reason_id()).await
+    {
         Ok(boolean) => {
             if !boolean {
                 return Err(BackendError::ResourceNotFoundError);
@@ -198,27 +560,379 @@ pub async fn change_quantity(
         Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
     }
 
-    let product = match find_product_by_id(database, *product_change_quantity_dto.get_id()).await {
-        Ok(model) => model,
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let movement = MovementEventDTO::new(
+        *product_change_quantity_dto.get_id(),
+        *product_change_quantity_dto.get_change_type(),
+        *product_change_quantity_dto.get_quantity(),
+    );
+
+    apply_movement(&transaction, product_change_quantity_dto, &actor).await?;
+
+    match transaction.commit().await {
+        Ok(_) => {
+            service_event::publish_movements(database, vec![movement]).await;
+
+            Ok(())
+        }
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn change_quantity_batch(
+    database: &DatabaseConnection,
+    product_batch_quantity_dto: ProductBatchQuantityDTO,
+    authentication: AuthenticationGuard,
+) -> Result<Vec<Result<(), BackendError>>, BackendError> {
+    let actor = match service_user::find_actor(database, &authentication).await {
+        Ok(actor) => actor,
         Err(backend_error) => return Err(backend_error),
     };
 
-    let updated_product = create_update_change_active_model(&product_change_quantity_dto, product);
+    let movements = product_batch_quantity_dto.get_movements().len();
 
-    match updated_product {
-        Ok(new_product) => match tb_product::Entity::update(new_product).exec(database).await {
-            Ok(_) => {
-                let result =
-                    service_report::create_report(database, product_change_quantity_dto).await;
+    if movements == 0 || movements > MAX_BATCH_MOVEMENTS {
+        return Err(BackendError::InvalidValuesError);
+    }
 
-                result
+    match service_reason::exists_by_id(database, product_batch_quantity_dto.get_reason_id()).await {
+        Ok(boolean) => {
+            if !boolean {
+                return Err(BackendError::ResourceNotFoundError);
             }
+        }
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    }
+
+    let reason_id = *product_batch_quantity_dto.get_reason_id();
+    let document = product_batch_quantity_dto.get_document().clone();
+    let partial = *product_batch_quantity_dto.get_partial();
+
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let mut results = Vec::with_capacity(movements);
+    let mut applied_movements = Vec::with_capacity(movements);
+
+    for movement in product_batch_quantity_dto.into_movements() {
+        let product_change_quantity_dto =
+            movement.into_change_quantity_dto(reason_id, document.clone());
+
+        let movement = MovementEventDTO::new(
+            *product_change_quantity_dto.get_id(),
+            *product_change_quantity_dto.get_change_type(),
+            *product_change_quantity_dto.get_quantity(),
+        );
+
+        let result = match partial {
+            true => {
+                apply_movement_in_savepoint(&transaction, product_change_quantity_dto, &actor).await
+            }
+            false => {
+                apply_movement(&transaction, product_change_quantity_dto, &actor).await?;
+
+                Ok(())
+            }
+        };
+
+        if result.is_ok() {
+            applied_movements.push(movement);
+        }
+
+        results.push(result);
+    }
+
+    match transaction.commit().await {
+        Ok(_) => {
+            service_event::publish_movements(database, applied_movements).await;
+
+            Ok(results)
+        }
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn apply_quantity_change<C: ConnectionTrait>(
+    connection: &C,
+    id: u64,
+    change_type: bool,
+    quantity: u64,
+) -> Result<(), BackendError> {
+    let result = tb_product::Entity::find_by_id(id)
+        .lock_exclusive()
+        .one(connection)
+        .await;
+
+    let product = match result {
+        Ok(Some(model)) if model.deleted_at.is_none() => model,
+        Ok(_) => return Err(BackendError::ResourceNotFoundError),
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    if service_stocktake::is_product_frozen(connection, id).await? {
+        return Err(BackendError::ResourceLockedError);
+    }
+
+    let updated_product = create_update_change_active_model(&product, change_type, quantity);
+
+    match updated_product {
+        Ok(new_product) => match tb_product::Entity::update(new_product)
+            .exec(connection)
+            .await
+        {
+            Ok(_) => Ok(()),
             Err(db_err) => Err(BackendError::DatabaseError(db_err)),
         },
         Err(backend_error) => Err(backend_error),
     }
 }
 
+async fn update_deleted_at(
+    database: &DatabaseConnection,
+    product: Model,
+    deleted_at: Option<NaiveDateTime>,
+    actor: ActorDTO,
+    action: AuditAction,
+) -> Result<(), BackendError> {
+    let updated_product = ActiveModel {
+        id: ActiveValue::Set(product.id),
+        deleted_at: ActiveValue::Set(deleted_at),
+        updated_by: ActiveValue::Set(*actor.get_user_id()),
+        ..Default::default()
+    };
+
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let updated_product = match updated_product.update(&transaction).await {
+        Ok(model) => model,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    service_audit::register_audit(
+        &transaction,
+        &actor,
+        AuditEntity::Product,
+        product.id,
+        action,
+        service_audit::snapshot(&product),
+        service_audit::snapshot(&updated_product),
+    )
+    .await?;
+
+    match transaction.commit().await {
+        Ok(_) => {
+            let event_type = match updated_product.deleted_at {
+                Some(_) => StockEventType::ProductDeleted,
+                None => StockEventType::ProductUpdated,
+            };
+
+            service_event::publish_product(event_type, &updated_product);
+
+            Ok(())
+        }
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+async fn apply_movement<C: ConnectionTrait>(
+    connection: &C,
+    product_change_quantity_dto: ProductChangeQuantityDTO,
+    actor: &ActorDTO,
+) -> Result<(), BackendError> {
+    if *product_change_quantity_dto.get_change_type()
+        && product_change_quantity_dto.get_reservation_id().is_some()
+    {
+        return Err(BackendError::InvalidValuesError);
+    }
+
+    if let Some(document) = product_change_quantity_dto.get_document()
+        && document.chars().count() > 100
+    {
+        return Err(BackendError::InvalidValuesError);
+    }
+
+    apply_quantity_change(
+        connection,
+        *product_change_quantity_dto.get_id(),
+        *product_change_quantity_dto.get_change_type(),
+        *product_change_quantity_dto.get_quantity(),
+    )
+    .await?;
+
+    if !*product_change_quantity_dto.get_change_type() {
+        if let Some(reservation_id) = product_change_quantity_dto.get_reservation_id() {
+            service_reservation::consume_reservation(
+                connection,
+                *reservation_id,
+                *product_change_quantity_dto.get_id(),
+                *product_change_quantity_dto.get_quantity(),
+            )
+            .await?;
+        }
+
+        service_reservation::verify_reserved_stock(
+            connection,
+            *product_change_quantity_dto.get_id(),
+        )
+        .await?;
+    }
+
+    service_report::create_report(connection, product_change_quantity_dto, actor).await
+}
+
+async fn apply_movement_in_savepoint(
+    transaction: &DatabaseTransaction,
+    product_change_quantity_dto: ProductChangeQuantityDTO,
+    actor: &ActorDTO,
+) -> Result<(), BackendError> {
+    let savepoint = match transaction.begin().await {
+        Ok(savepoint) => savepoint,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    if let Err(backend_error) = apply_movement(&savepoint, product_change_quantity_dto, actor).await
+    {
+        return match savepoint.rollback().await {
+            Ok(_) => Err(backend_error),
+            Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+        };
+    }
+
+    match savepoint.commit().await {
+        Ok(_) => Ok(()),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn find_all(
+    database: &DatabaseConnection,
+    include_deleted: bool,
+    category_id: Option<u64>,
+    abc_class: Option<AbcClass>,
+) -> Result<Vec<Model>, BackendError> {
+    let mut query = tb_product::Entity::find();
+
+    if !include_deleted {
+        query = query.filter(tb_product::Column::DeletedAt.is_null());
+    }
+
+    if let Some(category_id) = category_id {
+        query = query.filter(tb_product::Column::CategoryId.eq(category_id));
+    }
+
+    if let Some(abc_class) = abc_class {
+        query = query.filter(tb_product::Column::AbcClass.eq(abc_class.as_str()));
+    }
+
+    let result = query
+        .order_by_asc(tb_product::Column::Name)
+        .all(database)
+        .await;
+
+    match result {
+        Ok(products) => Ok(products),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn find_available_by_ids(
+    database: &DatabaseConnection,
+    ids: &[u64],
+) -> Result<HashMap<u64, u64>, BackendError> {
+    if ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let stmt = Statement::from_sql_and_values(
+        DbBackend::MySql,
+        format!(
+            r#"
+            SELECT
+                tb_product.id,
+                {AVAILABLE_QUANTITY}
+            FROM tb_product
+            {RESERVED_QUANTITY_JOIN}
+            WHERE tb_product.id IN ({})
+        "#,
+            vec!["?"; ids.len()].join(", ")
+        ),
+        ids.iter().map(|id| (*id).into()).collect::<Vec<Value>>(),
+    );
+
+    let result = ProductAvailableDTO::find_by_statement(stmt)
+        .all(database)
+        .await;
+
+    match result {
+        Ok(products) => Ok(products
+            .into_iter()
+            .map(|product| (*product.get_id(), *product.get_available()))
+            .collect()),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+fn all_products_statement(
+    include_deleted: bool,
+    abc_class: Option<AbcClass>,
+    sort_dto: &SortDTO,
+) -> Statement {
+    let mut conditions = Vec::new();
+
+    if !include_deleted {
+        conditions.push("tb_product.deleted_at IS NULL".to_string());
+    }
+
+    if let Some(abc_class) = abc_class {
+        conditions.push(format!("tb_product.abc_class = '{}'", abc_class.as_str()));
+    }
+
+    let filter = match conditions.is_empty() {
+        true => String::new(),
+        false => format!("WHERE {}", conditions.join(" AND ")),
+    };
+
+    Statement::from_string(
+        DbBackend::MySql,
+        format!(
+            r#"
+            SELECT
+                tb_product.id,
+                tb_product.name,
+                tb_product.barcode,
+                tb_product.quantity,
+                {AVAILABLE_QUANTITY},
+                tb_product.min_quantity,
+                tb_category.name as category,
+                tb_product.description,
+                tb_product.abc_class,
+                tb_product.version,
+                {},
+                CAST(tb_product.deleted_at AS CHAR) AS deleted_at
+            FROM tb_product
+            JOIN tb_category
+                ON tb_category.id = tb_product.category_id
+            {RESERVED_QUANTITY_JOIN}
+            {}
+            {filter}
+            {}
+        "#,
+            service_user::authorship_columns("tb_product"),
+            service_user::authorship_join("tb_product"),
+            service_user::authorship_order_by("tb_product", sort_dto)
+        ),
+    )
+}
+
 async fn find_product_by_id(database: &DatabaseConnection, id: u64) -> Result<Model, BackendError> {
     let result = tb_product::Entity::find_by_id(id).one(database).await;
 
@@ -231,44 +945,42 @@ async fn find_product_by_id(database: &DatabaseConnection, id: u64) -> Result<Mo
     }
 }
 
-async fn find_by_name(database: &DatabaseConnection, name: &str) -> Result<Model, BackendError> {
+pub async fn find_by_barcode(
+    database: &DatabaseConnection,
+    barcode: &str,
+) -> Result<Model, BackendError> {
     let result = tb_product::Entity::find()
-        .filter(tb_product::Column::Name.eq(name))
+        .filter(tb_product::Column::Barcode.eq(barcode))
         .one(database)
         .await;
 
     match result {
-        Ok(model_opt) => match model_opt {
-            Some(model) => Ok(model),
-            None => Err(BackendError::ResourceNotFoundError),
-        },
+        Ok(Some(model)) => Ok(model),
+        Ok(None) => Err(BackendError::ResourceNotFoundError),
         Err(db_err) => Err(BackendError::DatabaseError(db_err)),
     }
 }
 
-async fn exists_by_id(database: &DatabaseConnection, id: &u64) -> Result<bool, DbErr> {
-    let stmt = Statement::from_string(
-        DbBackend::MySql,
-        format!(
-            "
-            SELECT
-                EXISTS(
-                    SELECT 1
-                    FROM tb_product
-                    WHERE tb_product.id = (\"{id}\")
-                ) AS 'exist'
-        "
-        ),
-    );
+fn normalize_barcode(barcode: &Option<String>) -> Result<Option<String>, BackendError> {
+    match barcode.as_deref().map(str::trim) {
+        Some(barcode) if barcode.chars().count() > 100 => Err(BackendError::InvalidValuesError),
+        Some(barcode) if !barcode.is_empty() => Ok(Some(barcode.to_string())),
+        _ => Ok(None),
+    }
+}
 
-    let result = ExistsDTO::find_by_statement(stmt).one(database).await;
+async fn find_by_name(database: &DatabaseConnection, name: &str) -> Result<Model, BackendError> {
+    let result = tb_product::Entity::find()
+        .filter(tb_product::Column::Name.eq(name))
+        .one(database)
+        .await;
 
     match result {
-        Ok(exists_opt) => match exists_opt {
-            Some(exists_dto) => Ok(exists_dto.get_into_exist()),
-            None => Err(DbErr::RecordNotInserted),
+        Ok(model_opt) => match model_opt {
+            Some(model) => Ok(model),
+            None => Err(BackendError::ResourceNotFoundError),
         },
-        Err(db_err) => Err(db_err),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
     }
 }
 
@@ -298,50 +1010,53 @@ async fn exists_by_name(database: &DatabaseConnection, name: &str) -> Result<boo
     }
 }
 
-fn create_update_active_model(product_update_dto: ProductUpdateDTO) -> ActiveModel {
-    let active_model = ActiveModel {
-        id: ActiveValue::Set(product_update_dto.get_id().clone()),
-        name: match product_update_dto.get_name().trim().is_empty() {
-            true => ActiveValue::NotSet,
-            false => ActiveValue::Set(product_update_dto.get_name().clone()),
+fn create_update_active_model(
+    id: u64,
+    product_patch_dto: ProductPatchDTO,
+    barcode: ActiveValue<Option<String>>,
+    version: u64,
+    actor: &ActorDTO,
+) -> ActiveModel {
+    ActiveModel {
+        id: ActiveValue::Set(id),
+        name: match product_patch_dto.get_name() {
+            Some(name) => ActiveValue::Set(name.clone()),
+            None => ActiveValue::NotSet,
         },
-        category_id: match product_update_dto.get_category_id() {
-            &0 => ActiveValue::NotSet,
-            _ => ActiveValue::Set(*product_update_dto.get_category_id()),
+        barcode,
+        category_id: match product_patch_dto.get_category_id() {
+            Some(category_id) => ActiveValue::Set(*category_id),
+            None => ActiveValue::NotSet,
         },
-        min_quantity: match product_update_dto.get_min_quantity() {
-            &0 => ActiveValue::NotSet,
-            _ => ActiveValue::Set(*product_update_dto.get_min_quantity()),
+        min_quantity: match product_patch_dto.get_min_quantity() {
+            Some(min_quantity) => ActiveValue::Set(*min_quantity),
+            None => ActiveValue::NotSet,
         },
-        description: match product_update_dto.get_description() {
-            Some(description) => match description.trim().is_empty() {
-                true => ActiveValue::NotSet,
-                false => ActiveValue::Set(Some(description.clone())),
-            },
+        description: match product_patch_dto.get_description() {
+            Some(description) => ActiveValue::Set(description.clone()),
             None => ActiveValue::NotSet,
         },
+        version: ActiveValue::Set(version + 1),
+        updated_by: ActiveValue::Set(*actor.get_user_id()),
         ..Default::default()
-    };
-
-    active_model
+    }
 }
 
 fn create_update_change_active_model(
-    product_change_quantity_dto: &ProductChangeQuantityDTO,
-    product: Model,
+    product: &Model,
+    change_type: bool,
+    quantity: u64,
 ) -> Result<ActiveModel, BackendError> {
     let active_model = ActiveModel {
-        id: ActiveValue::Set(product_change_quantity_dto.get_id().clone()),
-        quantity: match product_change_quantity_dto.get_change_type() {
-            &true => {
-                ActiveValue::Set(product.quantity + product_change_quantity_dto.get_quantity())
-            }
-            &false => {
-                if product.quantity < *product_change_quantity_dto.get_quantity() {
+        id: ActiveValue::Set(product.id),
+        quantity: match change_type {
+            true => ActiveValue::Set(product.quantity + quantity),
+            false => {
+                if product.quantity < quantity {
                     return Err(BackendError::NotEnoughtResourcers);
                 }
 
-                ActiveValue::Set(product.quantity - product_change_quantity_dto.get_quantity())
+                ActiveValue::Set(product.quantity - quantity)
             }
         },
         ..Default::default()
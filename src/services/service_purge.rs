@@ -0,0 +1,35 @@
+use chrono::{Duration, Utc};
+use sea_orm::DatabaseConnection;
+
+use crate::{
+    configs::config_environment::get_soft_delete_retention_days,
+    errors::BackendError,
+    services::{service_category, service_product, service_reason},
+};
+
+// Each purge runs on its own, so a failing entity does not hold back the others
+pub async fn purge_deleted_entities(database: &DatabaseConnection) {
+    let deleted_before =
+        Utc::now().naive_utc() - Duration::days(get_soft_delete_retention_days() as i64);
+
+    if let Err(backend_error) = service_product::purge_deleted(database, deleted_before).await {
+        log_purge_failure("produtos", backend_error);
+    }
+
+    if let Err(backend_error) = service_category::purge_deleted(database, deleted_before).await {
+        log_purge_failure("categorias", backend_error);
+    }
+
+    if let Err(backend_error) = service_reason::purge_deleted(database, deleted_before).await {
+        log_purge_failure("motivos", backend_error);
+    }
+}
+
+fn log_purge_failure(entity: &str, backend_error: BackendError) {
+    match backend_error {
+        BackendError::DatabaseError(db_err) => {
+            eprintln!("Falha ao remover {entity} excluídos: {db_err}")
+        }
+        _ => eprintln!("Falha ao remover {entity} excluídos"),
+    }
+}
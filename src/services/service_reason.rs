@@ -1,27 +1,76 @@
+use chrono::{NaiveDateTime, Utc};
 use sea_orm::{
-    ActiveValue, ColumnTrait, DatabaseConnection, DbBackend, DbErr, EntityTrait, FromQueryResult,
-    QueryFilter, Statement,
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, DbBackend, DbErr, EntityTrait,
+    FromQueryResult, QueryFilter, Statement, TransactionTrait, sea_query::Query,
 };
 
 use crate::{
     entities::{
         dtos::{
-            generic_dtos::ExistsDTO,
+            generic_dtos::{ActorDTO, ExistsDTO, SortDTO},
             reason_dtos::{ReasonCreateDTO, ReasonDTO},
         },
+        enums::audit_enums::{AuditAction, AuditEntity},
         tb_reason::{self, ActiveModel, Model},
+        tb_report,
     },
     errors::BackendError,
+    guards::guard_user::AuthenticationGuard,
+    services::{service_audit, service_user},
 };
 
-pub async fn get_all_reason(database: &DatabaseConnection) -> Result<Vec<ReasonDTO>, BackendError> {
-    let result = tb_reason::Entity::find().all(database).await;
+pub async fn get_all_reason(
+    database: &DatabaseConnection,
+    include_deleted: bool,
+    sort_dto: &SortDTO,
+) -> Result<Vec<ReasonDTO>, BackendError> {
+    let filter = match include_deleted {
+        true => String::new(),
+        false => "WHERE tb_reason.deleted_at IS NULL".to_string(),
+    };
+
+    let stmt = Statement::from_string(
+        DbBackend::MySql,
+        format!(
+            r#"
+            SELECT
+                tb_reason.id,
+                tb_reason.name,
+                {},
+                CAST(tb_reason.deleted_at AS CHAR) AS deleted_at
+            FROM tb_reason
+            {}
+            {filter}
+            {}
+        "#,
+            service_user::authorship_columns("tb_reason"),
+            service_user::authorship_join("tb_reason"),
+            service_user::authorship_order_by("tb_reason", sort_dto)
+        ),
+    );
+
+    let result = ReasonDTO::find_by_statement(stmt).all(database).await;
+
+    match result {
+        Ok(reasons) => Ok(reasons),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn find_all(
+    database: &DatabaseConnection,
+    include_deleted: bool,
+) -> Result<Vec<Model>, BackendError> {
+    let mut query = tb_reason::Entity::find();
+
+    if !include_deleted {
+        query = query.filter(tb_reason::Column::DeletedAt.is_null());
+    }
+
+    let result = query.all(database).await;
 
     match result {
-        Ok(reasons) => Ok(reasons
-            .into_iter()
-            .map(|model| ReasonDTO::new(model.id, model.name))
-            .collect()),
+        Ok(reasons) => Ok(reasons),
         Err(db_err) => Err(BackendError::DatabaseError(db_err)),
     }
 }
@@ -29,7 +78,13 @@ pub async fn get_all_reason(database: &DatabaseConnection) -> Result<Vec<ReasonD
 pub async fn create_reason(
     database: &DatabaseConnection,
     reason_create_dto: ReasonCreateDTO,
+    authentication: AuthenticationGuard,
 ) -> Result<(), BackendError> {
+    let actor = match service_user::find_actor(database, &authentication).await {
+        Ok(actor) => actor,
+        Err(backend_error) => return Err(backend_error),
+    };
+
     match exists_by_name(database, reason_create_dto.get_name()).await {
         Ok(boolean) => {
             if boolean {
@@ -41,12 +96,34 @@ pub async fn create_reason(
 
     let reason = ActiveModel {
         name: sea_orm::ActiveValue::Set(reason_create_dto.get_name().clone()),
+        updated_by: ActiveValue::Set(*actor.get_user_id()),
         ..Default::default()
     };
 
-    let result = tb_reason::Entity::insert(reason).exec(database).await;
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
 
-    match result {
+    let result = reason.insert(&transaction).await;
+
+    let reason = match result {
+        Ok(model) => model,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    service_audit::register_audit(
+        &transaction,
+        &actor,
+        AuditEntity::Reason,
+        reason.id,
+        AuditAction::Create,
+        None,
+        service_audit::snapshot(&reason),
+    )
+    .await?;
+
+    match transaction.commit().await {
         Ok(_) => Ok(()),
         Err(db_err) => Err(BackendError::DatabaseError(db_err)),
     }
@@ -55,51 +132,218 @@ pub async fn create_reason(
 pub async fn update_reason(
     database: &DatabaseConnection,
     reason_update_dto: ReasonDTO,
+    authentication: AuthenticationGuard,
 ) -> Result<(), BackendError> {
-    match exists_by_id(database, reason_update_dto.get_id()).await {
-        Ok(boolean) => {
-            if !boolean {
-                return Err(BackendError::ResourceNotFoundError);
-            }
-        }
-        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    let actor = match service_user::find_actor(database, &authentication).await {
+        Ok(actor) => actor,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let old_reason = match find_by_id(database, *reason_update_dto.get_id()).await {
+        Ok(model) => model,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    if old_reason.deleted_at.is_some() {
+        return Err(BackendError::ResourceNotFoundError);
     }
 
-    if let Ok(old_reason) = find_by_name(database, reason_update_dto.get_name()).await {
-        if &old_reason.id != reason_update_dto.get_id() {
-            return Err(BackendError::ResourceConflitUpdateError);
-        }
+    if let Ok(reason) = find_by_name(database, reason_update_dto.get_name()).await
+        && &reason.id != reason_update_dto.get_id()
+    {
+        return Err(BackendError::ResourceConflitUpdateError);
     }
 
-    let reason = create_update_active_model(reason_update_dto);
+    let reason = create_update_active_model(reason_update_dto, &actor);
 
-    let result = tb_reason::Entity::update(reason).exec(database).await;
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
 
-    match result {
+    let reason = match reason.update(&transaction).await {
+        Ok(model) => model,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    service_audit::register_audit(
+        &transaction,
+        &actor,
+        AuditEntity::Reason,
+        reason.id,
+        AuditAction::Update,
+        service_audit::snapshot(&old_reason),
+        service_audit::snapshot(&reason),
+    )
+    .await?;
+
+    match transaction.commit().await {
         Ok(_) => Ok(()),
         Err(db_err) => Err(BackendError::DatabaseError(db_err)),
     }
 }
 
-pub async fn delete_by_id(database: &DatabaseConnection, id: u64) -> Result<(), BackendError> {
-    match exists_by_id(database, &id).await {
-        Ok(boolean) => {
-            if !boolean {
-                return Err(BackendError::ResourceNotFoundError);
-            }
+pub async fn delete_by_id(
+    database: &DatabaseConnection,
+    id: u64,
+    authentication: AuthenticationGuard,
+) -> Result<(), BackendError> {
+    let actor = match service_user::find_actor(database, &authentication).await {
+        Ok(actor) => actor,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let reason = match find_by_id(database, id).await {
+        Ok(model) => model,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    if reason.deleted_at.is_some() {
+        return Err(BackendError::ResourceNotFoundError);
+    }
+
+    update_deleted_at(
+        database,
+        reason,
+        Some(Utc::now().naive_utc()),
+        actor,
+        AuditAction::Delete,
+    )
+    .await
+}
+
+pub async fn restore_by_id(
+    database: &DatabaseConnection,
+    id: u64,
+    authentication: AuthenticationGuard,
+) -> Result<(), BackendError> {
+    let actor = match service_user::find_actor(database, &authentication).await {
+        Ok(actor) => actor,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let reason = match find_by_id(database, id).await {
+        Ok(model) => model,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    if reason.deleted_at.is_none() {
+        return Ok(());
+    }
+
+    update_deleted_at(database, reason, None, actor, AuditAction::Restore).await
+}
+
+pub async fn purge_deleted(
+    database: &DatabaseConnection,
+    deleted_before: NaiveDateTime,
+) -> Result<(), BackendError> {
+    let result = tb_reason::Entity::find()
+        .filter(tb_reason::Column::DeletedAt.lt(deleted_before))
+        .filter(
+            tb_reason::Column::Id.not_in_subquery(
+                Query::select()
+                    .column(tb_report::Column::ReasonId)
+                    .from(tb_report::Entity)
+                    .to_owned(),
+            ),
+        )
+        .all(database)
+        .await;
+
+    let reasons = match result {
+        Ok(reasons) => reasons,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    for reason in reasons {
+        if let Err(db_err) = tb_reason::Entity::delete_by_id(reason.id)
+            .exec(&transaction)
+            .await
+        {
+            return Err(BackendError::DatabaseError(db_err));
         }
+
+        service_audit::register_audit(
+            &transaction,
+            &ActorDTO::new(None, None),
+            AuditEntity::Reason,
+            reason.id,
+            AuditAction::Purge,
+            service_audit::snapshot(&reason),
+            None,
+        )
+        .await?;
+    }
+
+    match transaction.commit().await {
+        Ok(_) => Ok(()),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+async fn update_deleted_at(
+    database: &DatabaseConnection,
+    reason: Model,
+    deleted_at: Option<NaiveDateTime>,
+    actor: ActorDTO,
+    action: AuditAction,
+) -> Result<(), BackendError> {
+    let updated_reason = ActiveModel {
+        id: ActiveValue::Set(reason.id),
+        deleted_at: ActiveValue::Set(deleted_at),
+        updated_by: ActiveValue::Set(*actor.get_user_id()),
+        ..Default::default()
+    };
+
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let updated_reason = match updated_reason.update(&transaction).await {
+        Ok(model) => model,
         Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    service_audit::register_audit(
+        &transaction,
+        &actor,
+        AuditEntity::Reason,
+        reason.id,
+        action,
+        service_audit::snapshot(&reason),
+        service_audit::snapshot(&updated_reason),
+    )
+    .await?;
+
+    match transaction.commit().await {
+        Ok(_) => Ok(()),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
     }
+}
 
-    let result = tb_reason::Entity::delete_by_id(id).exec(database).await;
+async fn find_by_id(database: &DatabaseConnection, id: u64) -> Result<Model, BackendError> {
+    let result = tb_reason::Entity::find_by_id(id).one(database).await;
 
     match result {
-        Ok(_) => Ok(()),
+        Ok(model_opt) => match model_opt {
+            Some(model) => Ok(model),
+            None => Err(BackendError::ResourceNotFoundError),
+        },
         Err(db_err) => Err(BackendError::DatabaseError(db_err)),
     }
 }
 
-async fn find_by_name(database: &DatabaseConnection, name: &str) -> Result<Model, BackendError> {
+pub async fn find_by_name(
+    database: &DatabaseConnection,
+    name: &str,
+) -> Result<Model, BackendError> {
     let result = tb_reason::Entity::find()
         .filter(tb_reason::Column::Name.eq(name))
         .one(database)
@@ -124,6 +368,7 @@ pub async fn exists_by_id(database: &DatabaseConnection, id: &u64) -> Result<boo
                     SELECT 1
                     FROM tb_reason
                     WHERE tb_reason.id = (\"{id}\")
+                        AND tb_reason.deleted_at IS NULL
                 ) AS 'exist'
         "
         ),
@@ -166,14 +411,14 @@ async fn exists_by_name(database: &DatabaseConnection, name: &str) -> Result<boo
     }
 }
 
-fn create_update_active_model(reason_update_dto: ReasonDTO) -> ActiveModel {
-    let active_model = ActiveModel {
+fn create_update_active_model(reason_update_dto: ReasonDTO, actor: &ActorDTO) -> ActiveModel {
+    ActiveModel {
         id: ActiveValue::Set(*reason_update_dto.get_id()),
         name: match reason_update_dto.get_name().trim().is_empty() {
             true => ActiveValue::NotSet,
             false => ActiveValue::Set(reason_update_dto.get_name().clone()),
         },
-    };
-
-    active_model
+        updated_by: ActiveValue::Set(*actor.get_user_id()),
+        ..Default::default()
+    }
 }
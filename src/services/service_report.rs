@@ -1,77 +1,303 @@
-use chrono::{Local, NaiveDateTime, TimeZone, Utc};
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use rocket::futures::StreamExt;
 use sea_orm::{
-    ActiveValue, DatabaseConnection, DbBackend, EntityTrait, FromQueryResult, Statement,
+    ActiveValue, ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait,
+    FromQueryResult, QueryFilter, QueryOrder, QuerySelect, Statement, TransactionTrait, Value,
 };
 
 use crate::{
     entities::{
-        dtos::{product_dtos::ProductChangeQuantityDTO, report_dtos::{ReportUpdateDTO, ReportViewDTO}},
-        tb_report::{self, ActiveModel},
+        dtos::{
+            event_dtos::MovementEventDTO,
+            generic_dtos::ActorDTO,
+            product_dtos::ProductChangeQuantityDTO,
+            report_dtos::{
+                OpeningBalanceDTO, ProductStatementFilterDTO, ProductStatementLineDTO,
+                ReportIntegrityDTO, ReportReversalDTO, ReportViewDTO, StockPositionDTO,
+            },
+        },
+        enums::export_enums::{ExportFormat, ExportLanguage},
+        tb_category, tb_product,
+        tb_report::{self, ActiveModel, Model},
+    },
+    errors::BackendError,
+    guards::guard_user::AuthenticationGuard,
+    services::{
+        service_event,
+        service_export::{self, ExportFile},
+        service_pdf, service_product, service_reason, service_reservation, service_user,
     },
-    errors::BackendError, services::service_reason,
 };
 
 pub async fn get_all_reports(
     database: &DatabaseConnection,
 ) -> Result<Vec<ReportViewDTO>, BackendError> {
+    let result = ReportViewDTO::find_by_statement(all_reports_statement())
+        .all(database)
+        .await;
+
+    match result {
+        Ok(mut reports) => {
+            reports.iter_mut().for_each(localize_date);
+
+            Ok(reports)
+        }
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn export_reports(
+    database: &DatabaseConnection,
+    format: ExportFormat,
+    language: ExportLanguage,
+) -> Result<ExportFile<'_>, BackendError> {
+    let result = ReportViewDTO::find_by_statement(all_reports_statement())
+        .stream(database)
+        .await;
+
+    match result {
+        Ok(reports) => {
+            let reports = reports.map(|report| {
+                report.map(|mut report| {
+                    localize_date(&mut report);
+
+                    report
+                })
+            });
+
+            service_export::export_rows(reports, "reports", format, language).await
+        }
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn get_integrity_report(
+    database: &DatabaseConnection,
+) -> Result<Vec<ReportIntegrityDTO>, BackendError> {
     let stmt = Statement::from_string(
         DbBackend::MySql,
         r#"
             SELECT
-                tb_report.id,
-                tb_report.change_type,
-                tb_report.quantity,
+                tb_product.id AS product_id,
                 tb_product.name AS product,
-                tb_reason.name AS reason,
-	            CAST(
-                    tb_report.date AS CHAR
-                ) AS date
-            FROM tb_report
-            JOIN tb_product
-                ON tb_product.id = tb_report.product_id 
-            JOIN tb_reason
-                ON tb_reason.id = tb_report.reason_id;
+                tb_product.quantity,
+                CAST(
+                    COALESCE(
+                        SUM(
+                            CASE tb_report.change_type
+                                WHEN 1 THEN CAST(tb_report.quantity AS SIGNED)
+                                ELSE -CAST(tb_report.quantity AS SIGNED)
+                            END
+                        ),
+                        0
+                    ) AS SIGNED
+                ) AS ledger_quantity
+            FROM tb_product
+            LEFT JOIN tb_report
+                ON tb_report.product_id = tb_product.id
+            GROUP BY tb_product.id, tb_product.name, tb_product.quantity
+            HAVING ledger_quantity <> tb_product.quantity
+            ORDER BY tb_product.id
         "#,
     );
 
-    let result = ReportViewDTO::find_by_statement(stmt).all(database).await;
+    let result = ReportIntegrityDTO::find_by_statement(stmt)
+        .all(database)
+        .await;
 
     match result {
-        Ok(mut reports) => {
-            reports.iter_mut().for_each(|report| {
-                let utc_string = report.get_date().to_string();
+        Ok(mismatches) => Ok(mismatches),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn generate_stock_position_pdf(
+    database: &DatabaseConnection,
+    authentication: AuthenticationGuard,
+) -> Result<Vec<u8>, BackendError> {
+    let generated_by = match service_user::find_actor_name(database, &authentication).await {
+        Ok(name) => name,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let stmt = Statement::from_string(
+        DbBackend::MySql,
+        r#"
+            SELECT
+                tb_category.name AS category,
+                tb_product.name AS product,
+                tb_product.quantity,
+                tb_product.min_quantity
+            FROM tb_product
+            JOIN tb_category
+                ON tb_category.id = tb_product.category_id
+            WHERE tb_product.deleted_at IS NULL
+            ORDER BY tb_category.name, tb_product.name
+        "#,
+    );
+
+    let positions = match StockPositionDTO::find_by_statement(stmt)
+        .all(database)
+        .await
+    {
+        Ok(positions) => positions,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
 
-                let naive =
-                    NaiveDateTime::parse_from_str(&utc_string, "%Y-%m-%d %H:%M:%S").unwrap();
+    service_pdf::render_stock_position(
+        &positions,
+        &Local::now().format("%d/%m/%Y %H:%M").to_string(),
+        &generated_by,
+    )
+}
 
-                let utc = Utc.from_utc_datetime(&naive);
+pub async fn generate_product_statement_pdf(
+    database: &DatabaseConnection,
+    product_id: u64,
+    product_statement_filter_dto: ProductStatementFilterDTO,
+    authentication: AuthenticationGuard,
+) -> Result<Vec<u8>, BackendError> {
+    let generated_by = match service_user::find_actor_name(database, &authentication).await {
+        Ok(name) => name,
+        Err(backend_error) => return Err(backend_error),
+    };
 
-                let local = utc.with_timezone(&Local);
+    let start_date = parse_date(product_statement_filter_dto.get_start_date())?;
+    let end_date = parse_date(product_statement_filter_dto.get_end_date())?;
 
-                let naive = local.naive_local();
+    if let (Some(start_date), Some(end_date)) = (start_date, end_date)
+        && start_date > end_date
+    {
+        return Err(BackendError::InvalidValuesError);
+    }
 
-                report.set_date(naive.to_string());
-            });
+    let product = match tb_product::Entity::find_by_id(product_id)
+        .one(database)
+        .await
+    {
+        Ok(Some(model)) => model,
+        Ok(None) => return Err(BackendError::ResourceNotFoundError),
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
 
-            Ok(reports)
+    let category = match tb_category::Entity::find_by_id(product.category_id)
+        .one(database)
+        .await
+    {
+        Ok(Some(model)) => model,
+        Ok(None) => return Err(BackendError::ResourceNotFoundError),
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let mut conditions = vec!["tb_report.product_id = ?"];
+    let mut values: Vec<Value> = vec![product_id.into()];
+
+    let opening_balance = match start_date {
+        Some(start_date) => {
+            let start = match local_day_start_to_utc(start_date) {
+                Some(start) => start,
+                None => return Err(BackendError::InvalidValuesError),
+            };
+
+            conditions.push("tb_report.date >= ?");
+            values.push(start.into());
+
+            find_balance_before(database, product_id, start).await?
         }
-        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+        None => 0,
+    };
+
+    if let Some(end_date) = end_date {
+        let end = match local_day_start_to_utc(end_date + Duration::days(1)) {
+            Some(end) => end,
+            None => return Err(BackendError::InvalidValuesError),
+        };
+
+        conditions.push("tb_report.date < ?");
+        values.push(end.into());
     }
+
+    let stmt = Statement::from_sql_and_values(
+        DbBackend::MySql,
+        format!(
+            r#"
+            SELECT
+                CAST(tb_report.date AS CHAR) AS date,
+                tb_report.change_type,
+                tb_report.quantity,
+                tb_reason.name AS reason,
+                tb_report.document,
+                tb_user.username AS user,
+                tb_api_key.name AS api_key
+            FROM tb_report
+            JOIN tb_reason
+                ON tb_reason.id = tb_report.reason_id
+            LEFT JOIN tb_user
+                ON tb_user.id = tb_report.user_id
+            LEFT JOIN tb_api_key
+                ON tb_api_key.id = tb_report.api_key_id
+            WHERE {}
+            ORDER BY tb_report.date, tb_report.id
+        "#,
+            conditions.join(" AND ")
+        ),
+        values,
+    );
+
+    let mut lines = match ProductStatementLineDTO::find_by_statement(stmt)
+        .all(database)
+        .await
+    {
+        Ok(lines) => lines,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    lines.iter_mut().for_each(|line| {
+        let date = to_local_date(line.get_date());
+
+        line.set_date(date);
+    });
+
+    let period = format!(
+        "{} a {}",
+        match start_date {
+            Some(start_date) => start_date.format("%d/%m/%Y").to_string(),
+            None => "primeiro registro".to_string(),
+        },
+        match end_date {
+            Some(end_date) => end_date.format("%d/%m/%Y").to_string(),
+            None => "hoje".to_string(),
+        }
+    );
+
+    service_pdf::render_product_statement(
+        &product.name,
+        &category.name,
+        &period,
+        opening_balance,
+        &lines,
+        &Local::now().format("%d/%m/%Y %H:%M").to_string(),
+        &generated_by,
+    )
 }
 
-pub async fn create_report(
-    database: &DatabaseConnection,
+pub async fn create_report<C: ConnectionTrait>(
+    database: &C,
     product_change_quantity_dto: ProductChangeQuantityDTO,
+    actor: &ActorDTO,
 ) -> Result<(), BackendError> {
     let report = ActiveModel {
         product_id: ActiveValue::Set(*product_change_quantity_dto.get_id()),
         reason_id: ActiveValue::Set(*product_change_quantity_dto.get_reason_id()),
-        change_type: ActiveValue::Set(match product_change_quantity_dto.get_change_type() {
-            &true => 1,
-            &false => 0,
+        change_type: ActiveValue::Set(match *product_change_quantity_dto.get_change_type() {
+            true => 1,
+            false => 0,
         }),
         quantity: ActiveValue::Set(*product_change_quantity_dto.get_quantity()),
         date: ActiveValue::Set(Utc::now().naive_local()),
+        user_id: ActiveValue::Set(*actor.get_user_id()),
+        api_key_id: ActiveValue::Set(*actor.get_api_key_id()),
+        document: ActiveValue::Set(product_change_quantity_dto.get_document().clone()),
         ..Default::default()
     };
 
@@ -83,40 +309,296 @@ pub async fn create_report(
     }
 }
 
-pub async fn update_report(
+pub async fn reverse_report(
     database: &DatabaseConnection,
-    report_update_dto: ReportUpdateDTO
+    id: u64,
+    report_reversal_dto: ReportReversalDTO,
+    authentication: AuthenticationGuard,
 ) -> Result<(), BackendError> {
+    let actor = match service_user::find_actor(database, &authentication).await {
+        Ok(actor) => actor,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let report = match find_by_id(database, id).await {
+        Ok(model) => model,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    if report.reversal_of.is_some() {
+        return Err(BackendError::InvalidValuesError);
+    }
+
+    let reversal = tb_report::Entity::find()
+        .filter(tb_report::Column::ReversalOf.eq(id))
+        .one(database)
+        .await;
+
+    match reversal {
+        Ok(Some(_)) => return Err(BackendError::ResourceAlreadyInsertedError),
+        Ok(None) => {}
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    }
+
+    let reason_id = match report_reversal_dto.get_reason_id() {
+        Some(reason_id) => *reason_id,
+        None => report.reason_id,
+    };
 
-    match service_reason::exists_by_id(database, report_update_dto.get_reason_id()).await {
+    match service_reason::exists_by_id(database, &reason_id).await {
         Ok(exists) => {
             if !exists {
                 return Err(BackendError::ResourceNotFoundError);
             }
-        },
-        Err(db_err) => return Err(BackendError::DatabaseError(db_err))
+        }
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    }
+
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    service_product::apply_quantity_change(
+        &transaction,
+        report.product_id,
+        report.change_type == 0,
+        report.quantity,
+    )
+    .await?;
+
+    // Reversing an entry takes stock out, which must not eat into the reserved quantity
+    if report.change_type != 0 {
+        service_reservation::verify_reserved_stock(&transaction, report.product_id).await?;
+    }
+
+    let reversal_report = ActiveModel {
+        product_id: ActiveValue::Set(report.product_id),
+        reason_id: ActiveValue::Set(reason_id),
+        change_type: ActiveValue::Set(match report.change_type {
+            0 => 1,
+            _ => 0,
+        }),
+        quantity: ActiveValue::Set(report.quantity),
+        date: ActiveValue::Set(Utc::now().naive_local()),
+        user_id: ActiveValue::Set(*actor.get_user_id()),
+        api_key_id: ActiveValue::Set(*actor.get_api_key_id()),
+        reversal_of: ActiveValue::Set(Some(report.id)),
+        ..Default::default()
+    };
+
+    if let Err(db_err) = tb_report::Entity::insert(reversal_report)
+        .exec(&transaction)
+        .await
+    {
+        return Err(BackendError::DatabaseError(db_err));
     }
 
-    let report = create_update_active_model(report_update_dto);
+    match transaction.commit().await {
+        Ok(_) => {
+            service_event::publish_movements(
+                database,
+                vec![MovementEventDTO::new(
+                    report.product_id,
+                    report.change_type == 0,
+                    report.quantity,
+                )],
+            )
+            .await;
 
-    let result = tb_report::Entity::update(report).exec(database).await;
+            Ok(())
+        }
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn find_balance_before(
+    database: &DatabaseConnection,
+    product_id: u64,
+    date: NaiveDateTime,
+) -> Result<i64, BackendError> {
+    let stmt = Statement::from_sql_and_values(
+        DbBackend::MySql,
+        r#"
+            SELECT
+                CAST(
+                    SUM(
+                        CASE
+                            WHEN tb_report.change_type THEN CAST(tb_report.quantity AS SIGNED)
+                            ELSE -CAST(tb_report.quantity AS SIGNED)
+                        END
+                    ) AS SIGNED
+                ) AS balance
+            FROM tb_report
+            WHERE tb_report.product_id = ?
+                AND tb_report.date < ?
+        "#,
+        [product_id.into(), date.into()],
+    );
+
+    let result = OpeningBalanceDTO::find_by_statement(stmt)
+        .one(database)
+        .await;
 
     match result {
-        Ok(_) => Ok(()),
+        Ok(balance_opt) => Ok(match balance_opt {
+            Some(balance_dto) => balance_dto.get_into_balance(),
+            None => 0,
+        }),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn find_all(
+    database: &DatabaseConnection,
+    product_id: Option<u64>,
+    limit: u64,
+) -> Result<Vec<Model>, BackendError> {
+    let mut query = tb_report::Entity::find();
+
+    if let Some(product_id) = product_id {
+        query = query.filter(tb_report::Column::ProductId.eq(product_id));
+    }
+
+    let result = query
+        .order_by_desc(tb_report::Column::Date)
+        .order_by_desc(tb_report::Column::Id)
+        .limit(limit)
+        .all(database)
+        .await;
+
+    match result {
+        Ok(reports) => Ok(reports),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn find_recent_by_product_ids(
+    database: &DatabaseConnection,
+    product_ids: &[u64],
+    limit: u64,
+) -> Result<Vec<Model>, BackendError> {
+    if product_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut values: Vec<Value> = product_ids.iter().map(|id| (*id).into()).collect();
+    values.push(limit.into());
+
+    let stmt = Statement::from_sql_and_values(
+        DbBackend::MySql,
+        format!(
+            r#"
+            SELECT *
+            FROM (
+                SELECT
+                    tb_report.*,
+                    ROW_NUMBER() OVER (
+                        PARTITION BY tb_report.product_id
+                        ORDER BY tb_report.date DESC, tb_report.id DESC
+                    ) AS position
+                FROM tb_report
+                WHERE tb_report.product_id IN ({})
+            ) AS movements
+            WHERE movements.position <= ?
+            ORDER BY movements.date DESC, movements.id DESC
+        "#,
+            vec!["?"; product_ids.len()].join(", ")
+        ),
+        values,
+    );
+
+    let result = tb_report::Entity::find()
+        .from_raw_sql(stmt)
+        .all(database)
+        .await;
+
+    match result {
+        Ok(reports) => Ok(reports),
         Err(db_err) => Err(BackendError::DatabaseError(db_err)),
     }
+}
 
+pub fn format_local_date(date: &NaiveDateTime) -> String {
+    Utc.from_utc_datetime(date)
+        .with_timezone(&Local)
+        .naive_local()
+        .to_string()
 }
 
-fn create_update_active_model(report_update_dto: ReportUpdateDTO) -> ActiveModel {
-    let active_model = ActiveModel {
-        id: ActiveValue::Set(*report_update_dto.get_id()),
-        reason_id: match report_update_dto.get_reason_id() {
-            &0 => ActiveValue::NotSet,
-            _ => ActiveValue::Set(*report_update_dto.get_reason_id())
+fn all_reports_statement() -> Statement {
+    Statement::from_string(
+        DbBackend::MySql,
+        r#"
+            SELECT
+                tb_report.id,
+                tb_report.change_type,
+                tb_report.quantity,
+                tb_product.name AS product,
+                tb_reason.name AS reason,
+	            CAST(
+                    tb_report.date AS CHAR
+                ) AS date,
+                tb_user.username AS user,
+                tb_api_key.name AS api_key,
+                tb_report.reversal_of,
+                tb_report.document
+            FROM tb_report
+            JOIN tb_product
+                ON tb_product.id = tb_report.product_id 
+            JOIN tb_reason
+                ON tb_reason.id = tb_report.reason_id
+            LEFT JOIN tb_user
+                ON tb_user.id = tb_report.user_id
+            LEFT JOIN tb_api_key
+                ON tb_api_key.id = tb_report.api_key_id;
+        "#,
+    )
+}
+
+fn localize_date(report: &mut ReportViewDTO) {
+    let date = to_local_date(report.get_date());
+
+    report.set_date(date);
+}
+
+fn to_local_date(utc_string: &str) -> String {
+    let naive = NaiveDateTime::parse_from_str(utc_string, "%Y-%m-%d %H:%M:%S").unwrap();
+
+    let utc = Utc.from_utc_datetime(&naive);
+
+    let local = utc.with_timezone(&Local);
+
+    let naive = local.naive_local();
+
+    naive.to_string()
+}
+
+fn local_day_start_to_utc(date: NaiveDate) -> Option<NaiveDateTime> {
+    Local
+        .from_local_datetime(&date.and_hms_opt(0, 0, 0)?)
+        .earliest()
+        .map(|local| local.naive_utc())
+}
+
+fn parse_date(date: &Option<String>) -> Result<Option<NaiveDate>, BackendError> {
+    match date {
+        Some(date) => match NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d") {
+            Ok(date) => Ok(Some(date)),
+            Err(_) => Err(BackendError::InvalidValuesError),
         },
-        ..Default::default()
-    };
+        None => Ok(None),
+    }
+}
 
-    active_model
+async fn find_by_id(database: &DatabaseConnection, id: u64) -> Result<Model, BackendError> {
+    let result = tb_report::Entity::find_by_id(id).one(database).await;
+
+    match result {
+        Ok(model_opt) => match model_opt {
+            Some(model) => Ok(model),
+            None => Err(BackendError::ResourceNotFoundError),
+        },
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
 }
@@ -0,0 +1,356 @@
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ActiveValue, ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait,
+    FromQueryResult, QueryFilter, QuerySelect, Statement, TransactionTrait, Value, sea_query::Expr,
+};
+
+use crate::{
+    entities::{
+        dtos::reservation_dtos::{ReservationCreateDTO, ReservationViewDTO, ReservedQuantityDTO},
+        enums::{
+            audit_enums::{AuditAction, AuditEntity},
+            reservation_enums::ReservationStatus,
+        },
+        tb_product,
+        tb_reservation::{self, ActiveModel},
+    },
+    errors::BackendError,
+    guards::guard_user::AuthenticationGuard,
+    services::{service_audit, service_user},
+};
+
+pub const AVAILABLE_QUANTITY: &str = r#"
+    CAST(
+        GREATEST(CAST(tb_product.quantity AS SIGNED) - COALESCE(reservations.reserved, 0), 0)
+        AS UNSIGNED
+    ) AS available
+"#;
+
+pub const RESERVED_QUANTITY_JOIN: &str = r#"
+    LEFT JOIN (
+        SELECT
+            tb_reservation.product_id,
+            SUM(tb_reservation.quantity - tb_reservation.consumed_quantity) AS reserved
+        FROM tb_reservation
+        WHERE tb_reservation.status = 'active'
+            AND tb_reservation.expires_at > UTC_TIMESTAMP()
+        GROUP BY tb_reservation.product_id
+    ) AS reservations
+        ON reservations.product_id = tb_product.id
+"#;
+
+pub async fn get_active_reservations(
+    database: &DatabaseConnection,
+    product_id: Option<u64>,
+) -> Result<Vec<ReservationViewDTO>, BackendError> {
+    let mut values: Vec<Value> = vec![ReservationStatus::Active.as_str().into()];
+
+    let filter = match product_id {
+        Some(product_id) => {
+            values.push(product_id.into());
+            "AND tb_reservation.product_id = ?"
+        }
+        None => "",
+    };
+
+    let stmt = Statement::from_sql_and_values(
+        DbBackend::MySql,
+        format!(
+            r#"
+            SELECT
+                tb_reservation.id,
+                tb_reservation.product_id,
+                tb_product.name AS product,
+                tb_reservation.holder,
+                tb_reservation.quantity,
+                tb_reservation.consumed_quantity,
+                CAST(tb_reservation.expires_at AS CHAR) AS expires_at,
+                CAST(tb_reservation.created_at AS CHAR) AS created_at,
+                COALESCE(tb_user.username, tb_api_key.name) AS created_by
+            FROM tb_reservation
+            JOIN tb_product
+                ON tb_product.id = tb_reservation.product_id
+            LEFT JOIN tb_user
+                ON tb_user.id = tb_reservation.user_id
+            LEFT JOIN tb_api_key
+                ON tb_api_key.id = tb_reservation.api_key_id
+            WHERE tb_reservation.status = ?
+                AND tb_reservation.expires_at > UTC_TIMESTAMP()
+                {filter}
+            ORDER BY tb_reservation.expires_at
+        "#
+        ),
+        values,
+    );
+
+    let result = ReservationViewDTO::find_by_statement(stmt)
+        .all(database)
+        .await;
+
+    match result {
+        Ok(reservations) => Ok(reservations),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn create_reservation(
+    database: &DatabaseConnection,
+    reservation_create_dto: ReservationCreateDTO,
+    authentication: AuthenticationGuard,
+) -> Result<(), BackendError> {
+    let actor = match service_user::find_actor(database, &authentication).await {
+        Ok(actor) => actor,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let expires_at = match DateTime::parse_from_rfc3339(reservation_create_dto.get_expires_at()) {
+        Ok(expires_at) => expires_at.with_timezone(&Utc).naive_utc(),
+        Err(_) => return Err(BackendError::InvalidValuesError),
+    };
+
+    if reservation_create_dto.get_holder().trim().is_empty()
+        || *reservation_create_dto.get_quantity() == 0
+        || expires_at <= Utc::now().naive_utc()
+    {
+        return Err(BackendError::InvalidValuesError);
+    }
+
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let result = tb_product::Entity::find_by_id(*reservation_create_dto.get_product_id())
+        .lock_exclusive()
+        .one(&transaction)
+        .await;
+
+    let product = match result {
+        Ok(Some(model)) if model.deleted_at.is_none() => model,
+        Ok(_) => return Err(BackendError::ResourceNotFoundError),
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let reserved = match find_reserved_quantity(&transaction, product.id).await {
+        Ok(reserved) => reserved,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    if product.quantity.saturating_sub(reserved) < *reservation_create_dto.get_quantity() {
+        return Err(BackendError::NotEnoughtResourcers);
+    }
+
+    let reservation = ActiveModel {
+        product_id: ActiveValue::Set(product.id),
+        holder: ActiveValue::Set(reservation_create_dto.get_holder().trim().to_string()),
+        quantity: ActiveValue::Set(*reservation_create_dto.get_quantity()),
+        consumed_quantity: ActiveValue::Set(0),
+        status: ActiveValue::Set(ReservationStatus::Active.as_str().to_string()),
+        expires_at: ActiveValue::Set(expires_at),
+        created_at: ActiveValue::Set(Utc::now().naive_utc()),
+        user_id: ActiveValue::Set(*actor.get_user_id()),
+        api_key_id: ActiveValue::Set(*actor.get_api_key_id()),
+        ..Default::default()
+    };
+
+    if let Err(db_err) = tb_reservation::Entity::insert(reservation)
+        .exec(&transaction)
+        .await
+    {
+        return Err(BackendError::DatabaseError(db_err));
+    }
+
+    match transaction.commit().await {
+        Ok(_) => Ok(()),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn release_reservation(
+    database: &DatabaseConnection,
+    id: u64,
+    authentication: AuthenticationGuard,
+) -> Result<(), BackendError> {
+    let actor = match service_user::find_actor(database, &authentication).await {
+        Ok(actor) => actor,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    // The row lock serializes the release with consumptions and the expiry job
+    let result = tb_reservation::Entity::find_by_id(id)
+        .lock_exclusive()
+        .one(&transaction)
+        .await;
+
+    let reservation = match result {
+        Ok(Some(model)) => model,
+        Ok(None) => return Err(BackendError::ResourceNotFoundError),
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    if reservation.status != ReservationStatus::Active.as_str() {
+        return Ok(());
+    }
+
+    let released_reservation = ActiveModel {
+        id: ActiveValue::Set(id),
+        status: ActiveValue::Set(ReservationStatus::Released.as_str().to_string()),
+        closed_at: ActiveValue::Set(Some(Utc::now().naive_utc())),
+        ..Default::default()
+    };
+
+    let released_reservation = match tb_reservation::Entity::update(released_reservation)
+        .exec(&transaction)
+        .await
+    {
+        Ok(model) => model,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    service_audit::register_audit(
+        &transaction,
+        &actor,
+        AuditEntity::Reservation,
+        id,
+        AuditAction::Release,
+        service_audit::snapshot(&reservation),
+        service_audit::snapshot(&released_reservation),
+    )
+    .await?;
+
+    match transaction.commit().await {
+        Ok(_) => Ok(()),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn consume_reservation<C: ConnectionTrait>(
+    connection: &C,
+    id: u64,
+    product_id: u64,
+    quantity: u64,
+) -> Result<(), BackendError> {
+    let result = tb_reservation::Entity::find_by_id(id)
+        .lock_exclusive()
+        .one(connection)
+        .await;
+
+    let reservation = match result {
+        Ok(Some(model))
+            if model.product_id == product_id
+                && model.status == ReservationStatus::Active.as_str()
+                && model.expires_at > Utc::now().naive_utc() =>
+        {
+            model
+        }
+        Ok(_) => return Err(BackendError::ResourceNotFoundError),
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let consumed_quantity = reservation.consumed_quantity + quantity;
+
+    if consumed_quantity > reservation.quantity {
+        return Err(BackendError::NotEnoughtResourcers);
+    }
+
+    let consumed_reservation = ActiveModel {
+        id: ActiveValue::Set(id),
+        consumed_quantity: ActiveValue::Set(consumed_quantity),
+        status: match consumed_quantity == reservation.quantity {
+            true => ActiveValue::Set(ReservationStatus::Consumed.as_str().to_string()),
+            false => ActiveValue::NotSet,
+        },
+        closed_at: match consumed_quantity == reservation.quantity {
+            true => ActiveValue::Set(Some(Utc::now().naive_utc())),
+            false => ActiveValue::NotSet,
+        },
+        ..Default::default()
+    };
+
+    match tb_reservation::Entity::update(consumed_reservation)
+        .exec(connection)
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn verify_reserved_stock<C: ConnectionTrait>(
+    connection: &C,
+    product_id: u64,
+) -> Result<(), BackendError> {
+    let product = match tb_product::Entity::find_by_id(product_id)
+        .one(connection)
+        .await
+    {
+        Ok(Some(model)) => model,
+        Ok(None) => return Err(BackendError::ResourceNotFoundError),
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let reserved = match find_reserved_quantity(connection, product_id).await {
+        Ok(reserved) => reserved,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    match product.quantity >= reserved {
+        true => Ok(()),
+        false => Err(BackendError::NotEnoughtResourcers),
+    }
+}
+
+pub async fn release_expired(database: &DatabaseConnection) -> Result<(), BackendError> {
+    let now = Utc::now().naive_utc();
+
+    let result = tb_reservation::Entity::update_many()
+        .col_expr(
+            tb_reservation::Column::Status,
+            Expr::value(ReservationStatus::Expired.as_str()),
+        )
+        .col_expr(tb_reservation::Column::ClosedAt, Expr::value(now))
+        .filter(tb_reservation::Column::Status.eq(ReservationStatus::Active.as_str()))
+        .filter(tb_reservation::Column::ExpiresAt.lte(now))
+        .exec(database)
+        .await;
+
+    match result {
+        Ok(_) => Ok(()),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+async fn find_reserved_quantity<C: ConnectionTrait>(
+    connection: &C,
+    product_id: u64,
+) -> Result<u64, BackendError> {
+    let stmt = Statement::from_sql_and_values(
+        DbBackend::MySql,
+        r#"
+            SELECT
+                CAST(SUM(quantity - consumed_quantity) AS UNSIGNED) AS reserved
+            FROM tb_reservation
+            WHERE product_id = ?
+                AND status = ?
+                AND expires_at > UTC_TIMESTAMP()
+        "#,
+        [product_id.into(), ReservationStatus::Active.as_str().into()],
+    );
+
+    let result = ReservedQuantityDTO::find_by_statement(stmt)
+        .one(connection)
+        .await;
+
+    match result {
+        Ok(reserved_opt) => Ok(match reserved_opt {
+            Some(reserved_dto) => reserved_dto.get_into_reserved(),
+            None => 0,
+        }),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
@@ -0,0 +1,567 @@
+use sea_orm::{
+    ActiveValue, ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait,
+    FromQueryResult, PaginatorTrait, QueryFilter, Statement, TransactionTrait,
+};
+use serde_json::json;
+
+use crate::{
+    entities::{
+        dtos::role_dtos::{
+            PermissionDTO, PermissionNameQueryDTO, PermissionsUpdateDTO, RoleDTO,
+            RolePermissionQueryDTO, RoleViewDTO,
+        },
+        enums::audit_enums::{AuditAction, AuditEntity},
+        tb_permission, tb_role, tb_role_permission, tb_user, tb_user_permission,
+    },
+    errors::BackendError,
+    guards::guard_user::AuthenticationGuard,
+    services::{service_audit, service_user},
+};
+
+pub async fn get_all_roles(
+    database: &DatabaseConnection,
+) -> Result<Vec<RoleViewDTO>, BackendError> {
+    let stmt = Statement::from_string(
+        DbBackend::MySql,
+        r#"
+            SELECT
+                tb_role.id,
+                tb_role.name,
+                tb_permission.name AS permission
+            FROM tb_role
+            LEFT JOIN tb_role_permission
+                ON tb_role_permission.role_id = tb_role.id
+            LEFT JOIN tb_permission
+                ON tb_permission.id = tb_role_permission.permission_id
+            ORDER BY tb_role.id, tb_permission.name
+        "#,
+    );
+
+    let result = RolePermissionQueryDTO::find_by_statement(stmt)
+        .all(database)
+        .await;
+
+    match result {
+        Ok(rows) => Ok(RoleViewDTO::from_rows(rows)),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn get_all_permissions(
+    database: &DatabaseConnection,
+) -> Result<Vec<PermissionDTO>, BackendError> {
+    let result = tb_permission::Entity::find().all(database).await;
+
+    match result {
+        Ok(permissions) => Ok(permissions
+            .into_iter()
+            .map(|model| PermissionDTO::new(model.id, model.name))
+            .collect()),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn create_role(
+    database: &DatabaseConnection,
+    role_dto: RoleDTO,
+    authentication: AuthenticationGuard,
+) -> Result<(), BackendError> {
+    if role_dto.get_name().trim().is_empty() {
+        return Err(BackendError::InvalidValuesError);
+    }
+
+    let actor = match service_user::find_actor(database, &authentication).await {
+        Ok(actor) => actor,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    match find_role_by_name(database, role_dto.get_name()).await {
+        Ok(_) => return Err(BackendError::ResourceAlreadyInsertedError),
+        Err(BackendError::ResourceNotFoundError) => {}
+        Err(backend_error) => return Err(backend_error),
+    }
+
+    let permission_ids = match find_permission_ids(database, role_dto.get_permissions()).await {
+        Ok(permission_ids) => permission_ids,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let role = tb_role::ActiveModel {
+        id: ActiveValue::NotSet,
+        name: ActiveValue::Set(role_dto.get_name().clone()),
+    };
+
+    let role_id = match tb_role::Entity::insert(role).exec(&transaction).await {
+        Ok(insert_result) => insert_result.last_insert_id,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    replace_role_permissions(&transaction, role_id, permission_ids).await?;
+
+    let role = RoleViewDTO::new(
+        role_id,
+        role_dto.get_name().clone(),
+        role_dto.get_permissions().clone(),
+    );
+
+    service_audit::register_audit(
+        &transaction,
+        &actor,
+        AuditEntity::Role,
+        role_id as u64,
+        AuditAction::Create,
+        None,
+        service_audit::snapshot(&role),
+    )
+    .await?;
+
+    match transaction.commit().await {
+        Ok(_) => Ok(()),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn update_role(
+    database: &DatabaseConnection,
+    id: u8,
+    role_dto: RoleDTO,
+    authentication: AuthenticationGuard,
+) -> Result<(), BackendError> {
+    if id == 1 {
+        return Err(BackendError::InvalidValuesError);
+    }
+
+    let actor = match service_user::find_actor(database, &authentication).await {
+        Ok(actor) => actor,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let old_role = match find_role_view_by_id(database, id).await {
+        Ok(role) => role,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    if let Ok(old_role) = find_role_by_name(database, role_dto.get_name()).await
+        && old_role.id != id
+    {
+        return Err(BackendError::ResourceConflitUpdateError);
+    }
+
+    let permission_ids = match find_permission_ids(database, role_dto.get_permissions()).await {
+        Ok(permission_ids) => permission_ids,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    if !role_dto.get_name().trim().is_empty() {
+        let role = tb_role::ActiveModel {
+            id: ActiveValue::Set(id),
+            name: ActiveValue::Set(role_dto.get_name().clone()),
+        };
+
+        if let Err(db_err) = tb_role::Entity::update(role).exec(&transaction).await {
+            return Err(BackendError::DatabaseError(db_err));
+        }
+    }
+
+    replace_role_permissions(&transaction, id, permission_ids).await?;
+
+    let role = RoleViewDTO::new(
+        id,
+        match role_dto.get_name().trim().is_empty() {
+            true => old_role.get_name().clone(),
+            false => role_dto.get_name().clone(),
+        },
+        role_dto.get_permissions().clone(),
+    );
+
+    service_audit::register_audit(
+        &transaction,
+        &actor,
+        AuditEntity::Role,
+        id as u64,
+        AuditAction::Update,
+        service_audit::snapshot(&old_role),
+        service_audit::snapshot(&role),
+    )
+    .await?;
+
+    match transaction.commit().await {
+        Ok(_) => Ok(()),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn delete_role(
+    database: &DatabaseConnection,
+    id: u8,
+    authentication: AuthenticationGuard,
+) -> Result<(), BackendError> {
+    if id == 1 {
+        return Err(BackendError::InvalidValuesError);
+    }
+
+    let actor = match service_user::find_actor(database, &authentication).await {
+        Ok(actor) => actor,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let role = match find_role_view_by_id(database, id).await {
+        Ok(role) => role,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let users_with_role = tb_user::Entity::find()
+        .filter(tb_user::Column::Role.eq(id))
+        .count(database)
+        .await;
+
+    match users_with_role {
+        Ok(0) => {}
+        Ok(_) => return Err(BackendError::ResourceConflitUpdateError),
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    }
+
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    if let Err(db_err) = tb_role::Entity::delete_by_id(id).exec(&transaction).await {
+        return Err(BackendError::DatabaseError(db_err));
+    }
+
+    service_audit::register_audit(
+        &transaction,
+        &actor,
+        AuditEntity::Role,
+        id as u64,
+        AuditAction::Delete,
+        service_audit::snapshot(&role),
+        None,
+    )
+    .await?;
+
+    match transaction.commit().await {
+        Ok(_) => Ok(()),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn update_user_permissions(
+    database: &DatabaseConnection,
+    user_id: u64,
+    permissions_update_dto: PermissionsUpdateDTO,
+    authentication: AuthenticationGuard,
+) -> Result<(), BackendError> {
+    if user_id == 1 {
+        return Err(BackendError::InvalidValuesError);
+    }
+
+    let actor = match service_user::find_actor(database, &authentication).await {
+        Ok(actor) => actor,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    match tb_user::Entity::find_by_id(user_id).one(database).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return Err(BackendError::ResourceNotFoundError),
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    }
+
+    let old_permissions = match find_extra_permissions_by_user_id(database, user_id).await {
+        Ok(permissions) => permissions,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let permission_ids =
+        match find_permission_ids(database, permissions_update_dto.get_permissions()).await {
+            Ok(permission_ids) => permission_ids,
+            Err(backend_error) => return Err(backend_error),
+        };
+
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let result = tb_user_permission::Entity::delete_many()
+        .filter(tb_user_permission::Column::UserId.eq(user_id))
+        .exec(&transaction)
+        .await;
+
+    if let Err(db_err) = result {
+        return Err(BackendError::DatabaseError(db_err));
+    }
+
+    if !permission_ids.is_empty() {
+        let user_permissions =
+            permission_ids
+                .into_iter()
+                .map(|permission_id| tb_user_permission::ActiveModel {
+                    user_id: ActiveValue::Set(user_id),
+                    permission_id: ActiveValue::Set(permission_id),
+                });
+
+        let result = tb_user_permission::Entity::insert_many(user_permissions)
+            .exec_without_returning(&transaction)
+            .await;
+
+        if let Err(db_err) = result {
+            return Err(BackendError::DatabaseError(db_err));
+        }
+    }
+
+    service_audit::register_audit(
+        &transaction,
+        &actor,
+        AuditEntity::User,
+        user_id,
+        AuditAction::PermissionsUpdate,
+        Some(json!({ "permissions": old_permissions })),
+        Some(json!({ "permissions": permissions_update_dto.get_permissions() })),
+    )
+    .await?;
+
+    match transaction.commit().await {
+        Ok(_) => Ok(()),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn find_permissions_by_user_id(
+    database: &DatabaseConnection,
+    user_id: u64,
+) -> Result<Vec<String>, BackendError> {
+    let stmt = Statement::from_sql_and_values(
+        DbBackend::MySql,
+        r#"
+            SELECT tb_permission.name
+            FROM tb_permission
+            JOIN tb_role_permission
+                ON tb_role_permission.permission_id = tb_permission.id
+            JOIN tb_user
+                ON tb_user.role = tb_role_permission.role_id
+            WHERE tb_user.id = ?
+            UNION
+            SELECT tb_permission.name
+            FROM tb_permission
+            JOIN tb_user_permission
+                ON tb_user_permission.permission_id = tb_permission.id
+            WHERE tb_user_permission.user_id = ?
+        "#,
+        [user_id.into(), user_id.into()],
+    );
+
+    find_permission_names(database, stmt).await
+}
+
+// A key never grants more than its creator currently holds
+pub async fn find_permissions_by_api_key_id(
+    database: &DatabaseConnection,
+    api_key_id: u64,
+) -> Result<Vec<String>, BackendError> {
+    let stmt = Statement::from_sql_and_values(
+        DbBackend::MySql,
+        r#"
+            SELECT tb_permission.name
+            FROM tb_permission
+            JOIN tb_api_key_permission
+                ON tb_api_key_permission.permission_id = tb_permission.id
+            JOIN tb_api_key
+                ON tb_api_key.id = tb_api_key_permission.api_key_id
+            WHERE tb_api_key_permission.api_key_id = ?
+                AND tb_permission.id IN (
+                    SELECT tb_role_permission.permission_id
+                    FROM tb_role_permission
+                    JOIN tb_user
+                        ON tb_user.role = tb_role_permission.role_id
+                    WHERE tb_user.id = tb_api_key.created_by
+                    UNION
+                    SELECT tb_user_permission.permission_id
+                    FROM tb_user_permission
+                    WHERE tb_user_permission.user_id = tb_api_key.created_by
+                )
+        "#,
+        [api_key_id.into()],
+    );
+
+    find_permission_names(database, stmt).await
+}
+
+pub async fn find_permissions_by_role_id(
+    database: &DatabaseConnection,
+    role_id: u8,
+) -> Result<Vec<String>, BackendError> {
+    let stmt = Statement::from_sql_and_values(
+        DbBackend::MySql,
+        r#"
+            SELECT tb_permission.name
+            FROM tb_permission
+            JOIN tb_role_permission
+                ON tb_role_permission.permission_id = tb_permission.id
+            WHERE tb_role_permission.role_id = ?
+        "#,
+        [role_id.into()],
+    );
+
+    find_permission_names(database, stmt).await
+}
+
+pub async fn find_extra_permissions_by_user_id(
+    database: &DatabaseConnection,
+    user_id: u64,
+) -> Result<Vec<String>, BackendError> {
+    let stmt = Statement::from_sql_and_values(
+        DbBackend::MySql,
+        r#"
+            SELECT tb_permission.name
+            FROM tb_permission
+            JOIN tb_user_permission
+                ON tb_user_permission.permission_id = tb_permission.id
+            WHERE tb_user_permission.user_id = ?
+        "#,
+        [user_id.into()],
+    );
+
+    find_permission_names(database, stmt).await
+}
+
+pub async fn find_role_by_id(
+    database: &DatabaseConnection,
+    id: u8,
+) -> Result<tb_role::Model, BackendError> {
+    let result = tb_role::Entity::find_by_id(id).one(database).await;
+
+    match result {
+        Ok(model_opt) => match model_opt {
+            Some(model) => Ok(model),
+            None => Err(BackendError::ResourceNotFoundError),
+        },
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+async fn find_role_view_by_id(
+    database: &DatabaseConnection,
+    id: u8,
+) -> Result<RoleViewDTO, BackendError> {
+    let role = match find_role_by_id(database, id).await {
+        Ok(role) => role,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    match find_permissions_by_role_id(database, id).await {
+        Ok(permissions) => Ok(RoleViewDTO::new(role.id, role.name, permissions)),
+        Err(backend_error) => Err(backend_error),
+    }
+}
+
+async fn find_role_by_name(
+    database: &DatabaseConnection,
+    name: &str,
+) -> Result<tb_role::Model, BackendError> {
+    let result = tb_role::Entity::find()
+        .filter(tb_role::Column::Name.eq(name))
+        .one(database)
+        .await;
+
+    match result {
+        Ok(model_opt) => match model_opt {
+            Some(model) => Ok(model),
+            None => Err(BackendError::ResourceNotFoundError),
+        },
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+async fn find_permission_names(
+    database: &DatabaseConnection,
+    stmt: Statement,
+) -> Result<Vec<String>, BackendError> {
+    let result = PermissionNameQueryDTO::find_by_statement(stmt)
+        .all(database)
+        .await;
+
+    match result {
+        Ok(permissions) => Ok(permissions
+            .into_iter()
+            .map(|permission| permission.get_into_name())
+            .collect()),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn find_permission_ids(
+    database: &DatabaseConnection,
+    names: &[String],
+) -> Result<Vec<u64>, BackendError> {
+    if names.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let result = tb_permission::Entity::find()
+        .filter(tb_permission::Column::Name.is_in(names))
+        .all(database)
+        .await;
+
+    match result {
+        Ok(permissions) => {
+            if names.iter().any(|name| {
+                !permissions
+                    .iter()
+                    .any(|permission| &permission.name == name)
+            }) {
+                return Err(BackendError::InvalidValuesError);
+            }
+
+            Ok(permissions.into_iter().map(|model| model.id).collect())
+        }
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+async fn replace_role_permissions<C: ConnectionTrait>(
+    connection: &C,
+    role_id: u8,
+    permission_ids: Vec<u64>,
+) -> Result<(), BackendError> {
+    let result = tb_role_permission::Entity::delete_many()
+        .filter(tb_role_permission::Column::RoleId.eq(role_id))
+        .exec(connection)
+        .await;
+
+    if let Err(db_err) = result {
+        return Err(BackendError::DatabaseError(db_err));
+    }
+
+    if permission_ids.is_empty() {
+        return Ok(());
+    }
+
+    let role_permissions =
+        permission_ids
+            .into_iter()
+            .map(|permission_id| tb_role_permission::ActiveModel {
+                role_id: ActiveValue::Set(role_id),
+                permission_id: ActiveValue::Set(permission_id),
+            });
+
+    let result = tb_role_permission::Entity::insert_many(role_permissions)
+        .exec_without_returning(connection)
+        .await;
+
+    match result {
+        Ok(_) => Ok(()),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
@@ -0,0 +1,106 @@
+use sea_orm::DatabaseConnection;
+
+use crate::{
+    configs::config_jwt::valid_token,
+    entities::{
+        dtos::{product_dtos::ProductChangeQuantityDTO, scanner_dtos::ScannerProductDTO},
+        enums::{
+            permission_enums::{Permission, StockMove},
+            scanner_enums::ScannerDirection,
+        },
+    },
+    errors::BackendError,
+    guards::guard_user::AuthenticationGuard,
+    services::{service_api_key, service_product, service_role, service_user},
+};
+
+// The socket outlives the handshake, so every command re-reads the credentials and permissions
+pub async fn authorize(
+    database: &DatabaseConnection,
+    authentication: &AuthenticationGuard,
+) -> Result<AuthenticationGuard, BackendError> {
+    let (authentication, permissions) = match authentication {
+        AuthenticationGuard::User(token) => {
+            if !valid_token(token) {
+                return Err(BackendError::InvalidCredentialsError);
+            }
+
+            let user = match service_user::find_logged_user(database, authentication).await {
+                Ok(user) => user,
+                Err(BackendError::ResourceNotFoundError) => {
+                    return Err(BackendError::InvalidCredentialsError);
+                }
+                Err(backend_error) => return Err(backend_error),
+            };
+
+            (
+                authentication.clone(),
+                service_role::find_permissions_by_user_id(database, user.id).await,
+            )
+        }
+        AuthenticationGuard::ApiKey(api_key) => {
+            let api_key = match service_api_key::find_active_by_id(database, api_key.id).await {
+                Ok(model) => model,
+                Err(backend_error) => return Err(backend_error),
+            };
+
+            let permissions =
+                service_role::find_permissions_by_api_key_id(database, api_key.id).await;
+
+            (AuthenticationGuard::ApiKey(api_key), permissions)
+        }
+    };
+
+    match permissions {
+        Ok(permissions) if permissions.iter().any(|name| name == StockMove::CODE) => {
+            Ok(authentication)
+        }
+        Ok(_) => Err(BackendError::PermissionDeniedError),
+        Err(backend_error) => Err(backend_error),
+    }
+}
+
+pub async fn scan(
+    database: &DatabaseConnection,
+    barcode: &str,
+) -> Result<ScannerProductDTO, BackendError> {
+    let product = match service_product::find_by_barcode(database, barcode.trim()).await {
+        Ok(model) => model,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    find_scanner_product(database, product.id).await
+}
+
+pub async fn move_stock(
+    database: &DatabaseConnection,
+    barcode: &str,
+    quantity: u64,
+    direction: ScannerDirection,
+    reason_id: u64,
+    authentication: AuthenticationGuard,
+) -> Result<ScannerProductDTO, BackendError> {
+    let product = match service_product::find_by_barcode(database, barcode.trim()).await {
+        Ok(model) => model,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    service_product::change_quantity(
+        database,
+        ProductChangeQuantityDTO::new(product.id, direction.is_entry(), quantity, reason_id),
+        authentication,
+    )
+    .await?;
+
+    find_scanner_product(database, product.id).await
+}
+
+async fn find_scanner_product(
+    database: &DatabaseConnection,
+    product_id: u64,
+) -> Result<ScannerProductDTO, BackendError> {
+    match service_product::get_product_by_id(database, product_id).await {
+        Ok(product) => Ok(ScannerProductDTO::new(product_id, product)),
+        Err(backend_error) => Err(backend_error),
+    }
+}
@@ -0,0 +1,499 @@
+use chrono::Utc;
+use sea_orm::{
+    ActiveValue, ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait,
+    FromQueryResult, JoinType, PaginatorTrait, QueryFilter, QuerySelect, RelationTrait, Statement,
+    TransactionTrait, sea_query::OnConflict,
+};
+use serde_json::json;
+
+use crate::{
+    configs::config_environment::get_stocktake_reason,
+    entities::{
+        dtos::{
+            event_dtos::MovementEventDTO,
+            product_dtos::ProductChangeQuantityDTO,
+            stocktake_dtos::{
+                StocktakeCountDTO, StocktakeCreateDTO, StocktakeItemViewDTO, StocktakeViewDTO,
+            },
+        },
+        enums::{
+            audit_enums::{AuditAction, AuditEntity},
+            stocktake_enums::StocktakeStatus,
+        },
+        tb_product,
+        tb_stocktake::{self, ActiveModel, Model},
+        tb_stocktake_count, tb_stocktake_item,
+    },
+    errors::BackendError,
+    guards::guard_user::AuthenticationGuard,
+    services::{
+        service_audit, service_category, service_event, service_product, service_reason,
+        service_report, service_user,
+    },
+};
+
+pub async fn get_all_stocktakes(
+    database: &DatabaseConnection,
+) -> Result<Vec<StocktakeViewDTO>, BackendError> {
+    let stmt = Statement::from_string(
+        DbBackend::MySql,
+        r#"
+            SELECT
+                tb_stocktake.id,
+                tb_category.name AS category,
+                tb_stocktake.status,
+                CAST(tb_stocktake.created_at AS CHAR) AS created_at,
+                CAST(tb_stocktake.closed_at AS CHAR) AS closed_at,
+                CAST(
+                    (
+                        SELECT COUNT(*)
+                        FROM tb_stocktake_item
+                        WHERE tb_stocktake_item.stocktake_id = tb_stocktake.id
+                    ) AS UNSIGNED
+                ) AS products,
+                CAST(
+                    (
+                        SELECT COUNT(DISTINCT tb_stocktake_count.product_id)
+                        FROM tb_stocktake_count
+                        WHERE tb_stocktake_count.stocktake_id = tb_stocktake.id
+                    ) AS UNSIGNED
+                ) AS counted_products
+            FROM tb_stocktake
+            LEFT JOIN tb_category
+                ON tb_category.id = tb_stocktake.category_id
+            ORDER BY tb_stocktake.id DESC
+        "#,
+    );
+
+    let result = StocktakeViewDTO::find_by_statement(stmt)
+        .all(database)
+        .await;
+
+    match result {
+        Ok(stocktakes) => Ok(stocktakes),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn get_stocktake_items(
+    database: &DatabaseConnection,
+    id: u64,
+) -> Result<Vec<StocktakeItemViewDTO>, BackendError> {
+    find_by_id(database, id).await?;
+
+    find_items(database, id).await
+}
+
+pub async fn create_stocktake(
+    database: &DatabaseConnection,
+    stocktake_create_dto: StocktakeCreateDTO,
+    authentication: AuthenticationGuard,
+) -> Result<(), BackendError> {
+    let actor = match service_user::find_actor(database, &authentication).await {
+        Ok(actor) => actor,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    if let Some(category_id) = stocktake_create_dto.get_category_id() {
+        match service_category::exists_by_id(database, category_id).await {
+            Ok(boolean) => {
+                if !boolean {
+                    return Err(BackendError::ResourceNotFoundError);
+                }
+            }
+            Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+        }
+    }
+
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let mut query = tb_product::Entity::find().filter(tb_product::Column::DeletedAt.is_null());
+
+    if let Some(category_id) = stocktake_create_dto.get_category_id() {
+        query = query.filter(tb_product::Column::CategoryId.eq(*category_id));
+    }
+
+    let products = match query.lock_exclusive().all(&transaction).await {
+        Ok(products) => products,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    if products.is_empty() {
+        return Err(BackendError::InvalidValuesError);
+    }
+
+    for product in &products {
+        if is_product_frozen(&transaction, product.id).await? {
+            return Err(BackendError::ResourceLockedError);
+        }
+    }
+
+    let stocktake = ActiveModel {
+        category_id: ActiveValue::Set(*stocktake_create_dto.get_category_id()),
+        status: ActiveValue::Set(StocktakeStatus::Open.as_str().to_string()),
+        created_at: ActiveValue::Set(Utc::now().naive_utc()),
+        ..Default::default()
+    };
+
+    let stocktake = match tb_stocktake::Entity::insert(stocktake)
+        .exec_with_returning(&transaction)
+        .await
+    {
+        Ok(model) => model,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let items = products
+        .iter()
+        .map(|product| tb_stocktake_item::ActiveModel {
+            stocktake_id: ActiveValue::Set(stocktake.id),
+            product_id: ActiveValue::Set(product.id),
+            expected_quantity: ActiveValue::Set(product.quantity),
+        });
+
+    if let Err(db_err) = tb_stocktake_item::Entity::insert_many(items)
+        .exec_without_returning(&transaction)
+        .await
+    {
+        return Err(BackendError::DatabaseError(db_err));
+    }
+
+    service_audit::register_audit(
+        &transaction,
+        &actor,
+        AuditEntity::Stocktake,
+        stocktake.id,
+        AuditAction::Create,
+        None,
+        Some(json!({
+            "stocktake": stocktake,
+            "products": products.iter().map(|product| product.id).collect::<Vec<u64>>(),
+        })),
+    )
+    .await?;
+
+    match transaction.commit().await {
+        Ok(_) => Ok(()),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn register_count(
+    database: &DatabaseConnection,
+    id: u64,
+    stocktake_count_dto: StocktakeCountDTO,
+    authentication: AuthenticationGuard,
+) -> Result<(), BackendError> {
+    let logged_user = match service_user::find_logged_user(database, &authentication).await {
+        Ok(model) => model,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let stocktake = match find_by_id(database, id).await {
+        Ok(model) => model,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    if stocktake.status != StocktakeStatus::Open.as_str() {
+        return Err(BackendError::InvalidValuesError);
+    }
+
+    let item = tb_stocktake_item::Entity::find_by_id((id, *stocktake_count_dto.get_product_id()))
+        .one(database)
+        .await;
+
+    match item {
+        Ok(Some(_)) => {}
+        Ok(None) => return Err(BackendError::ResourceNotFoundError),
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    }
+
+    let count = tb_stocktake_count::ActiveModel {
+        stocktake_id: ActiveValue::Set(id),
+        product_id: ActiveValue::Set(*stocktake_count_dto.get_product_id()),
+        user_id: ActiveValue::Set(logged_user.id),
+        quantity: ActiveValue::Set(*stocktake_count_dto.get_quantity()),
+        counted_at: ActiveValue::Set(Utc::now().naive_utc()),
+    };
+
+    let result = tb_stocktake_count::Entity::insert(count)
+        .on_conflict(
+            OnConflict::columns([
+                tb_stocktake_count::Column::StocktakeId,
+                tb_stocktake_count::Column::ProductId,
+                tb_stocktake_count::Column::UserId,
+            ])
+            .update_columns([
+                tb_stocktake_count::Column::Quantity,
+                tb_stocktake_count::Column::CountedAt,
+            ])
+            .to_owned(),
+        )
+        .exec_without_returning(database)
+        .await;
+
+    match result {
+        Ok(_) => Ok(()),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn approve_stocktake(
+    database: &DatabaseConnection,
+    id: u64,
+    authentication: AuthenticationGuard,
+) -> Result<(), BackendError> {
+    let actor = match service_user::find_actor(database, &authentication).await {
+        Ok(actor) => actor,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let reason = match service_reason::find_by_name(database, &get_stocktake_reason()).await {
+        Ok(model) => model,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let stocktake = match find_open_for_update(&transaction, id).await {
+        Ok(model) => model,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let items = match find_items(&transaction, id).await {
+        Ok(items) => items,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    if items
+        .iter()
+        .any(|item| item.get_counted_quantity().is_none())
+    {
+        return Err(BackendError::InvalidValuesError);
+    }
+
+    let approved_stocktake =
+        match close_stocktake(&transaction, id, StocktakeStatus::Approved).await {
+            Ok(model) => model,
+            Err(backend_error) => return Err(backend_error),
+        };
+
+    let mut movements = Vec::new();
+
+    for item in &items {
+        let counted_quantity = item.get_counted_quantity().unwrap_or(*item.get_quantity());
+
+        if counted_quantity == *item.get_quantity() {
+            continue;
+        }
+
+        let change_type = counted_quantity > *item.get_quantity();
+        let quantity = counted_quantity.abs_diff(*item.get_quantity());
+
+        service_product::apply_quantity_change(
+            &transaction,
+            *item.get_product_id(),
+            change_type,
+            quantity,
+        )
+        .await?;
+
+        service_report::create_report(
+            &transaction,
+            ProductChangeQuantityDTO::new(*item.get_product_id(), change_type, quantity, reason.id),
+            &actor,
+        )
+        .await?;
+
+        movements.push(MovementEventDTO::new(
+            *item.get_product_id(),
+            change_type,
+            quantity,
+        ));
+    }
+
+    service_audit::register_audit(
+        &transaction,
+        &actor,
+        AuditEntity::Stocktake,
+        id,
+        AuditAction::Approve,
+        service_audit::snapshot(&stocktake),
+        Some(json!({
+            "stocktake": approved_stocktake,
+            "items": items,
+        })),
+    )
+    .await?;
+
+    match transaction.commit().await {
+        Ok(_) => {
+            service_event::publish_movements(database, movements).await;
+
+            Ok(())
+        }
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn cancel_stocktake(
+    database: &DatabaseConnection,
+    id: u64,
+    authentication: AuthenticationGuard,
+) -> Result<(), BackendError> {
+    let actor = match service_user::find_actor(database, &authentication).await {
+        Ok(actor) => actor,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let stocktake = match find_open_for_update(&transaction, id).await {
+        Ok(model) => model,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let cancelled_stocktake =
+        match close_stocktake(&transaction, id, StocktakeStatus::Cancelled).await {
+            Ok(model) => model,
+            Err(backend_error) => return Err(backend_error),
+        };
+
+    service_audit::register_audit(
+        &transaction,
+        &actor,
+        AuditEntity::Stocktake,
+        id,
+        AuditAction::Cancel,
+        service_audit::snapshot(&stocktake),
+        service_audit::snapshot(&cancelled_stocktake),
+    )
+    .await?;
+
+    match transaction.commit().await {
+        Ok(_) => Ok(()),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn is_product_frozen<C: ConnectionTrait>(
+    connection: &C,
+    product_id: u64,
+) -> Result<bool, BackendError> {
+    let result = tb_stocktake_item::Entity::find()
+        .join(
+            JoinType::InnerJoin,
+            tb_stocktake_item::Relation::TbStocktake.def(),
+        )
+        .filter(tb_stocktake_item::Column::ProductId.eq(product_id))
+        .filter(tb_stocktake::Column::Status.eq(StocktakeStatus::Open.as_str()))
+        .count(connection)
+        .await;
+
+    match result {
+        Ok(count) => Ok(count > 0),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+async fn find_items<C: ConnectionTrait>(
+    connection: &C,
+    id: u64,
+) -> Result<Vec<StocktakeItemViewDTO>, BackendError> {
+    let stmt = Statement::from_sql_and_values(
+        DbBackend::MySql,
+        r#"
+            SELECT
+                tb_stocktake_item.product_id,
+                tb_product.name AS product,
+                tb_stocktake_item.expected_quantity,
+                tb_product.quantity,
+                CAST(SUM(tb_stocktake_count.quantity) AS UNSIGNED) AS counted_quantity,
+                CAST(COUNT(tb_stocktake_count.user_id) AS UNSIGNED) AS counters,
+                CAST(SUM(tb_stocktake_count.quantity) AS SIGNED)
+                    - CAST(tb_product.quantity AS SIGNED) AS variance
+            FROM tb_stocktake_item
+            JOIN tb_product
+                ON tb_product.id = tb_stocktake_item.product_id
+            LEFT JOIN tb_stocktake_count
+                ON tb_stocktake_count.stocktake_id = tb_stocktake_item.stocktake_id
+                AND tb_stocktake_count.product_id = tb_stocktake_item.product_id
+            WHERE tb_stocktake_item.stocktake_id = ?
+            GROUP BY
+                tb_stocktake_item.product_id,
+                tb_product.name,
+                tb_stocktake_item.expected_quantity,
+                tb_product.quantity
+            ORDER BY tb_product.name
+        "#,
+        [id.into()],
+    );
+
+    let result = StocktakeItemViewDTO::find_by_statement(stmt)
+        .all(connection)
+        .await;
+
+    match result {
+        Ok(items) => Ok(items),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+async fn find_open_for_update<C: ConnectionTrait>(
+    connection: &C,
+    id: u64,
+) -> Result<Model, BackendError> {
+    let result = tb_stocktake::Entity::find_by_id(id)
+        .lock_exclusive()
+        .one(connection)
+        .await;
+
+    match result {
+        Ok(Some(model)) if model.status == StocktakeStatus::Open.as_str() => Ok(model),
+        Ok(Some(_)) => Err(BackendError::InvalidValuesError),
+        Ok(None) => Err(BackendError::ResourceNotFoundError),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+async fn close_stocktake<C: ConnectionTrait>(
+    connection: &C,
+    id: u64,
+    status: StocktakeStatus,
+) -> Result<Model, BackendError> {
+    let closed_stocktake = ActiveModel {
+        id: ActiveValue::Set(id),
+        status: ActiveValue::Set(status.as_str().to_string()),
+        closed_at: ActiveValue::Set(Some(Utc::now().naive_utc())),
+        ..Default::default()
+    };
+
+    match tb_stocktake::Entity::update(closed_stocktake)
+        .exec(connection)
+        .await
+    {
+        Ok(model) => Ok(model),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+async fn find_by_id(database: &DatabaseConnection, id: u64) -> Result<Model, BackendError> {
+    let result = tb_stocktake::Entity::find_by_id(id).one(database).await;
+
+    match result {
+        Ok(model_opt) => match model_opt {
+            Some(model) => Ok(model),
+            None => Err(BackendError::ResourceNotFoundError),
+        },
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
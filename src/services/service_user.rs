@@ -1,92 +1,285 @@
+use std::net::IpAddr;
+
+use chrono::Utc;
 use sea_orm::{
-    ActiveValue, ColumnTrait, DatabaseConnection, DbBackend, DbErr, EntityTrait, FromQueryResult,
-    QueryFilter, Statement,
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend,
+    DbErr, EntityTrait, FromQueryResult, QueryFilter, Statement, TransactionTrait,
 };
 
 use crate::{
     configs::{
-        config_bcrypt::{self, encrypt_password, verify_password},
-        config_jwt::{self, generate_token, get_email_by_token},
+        config_bcrypt::{self, encrypt_password, verify_dummy_password, verify_password},
+        config_environment::{get_login_max_attempts, get_totp_required_roles},
+        config_jwt::{
+            self, generate_challenge_token, generate_token, get_email_by_challenge_token,
+            get_email_by_token,
+        },
+        config_totp::{
+            generate_recovery_codes, generate_totp_secret, get_provisioning_uri,
+            hash_recovery_code, verify_totp_code,
+        },
     },
     entities::{
         dtos::{
-            generic_dtos::ExistsDTO,
+            generic_dtos::{ActorDTO, ExistsDTO, SortDTO},
             user_dtos::{
-                AuthenticationDTO, LoginDTO, UserCreateDTO, UserCredentialsUpdateDTO,
-                UserInformationsUpdateDTO, UserSummaryForAdminDTO, UserSummaryForAdminQueryDTO,
-                ValidedTokenDTO,
+                AuthenticationDTO, LoginChallengeDTO, LoginDTO, LoginResultDTO,
+                TwoFactorChallengeDTO, TwoFactorCodeDTO, TwoFactorEnrollmentDTO, TwoFactorLoginDTO,
+                UserActiveUpdateDTO, UserAdminUpdateDTO, UserCreateDTO, UserCredentialsUpdateDTO,
+                UserInformationsUpdateDTO, UserPasswordResetDTO, UserPatchDTO,
+                UserSummaryForAdminDTO, ValidedTokenDTO,
             },
         },
-        enums::user_enums::UserRole,
+        enums::{
+            audit_enums::{AuditAction, AuditEntity},
+            export_enums::{ExportFormat, ExportLanguage},
+            permission_enums::{Permission, RoleManage},
+            sort_enums::SortField,
+        },
         tb_user::{self, ActiveModel, Model},
+        tb_user_recovery_code,
     },
     errors::BackendError,
     guards::guard_user::AuthenticationGuard,
+    services::{
+        service_audit,
+        service_export::{self, ExportFile},
+        service_login_attempt, service_role,
+    },
 };
 
 pub async fn login(
     database: &DatabaseConnection,
     login_dto: LoginDTO,
+    client_ip: Option<IpAddr>,
+) -> Result<LoginResultDTO, BackendError> {
+    verify_ip_not_locked(database, &client_ip).await?;
+
+    let user = match find_by_email(database, login_dto.get_email()).await {
+        Ok(user) => user,
+        Err(BackendError::ResourceNotFoundError) => {
+            verify_dummy_password(login_dto.get_password());
+
+            return Err(register_login_failure(database, None, &client_ip).await);
+        }
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let locked = service_login_attempt::is_locked(&user.locked_until);
+
+    // A locked account answers a wrong password like an unknown e-mail, so the lockout does not
+    // reveal which e-mails are registered
+    if !verify_password(login_dto.get_password(), &user.password) {
+        let failed_user = match locked {
+            true => None,
+            false => Some(&user),
+        };
+
+        return Err(register_login_failure(database, failed_user, &client_ip).await);
+    }
+
+    if locked {
+        return Err(BackendError::TooManyAttemptsError);
+    }
+
+    if !user.active {
+        return Err(BackendError::InactiveUserError);
+    }
+
+    if user.totp_enabled || get_totp_required_roles().contains(&user.role) {
+        let challenge_token = generate_challenge_token(user.email.clone());
+
+        return Ok(LoginResultDTO::Challenge(LoginChallengeDTO::new(
+            challenge_token.unwrap(),
+            !user.totp_enabled,
+        )));
+    }
+
+    if user.failed_attempts > 0
+        && let Err(backend_error) = reset_login_failures(database, user.id).await
+    {
+        return Err(backend_error);
+    }
+
+    match create_authentication(database, user).await {
+        Ok(authentication_dto) => Ok(LoginResultDTO::Authenticated(authentication_dto)),
+        Err(backend_error) => Err(backend_error),
+    }
+}
+
+pub async fn login_two_factor(
+    database: &DatabaseConnection,
+    two_factor_login_dto: TwoFactorLoginDTO,
+    client_ip: Option<IpAddr>,
 ) -> Result<AuthenticationDTO, BackendError> {
-    let result = find_by_email(database, login_dto.get_email()).await;
+    verify_ip_not_locked(database, &client_ip).await?;
 
-    match result {
-        Ok(user) => {
-            if !verify_password(login_dto.get_password(), &user.password) {
-                return Err(BackendError::InvalidCredentialsError);
-            }
+    let user =
+        match find_by_challenge_token(database, two_factor_login_dto.get_challenge_token()).await {
+            Ok(user) => user,
+            Err(backend_error) => return Err(backend_error),
+        };
 
-            let token = generate_token(user.email.clone());
+    if service_login_attempt::is_locked(&user.locked_until) {
+        return Err(BackendError::TooManyAttemptsError);
+    }
 
-            Ok(AuthenticationDTO::new(
-                token.unwrap(),
-                user.role,
-                user.username,
-                UserRole::code_to_string(user.role),
-            ))
-        }
+    if !user.active {
+        return Err(BackendError::InactiveUserError);
+    }
+
+    let totp_secret = match &user.totp_secret {
+        Some(totp_secret) => totp_secret,
+        None => return Err(BackendError::InvalidValuesError),
+    };
+
+    let mut code_is_valid =
+        match verify_totp_code(totp_secret, &user.email, two_factor_login_dto.get_code()) {
+            Some(step) => match use_totp_step(database, user.id, step).await {
+                Ok(boolean) => boolean,
+                Err(backend_error) => return Err(backend_error),
+            },
+            None => false,
+        };
+
+    if !code_is_valid && user.totp_enabled {
+        code_is_valid =
+            match use_recovery_code(database, user.id, two_factor_login_dto.get_code()).await {
+                Ok(boolean) => boolean,
+                Err(backend_error) => return Err(backend_error),
+            };
+    }
+
+    if !code_is_valid {
+        return Err(register_login_failure(database, Some(&user), &client_ip).await);
+    }
+
+    if !user.totp_enabled
+        && let Err(backend_error) = enable_two_factor(database, &user).await
+    {
+        return Err(backend_error);
+    }
+
+    if user.failed_attempts > 0
+        && let Err(backend_error) = reset_login_failures(database, user.id).await
+    {
+        return Err(backend_error);
+    }
+
+    create_authentication(database, user).await
+}
+
+pub async fn start_two_factor_enrollment_by_challenge(
+    database: &DatabaseConnection,
+    two_factor_challenge_dto: TwoFactorChallengeDTO,
+) -> Result<TwoFactorEnrollmentDTO, BackendError> {
+    let user =
+        match find_by_challenge_token(database, two_factor_challenge_dto.get_challenge_token())
+            .await
+        {
+            Ok(user) => user,
+            Err(backend_error) => return Err(backend_error),
+        };
+
+    if !user.active {
+        return Err(BackendError::InactiveUserError);
+    }
+
+    begin_two_factor_enrollment(database, &user).await
+}
+
+pub async fn start_two_factor_enrollment(
+    database: &DatabaseConnection,
+    authentication: AuthenticationGuard,
+) -> Result<TwoFactorEnrollmentDTO, BackendError> {
+    match find_logged_user(database, &authentication).await {
+        Ok(logged_user) => begin_two_factor_enrollment(database, &logged_user).await,
         Err(backend_error) => Err(backend_error),
     }
 }
 
+pub async fn confirm_two_factor_enrollment(
+    database: &DatabaseConnection,
+    two_factor_code_dto: TwoFactorCodeDTO,
+    authentication: AuthenticationGuard,
+) -> Result<(), BackendError> {
+    let logged_user = match find_logged_user(database, &authentication).await {
+        Ok(model) => model,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    if logged_user.totp_enabled {
+        return Err(BackendError::ResourceAlreadyInsertedError);
+    }
+
+    let totp_secret = match &logged_user.totp_secret {
+        Some(totp_secret) => totp_secret,
+        None => return Err(BackendError::InvalidValuesError),
+    };
+
+    let step = match verify_totp_code(
+        totp_secret,
+        &logged_user.email,
+        two_factor_code_dto.get_code(),
+    ) {
+        Some(step) => step,
+        None => return Err(BackendError::InvalidCredentialsError),
+    };
+
+    match use_totp_step(database, logged_user.id, step).await {
+        Ok(true) => {}
+        Ok(false) => return Err(BackendError::InvalidCredentialsError),
+        Err(backend_error) => return Err(backend_error),
+    }
+
+    enable_two_factor(database, &logged_user).await
+}
+
 pub async fn valid(
     database: &DatabaseConnection,
     authentication: AuthenticationGuard,
 ) -> Result<ValidedTokenDTO, BackendError> {
-    let token_is_valid = config_jwt::valid_token(&authentication.0);
-    let result =
-        exists_by_email(database, &config_jwt::get_email_by_token(&authentication.0)).await;
+    let token = match authentication {
+        AuthenticationGuard::User(token) => token,
+        AuthenticationGuard::ApiKey(_) => return Ok(ValidedTokenDTO::new(true)),
+    };
+
+    let token_is_valid = config_jwt::valid_token(&token);
+    let result = find_by_email(database, &config_jwt::get_email_by_token(&token)).await;
 
     match result {
-        Ok(user_exists) => match user_exists {
-            true => Ok(ValidedTokenDTO::new(token_is_valid && user_exists)),
-            false => Err(BackendError::InvalidCredentialsError),
-        },
-        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+        Ok(user) => Ok(ValidedTokenDTO::new(token_is_valid && user.active)),
+        Err(BackendError::ResourceNotFoundError) => Err(BackendError::InvalidCredentialsError),
+        Err(backend_error) => Err(backend_error),
     }
 }
 
 pub async fn get_all_users(
     database: &DatabaseConnection,
+    sort_dto: &SortDTO,
 ) -> Result<Vec<UserSummaryForAdminDTO>, BackendError> {
-    let stmt = Statement::from_string(
-        DbBackend::MySql,
-        r#"
-            SELECT 
-                tb_user.id,
-                tb_user.username,
-                tb_user.email,
-                tb_user.role
-            FROM tb_user
-        "#,
-    );
-
-    let result = UserSummaryForAdminQueryDTO::find_by_statement(stmt)
+    let result = UserSummaryForAdminDTO::find_by_statement(all_users_statement(sort_dto))
         .all(database)
         .await;
 
     match result {
-        Ok(users) => Ok(users.into_iter().map(|user| user.into()).collect()),
+        Ok(users) => Ok(users),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn export_users(
+    database: &DatabaseConnection,
+    format: ExportFormat,
+    language: ExportLanguage,
+) -> Result<ExportFile<'_>, BackendError> {
+    let result =
+        UserSummaryForAdminDTO::find_by_statement(all_users_statement(&SortDTO::default()))
+            .stream(database)
+            .await;
+
+    match result {
+        Ok(users) => service_export::export_rows(users, "users", format, language).await,
         Err(db_err) => Err(BackendError::DatabaseError(db_err)),
     }
 }
@@ -94,12 +287,17 @@ pub async fn get_all_users(
 pub async fn create_user(
     database: &DatabaseConnection,
     user_create_dto: UserCreateDTO,
+    authentication: AuthenticationGuard,
 ) -> Result<(), BackendError> {
-    if user_create_dto.get_role() == &1
-        || user_create_dto.get_role() < &1
-        || user_create_dto.get_role() > &4
-    {
-        return Err(BackendError::InvalidValuesError);
+    let logged_user = match find_logged_user(database, &authentication).await {
+        Ok(model) => model,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    match can_grant_role(database, &logged_user, *user_create_dto.get_role()).await {
+        Ok(true) => {}
+        Ok(false) => return Err(BackendError::PermissionDeniedError),
+        Err(backend_error) => return Err(backend_error),
     }
 
     match exists_by_email(database, user_create_dto.get_email()).await {
@@ -116,6 +314,11 @@ pub async fn create_user(
         username: ActiveValue::set(user_create_dto.get_username().to_string()),
         email: ActiveValue::Set(user_create_dto.get_email().to_string()),
         password: ActiveValue::Set(encrypt_password(user_create_dto.get_password())),
+        failed_attempts: ActiveValue::Set(0),
+        locked_until: ActiveValue::Set(None),
+        totp_secret: ActiveValue::Set(None),
+        totp_enabled: ActiveValue::Set(false),
+        totp_last_step: ActiveValue::Set(None),
         role: ActiveValue::Set(*user_create_dto.get_role()),
         phone: ActiveValue::Set(match user_create_dto.get_phone() {
             Some(phone) => match phone.trim().is_empty() {
@@ -124,11 +327,35 @@ pub async fn create_user(
             },
             None => None,
         }),
+        active: ActiveValue::Set(true),
+        created_at: ActiveValue::NotSet,
+        updated_at: ActiveValue::NotSet,
+        created_by: ActiveValue::NotSet,
+        updated_by: ActiveValue::Set(Some(logged_user.id)),
     };
 
-    let result = tb_user::Entity::insert(user).exec(database).await;
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
 
-    match result {
+    let user = match user.insert(&transaction).await {
+        Ok(model) => model,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    service_audit::register_audit(
+        &transaction,
+        &ActorDTO::new(Some(logged_user.id), None),
+        AuditEntity::User,
+        user.id,
+        AuditAction::Create,
+        None,
+        service_audit::snapshot(&user),
+    )
+    .await?;
+
+    match transaction.commit().await {
         Ok(_) => Ok(()),
         Err(db_err) => Err(BackendError::DatabaseError(db_err)),
     }
@@ -139,20 +366,20 @@ pub async fn update_user_informations(
     user_update_dto: UserInformationsUpdateDTO,
     authentication: AuthenticationGuard,
 ) -> Result<(), BackendError> {
-    let email = get_email_by_token(&authentication.0);
-
-    let result = find_by_email(database, &email).await;
+    let result = find_logged_user(database, &authentication).await;
 
     match result {
         Ok(logged_user) => {
-            let update_user = create_update_active_model(user_update_dto, logged_user);
+            let update_user = create_update_active_model(user_update_dto, &logged_user);
 
-            let result = tb_user::Entity::update(update_user).exec(database).await;
-
-            match result {
-                Ok(_) => Ok(()),
-                Err(db_err) => Err(BackendError::DatabaseError(db_err)),
-            }
+            update_user_with_audit(
+                database,
+                &ActorDTO::new(Some(logged_user.id), None),
+                &logged_user,
+                update_user,
+                AuditAction::Update,
+            )
+            .await
         }
         Err(backend_error) => Err(backend_error),
     }
@@ -163,9 +390,7 @@ pub async fn update_user_credentials(
     user_update_dto: UserCredentialsUpdateDTO,
     authentication: AuthenticationGuard,
 ) -> Result<(), BackendError> {
-    let email = get_email_by_token(&authentication.0);
-
-    let result = find_by_email(database, &email).await;
+    let result = find_logged_user(database, &authentication).await;
 
     match result {
         Ok(logged_user) => {
@@ -184,39 +409,387 @@ pub async fn update_user_credentials(
                 ..Default::default()
             };
 
-            let result = tb_user::Entity::update(update_user).exec(database).await;
-
-            match result {
-                Ok(_) => Ok(()),
-                Err(db_err) => Err(BackendError::DatabaseError(db_err)),
-            }
+            update_user_with_audit(
+                database,
+                &ActorDTO::new(Some(logged_user.id), None),
+                &logged_user,
+                update_user,
+                AuditAction::PasswordChange,
+            )
+            .await
         }
         Err(backend_error) => Err(backend_error),
     }
 }
 
-pub async fn delete_user_by_id(database: &DatabaseConnection, id: u64) -> Result<(), BackendError> {
-    if id == 1 {
+pub async fn update_user(
+    database: &DatabaseConnection,
+    id: u64,
+    user_update_dto: UserAdminUpdateDTO,
+    authentication: AuthenticationGuard,
+) -> Result<(), BackendError> {
+    patch_user(database, id, user_update_dto.into(), authentication).await
+}
+
+pub async fn patch_user(
+    database: &DatabaseConnection,
+    id: u64,
+    user_patch_dto: UserPatchDTO,
+    authentication: AuthenticationGuard,
+) -> Result<(), BackendError> {
+    let logged_user = match find_logged_user(database, &authentication).await {
+        Ok(model) => model,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let target_user = match find_by_id(database, id).await {
+        Ok(model) => model,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    match can_manage(database, &logged_user, &target_user).await {
+        Ok(true) => {}
+        Ok(false) => return Err(BackendError::PermissionDeniedError),
+        Err(backend_error) => return Err(backend_error),
+    }
+
+    if let Some(username) = user_patch_dto.get_username()
+        && username.trim().is_empty()
+    {
         return Err(BackendError::InvalidValuesError);
     }
 
-    match exists_by_id(database, &id).await {
-        Ok(boolean) => {
-            if !boolean {
-                return Err(BackendError::ResourceNotFoundError);
-            }
+    if let Some(email) = user_patch_dto.get_email()
+        && email.trim().is_empty()
+    {
+        return Err(BackendError::InvalidValuesError);
+    }
+
+    if let Some(Some(phone)) = user_patch_dto.get_phone()
+        && phone.trim().is_empty()
+    {
+        return Err(BackendError::InvalidValuesError);
+    }
+
+    if let Some(role) = *user_patch_dto.get_role()
+        && role != target_user.role
+    {
+        if target_user.id == 1 {
+            return Err(BackendError::InvalidValuesError);
+        }
+
+        match can_grant_role(database, &logged_user, role).await {
+            Ok(true) => {}
+            Ok(false) => return Err(BackendError::PermissionDeniedError),
+            Err(backend_error) => return Err(backend_error),
         }
+    }
+
+    if let Some(email) = user_patch_dto.get_email()
+        && let Ok(old_user) = find_by_email(database, email).await
+        && old_user.id != target_user.id
+    {
+        return Err(BackendError::ResourceConflitUpdateError);
+    }
+
+    let update_user = create_admin_update_active_model(user_patch_dto, &target_user);
+
+    update_user_with_audit(
+        database,
+        &ActorDTO::new(Some(logged_user.id), None),
+        &target_user,
+        update_user,
+        AuditAction::Update,
+    )
+    .await
+}
+
+pub async fn reset_user_password(
+    database: &DatabaseConnection,
+    id: u64,
+    user_password_reset_dto: UserPasswordResetDTO,
+    authentication: AuthenticationGuard,
+) -> Result<(), BackendError> {
+    if user_password_reset_dto.get_new_password().trim().is_empty() {
+        return Err(BackendError::InvalidValuesError);
+    }
+
+    // Credentials are only reset by people, so API keys are refused by find_logged_user
+    let logged_user = match find_logged_user(database, &authentication).await {
+        Ok(model) => model,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let target_user = match find_by_id(database, id).await {
+        Ok(model) => model,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    match can_manage(database, &logged_user, &target_user).await {
+        Ok(true) => {}
+        Ok(false) => return Err(BackendError::PermissionDeniedError),
+        Err(backend_error) => return Err(backend_error),
+    }
+
+    let update_user = ActiveModel {
+        id: ActiveValue::Set(id),
+        password: ActiveValue::Set(config_bcrypt::encrypt_password(
+            user_password_reset_dto.get_new_password(),
+        )),
+        ..Default::default()
+    };
+
+    update_user_with_audit(
+        database,
+        &ActorDTO::new(Some(logged_user.id), None),
+        &target_user,
+        update_user,
+        AuditAction::PasswordReset,
+    )
+    .await
+}
+
+pub async fn update_user_active(
+    database: &DatabaseConnection,
+    id: u64,
+    user_active_update_dto: UserActiveUpdateDTO,
+    authentication: AuthenticationGuard,
+) -> Result<(), BackendError> {
+    let logged_user = match find_logged_user(database, &authentication).await {
+        Ok(model) => model,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let target_user = match find_by_id(database, id).await {
+        Ok(model) => model,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    if target_user.id == logged_user.id || target_user.id == 1 {
+        return Err(BackendError::InvalidValuesError);
+    }
+
+    match can_manage(database, &logged_user, &target_user).await {
+        Ok(true) => {}
+        Ok(false) => return Err(BackendError::PermissionDeniedError),
+        Err(backend_error) => return Err(backend_error),
+    }
+
+    let update_user = ActiveModel {
+        id: ActiveValue::Set(target_user.id),
+        active: ActiveValue::Set(*user_active_update_dto.get_active()),
+        ..Default::default()
+    };
+
+    update_user_with_audit(
+        database,
+        &ActorDTO::new(Some(logged_user.id), None),
+        &target_user,
+        update_user,
+        AuditAction::Update,
+    )
+    .await
+}
+
+pub async fn unlock_user(
+    database: &DatabaseConnection,
+    id: u64,
+    authentication: AuthenticationGuard,
+) -> Result<(), BackendError> {
+    let logged_user = match find_logged_user(database, &authentication).await {
+        Ok(model) => model,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let target_user = match find_by_id(database, id).await {
+        Ok(model) => model,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    match can_manage(database, &logged_user, &target_user).await {
+        Ok(true) => {}
+        Ok(false) => return Err(BackendError::PermissionDeniedError),
+        Err(backend_error) => return Err(backend_error),
+    }
+
+    let update_user = ActiveModel {
+        id: ActiveValue::Set(id),
+        failed_attempts: ActiveValue::Set(0),
+        locked_until: ActiveValue::Set(None),
+        ..Default::default()
+    };
+
+    update_user_with_audit(
+        database,
+        &ActorDTO::new(Some(logged_user.id), None),
+        &target_user,
+        update_user,
+        AuditAction::Unlock,
+    )
+    .await
+}
+
+pub async fn reset_two_factor(
+    database: &DatabaseConnection,
+    id: u64,
+    authentication: AuthenticationGuard,
+) -> Result<(), BackendError> {
+    let logged_user = match find_logged_user(database, &authentication).await {
+        Ok(model) => model,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let target_user = match find_by_id(database, id).await {
+        Ok(model) => model,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    match can_manage(database, &logged_user, &target_user).await {
+        Ok(true) => {}
+        Ok(false) => return Err(BackendError::PermissionDeniedError),
+        Err(backend_error) => return Err(backend_error),
+    }
+
+    let actor = ActorDTO::new(Some(logged_user.id), None);
+
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
         Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let update_user = ActiveModel {
+        id: ActiveValue::Set(id),
+        totp_secret: ActiveValue::Set(None),
+        totp_enabled: ActiveValue::Set(false),
+        totp_last_step: ActiveValue::Set(None),
+        updated_by: ActiveValue::Set(*actor.get_user_id()),
+        ..Default::default()
+    };
+
+    let user = match update_user.update(&transaction).await {
+        Ok(model) => model,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let result = tb_user_recovery_code::Entity::delete_many()
+        .filter(tb_user_recovery_code::Column::UserId.eq(id))
+        .exec(&transaction)
+        .await;
+
+    if let Err(db_err) = result {
+        return Err(BackendError::DatabaseError(db_err));
     }
 
-    let result = tb_user::Entity::delete_by_id(id).exec(database).await;
+    service_audit::register_audit(
+        &transaction,
+        &actor,
+        AuditEntity::User,
+        id,
+        AuditAction::TwoFactorReset,
+        service_audit::snapshot(&target_user),
+        service_audit::snapshot(&user),
+    )
+    .await?;
 
-    match result {
+    match transaction.commit().await {
+        Ok(_) => Ok(()),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+pub async fn delete_user_by_id(
+    database: &DatabaseConnection,
+    id: u64,
+    authentication: AuthenticationGuard,
+) -> Result<(), BackendError> {
+    if id == 1 {
+        return Err(BackendError::InvalidValuesError);
+    }
+
+    let logged_user = match find_logged_user(database, &authentication).await {
+        Ok(model) => model,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let user = match find_by_id(database, id).await {
+        Ok(model) => model,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    match can_manage(database, &logged_user, &user).await {
+        Ok(true) => {}
+        Ok(false) => return Err(BackendError::PermissionDeniedError),
+        Err(backend_error) => return Err(backend_error),
+    }
+
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    if let Err(db_err) = tb_user::Entity::delete_by_id(id).exec(&transaction).await {
+        return Err(BackendError::DatabaseError(db_err));
+    }
+
+    service_audit::register_audit(
+        &transaction,
+        &ActorDTO::new(Some(logged_user.id), None),
+        AuditEntity::User,
+        id,
+        AuditAction::Delete,
+        service_audit::snapshot(&user),
+        None,
+    )
+    .await?;
+
+    match transaction.commit().await {
         Ok(_) => Ok(()),
         Err(db_err) => Err(BackendError::DatabaseError(db_err)),
     }
 }
 
+pub async fn find_logged_user(
+    database: &DatabaseConnection,
+    authentication: &AuthenticationGuard,
+) -> Result<Model, BackendError> {
+    // A deactivated user's token is still signed, so routes without a PermissionGuard rely on this
+    match authentication {
+        AuthenticationGuard::User(token) => {
+            match find_by_email(database, &get_email_by_token(token)).await {
+                Ok(user) if !user.active => Err(BackendError::InactiveUserError),
+                result => result,
+            }
+        }
+        AuthenticationGuard::ApiKey(_) => Err(BackendError::PermissionDeniedError),
+    }
+}
+
+pub async fn find_actor(
+    database: &DatabaseConnection,
+    authentication: &AuthenticationGuard,
+) -> Result<ActorDTO, BackendError> {
+    match authentication {
+        AuthenticationGuard::User(_) => match find_logged_user(database, authentication).await {
+            Ok(logged_user) => Ok(ActorDTO::new(Some(logged_user.id), None)),
+            Err(backend_error) => Err(backend_error),
+        },
+        AuthenticationGuard::ApiKey(api_key) => Ok(ActorDTO::new(None, Some(api_key.id))),
+    }
+}
+
+pub async fn find_actor_name(
+    database: &DatabaseConnection,
+    authentication: &AuthenticationGuard,
+) -> Result<String, BackendError> {
+    match authentication {
+        AuthenticationGuard::User(_) => match find_logged_user(database, authentication).await {
+            Ok(logged_user) => Ok(logged_user.username),
+            Err(backend_error) => Err(backend_error),
+        },
+        AuthenticationGuard::ApiKey(api_key) => Ok(api_key.name.clone()),
+    }
+}
+
 pub async fn find_by_email(
     database: &DatabaseConnection,
     email: &str,
@@ -235,33 +808,411 @@ pub async fn find_by_email(
     }
 }
 
-async fn exists_by_email(database: &DatabaseConnection, email: &str) -> Result<bool, DbErr> {
-    let stmt = Statement::from_string(
+// Timestamps and author names of a row of `table`, joined by authorship_join
+pub fn authorship_columns(table: &str) -> String {
+    format!(
+        "
+            CAST({table}.created_at AS CHAR) AS created_at,
+            CAST({table}.updated_at AS CHAR) AS updated_at,
+            created_user.username AS created_by,
+            updated_user.username AS updated_by
+        "
+    )
+}
+
+pub fn authorship_join(table: &str) -> String {
+    format!(
+        "
+            LEFT JOIN tb_user AS created_user
+                ON created_user.id = {table}.created_by
+            LEFT JOIN tb_user AS updated_user
+                ON updated_user.id = {table}.updated_by
+        "
+    )
+}
+
+// Authors are sorted by name, and rows with the same value keep the order of their ids
+pub fn authorship_order_by(table: &str, sort_dto: &SortDTO) -> String {
+    let column = match sort_dto.get_sort() {
+        Some(SortField::CreatedAt) => format!("{table}.created_at"),
+        Some(SortField::UpdatedAt) => format!("{table}.updated_at"),
+        Some(SortField::CreatedBy) => "created_user.username".to_string(),
+        Some(SortField::UpdatedBy) => "updated_user.username".to_string(),
+        None => return String::new(),
+    };
+
+    format!(
+        "ORDER BY {column} {}, {table}.id",
+        sort_dto.get_order().as_sql()
+    )
+}
+
+fn all_users_statement(sort_dto: &SortDTO) -> Statement {
+    Statement::from_string(
         DbBackend::MySql,
         format!(
-            "
+            r#"
             SELECT
-                EXISTS(
-                    SELECT 1
-                    FROM tb_user
-                    WHERE tb_user.email = (\"{email}\")
-                ) AS 'exist'
-        "
+                tb_user.id,
+                tb_user.username,
+                tb_user.email,
+                tb_role.name AS role,
+                tb_user.active,
+                {}
+            FROM tb_user
+            JOIN tb_role
+                ON tb_role.id = tb_user.role
+            {}
+            {}
+        "#,
+            authorship_columns("tb_user"),
+            authorship_join("tb_user"),
+            authorship_order_by("tb_user", sort_dto)
         ),
+    )
+}
+
+async fn register_login_failure(
+    database: &DatabaseConnection,
+    user: Option<&Model>,
+    client_ip: &Option<IpAddr>,
+) -> BackendError {
+    if let Some(ip) = client_ip
+        && let Err(backend_error) = service_login_attempt::register_ip_failure(database, ip).await
+    {
+        return backend_error;
+    }
+
+    if let Some(user) = user
+        && let Err(backend_error) = register_user_failure(database, user.id).await
+    {
+        return backend_error;
+    }
+
+    BackendError::InvalidCredentialsError
+}
+
+// The increment is atomic and keeps the row locked until the lockout is written, so parallel
+// attempts cannot lose failures
+async fn register_user_failure(database: &DatabaseConnection, id: u64) -> Result<(), BackendError> {
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let stmt = Statement::from_sql_and_values(
+        DbBackend::MySql,
+        r#"
+            UPDATE tb_user
+            SET failed_attempts = failed_attempts + 1
+            WHERE id = ?
+        "#,
+        [id.into()],
     );
 
-    let result = ExistsDTO::find_by_statement(stmt).one(database).await;
+    if let Err(db_err) = transaction.execute(stmt).await {
+        return Err(BackendError::DatabaseError(db_err));
+    }
+
+    let user = match tb_user::Entity::find_by_id(id).one(&transaction).await {
+        Ok(Some(model)) => model,
+        Ok(None) => return Err(BackendError::ResourceNotFoundError),
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let update_user = ActiveModel {
+        id: ActiveValue::Set(id),
+        locked_until: ActiveValue::Set(service_login_attempt::calculate_locked_until(
+            user.failed_attempts,
+            get_login_max_attempts(),
+        )),
+        ..Default::default()
+    };
+
+    if let Err(db_err) = tb_user::Entity::update(update_user)
+        .exec(&transaction)
+        .await
+    {
+        return Err(BackendError::DatabaseError(db_err));
+    }
+
+    match transaction.commit().await {
+        Ok(_) => Ok(()),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+async fn verify_ip_not_locked(
+    database: &DatabaseConnection,
+    client_ip: &Option<IpAddr>,
+) -> Result<(), BackendError> {
+    match client_ip {
+        Some(ip) => match service_login_attempt::is_ip_locked(database, ip).await {
+            Ok(true) => Err(BackendError::TooManyAttemptsError),
+            Ok(false) => Ok(()),
+            Err(backend_error) => Err(backend_error),
+        },
+        None => Ok(()),
+    }
+}
+
+async fn create_authentication(
+    database: &DatabaseConnection,
+    user: Model,
+) -> Result<AuthenticationDTO, BackendError> {
+    let role = match service_role::find_role_by_id(database, user.role).await {
+        Ok(role) => role,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let permissions = match service_role::find_permissions_by_user_id(database, user.id).await {
+        Ok(permissions) => permissions,
+        Err(backend_error) => return Err(backend_error),
+    };
+
+    let token = generate_token(user.email.clone());
+
+    Ok(AuthenticationDTO::new(
+        token.unwrap(),
+        user.role,
+        user.username,
+        role.name,
+        permissions,
+    ))
+}
+
+async fn find_by_challenge_token(
+    database: &DatabaseConnection,
+    challenge_token: &str,
+) -> Result<Model, BackendError> {
+    let email = match get_email_by_challenge_token(challenge_token) {
+        Some(email) => email,
+        None => return Err(BackendError::InvalidCredentialsError),
+    };
+
+    match find_by_email(database, &email).await {
+        Ok(user) => Ok(user),
+        Err(BackendError::ResourceNotFoundError) => Err(BackendError::InvalidCredentialsError),
+        Err(backend_error) => Err(backend_error),
+    }
+}
+
+async fn begin_two_factor_enrollment(
+    database: &DatabaseConnection,
+    user: &Model,
+) -> Result<TwoFactorEnrollmentDTO, BackendError> {
+    if user.totp_enabled {
+        return Err(BackendError::ResourceAlreadyInsertedError);
+    }
+
+    let totp_secret = generate_totp_secret();
+
+    let provisioning_uri = match get_provisioning_uri(&totp_secret, &user.email) {
+        Some(provisioning_uri) => provisioning_uri,
+        None => return Err(BackendError::InvalidValuesError),
+    };
+
+    let recovery_codes = generate_recovery_codes();
+
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let update_user = ActiveModel {
+        id: ActiveValue::Set(user.id),
+        totp_secret: ActiveValue::Set(Some(totp_secret.clone())),
+        totp_enabled: ActiveValue::Set(false),
+        totp_last_step: ActiveValue::Set(None),
+        ..Default::default()
+    };
+
+    if let Err(db_err) = tb_user::Entity::update(update_user)
+        .exec(&transaction)
+        .await
+    {
+        return Err(BackendError::DatabaseError(db_err));
+    }
+
+    let result = tb_user_recovery_code::Entity::delete_many()
+        .filter(tb_user_recovery_code::Column::UserId.eq(user.id))
+        .exec(&transaction)
+        .await;
+
+    if let Err(db_err) = result {
+        return Err(BackendError::DatabaseError(db_err));
+    }
+
+    let user_recovery_codes =
+        recovery_codes
+            .iter()
+            .map(|recovery_code| tb_user_recovery_code::ActiveModel {
+                user_id: ActiveValue::Set(user.id),
+                code_hash: ActiveValue::Set(hash_recovery_code(recovery_code)),
+                ..Default::default()
+            });
+
+    let result = tb_user_recovery_code::Entity::insert_many(user_recovery_codes)
+        .exec(&transaction)
+        .await;
+
+    if let Err(db_err) = result {
+        return Err(BackendError::DatabaseError(db_err));
+    }
+
+    match transaction.commit().await {
+        Ok(_) => Ok(TwoFactorEnrollmentDTO::new(
+            totp_secret,
+            provisioning_uri,
+            recovery_codes,
+        )),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+async fn enable_two_factor(
+    database: &DatabaseConnection,
+    user: &Model,
+) -> Result<(), BackendError> {
+    let update_user = ActiveModel {
+        id: ActiveValue::Set(user.id),
+        totp_enabled: ActiveValue::Set(true),
+        ..Default::default()
+    };
+
+    update_user_with_audit(
+        database,
+        &ActorDTO::new(Some(user.id), None),
+        user,
+        update_user,
+        AuditAction::TwoFactorEnable,
+    )
+    .await
+}
+
+async fn reset_login_failures(database: &DatabaseConnection, id: u64) -> Result<(), BackendError> {
+    let update_user = ActiveModel {
+        id: ActiveValue::Set(id),
+        failed_attempts: ActiveValue::Set(0),
+        locked_until: ActiveValue::Set(None),
+        ..Default::default()
+    };
+
+    let result = tb_user::Entity::update(update_user).exec(database).await;
 
     match result {
-        Ok(exists_opt) => match exists_opt {
-            Some(exists_dto) => Ok(exists_dto.get_into_exist()),
-            None => Err(DbErr::RecordNotInserted),
+        Ok(_) => Ok(()),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+async fn update_user_with_audit(
+    database: &DatabaseConnection,
+    actor: &ActorDTO,
+    old_user: &Model,
+    mut update_user: ActiveModel,
+    action: AuditAction,
+) -> Result<(), BackendError> {
+    update_user.updated_by = ActiveValue::Set(*actor.get_user_id());
+
+    let transaction = match database.begin().await {
+        Ok(transaction) => transaction,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let user = match update_user.update(&transaction).await {
+        Ok(model) => model,
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    service_audit::register_audit(
+        &transaction,
+        actor,
+        AuditEntity::User,
+        user.id,
+        action,
+        service_audit::snapshot(old_user),
+        service_audit::snapshot(&user),
+    )
+    .await?;
+
+    match transaction.commit().await {
+        Ok(_) => Ok(()),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+// Moves the last used step forward only if the code is newer, so each code is accepted once even
+// under concurrent logins
+async fn use_totp_step(
+    database: &DatabaseConnection,
+    user_id: u64,
+    step: u64,
+) -> Result<bool, BackendError> {
+    let stmt = Statement::from_sql_and_values(
+        DbBackend::MySql,
+        r#"
+            UPDATE tb_user
+            SET totp_last_step = ?
+            WHERE id = ?
+                AND (totp_last_step IS NULL OR totp_last_step < ?)
+        "#,
+        [step.into(), user_id.into(), step.into()],
+    );
+
+    match database.execute(stmt).await {
+        Ok(result) => Ok(result.rows_affected() == 1),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+async fn use_recovery_code(
+    database: &DatabaseConnection,
+    user_id: u64,
+    code: &str,
+) -> Result<bool, BackendError> {
+    let result = tb_user_recovery_code::Entity::find()
+        .filter(tb_user_recovery_code::Column::UserId.eq(user_id))
+        .filter(tb_user_recovery_code::Column::CodeHash.eq(hash_recovery_code(code)))
+        .filter(tb_user_recovery_code::Column::UsedAt.is_null())
+        .one(database)
+        .await;
+
+    let recovery_code = match result {
+        Ok(Some(model)) => model,
+        Ok(None) => return Ok(false),
+        Err(db_err) => return Err(BackendError::DatabaseError(db_err)),
+    };
+
+    let used_recovery_code = tb_user_recovery_code::ActiveModel {
+        id: ActiveValue::Set(recovery_code.id),
+        used_at: ActiveValue::Set(Some(Utc::now().naive_utc())),
+        ..Default::default()
+    };
+
+    let result = tb_user_recovery_code::Entity::update(used_recovery_code)
+        .exec(database)
+        .await;
+
+    match result {
+        Ok(_) => Ok(true),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
+    }
+}
+
+async fn find_by_id(database: &DatabaseConnection, id: u64) -> Result<Model, BackendError> {
+    let result = tb_user::Entity::find_by_id(id).one(database).await;
+
+    match result {
+        Ok(model_opt) => match model_opt {
+            Some(model) => Ok(model),
+            None => Err(BackendError::ResourceNotFoundError),
         },
-        Err(db_err) => Err(db_err),
+        Err(db_err) => Err(BackendError::DatabaseError(db_err)),
     }
 }
 
-pub async fn exists_by_id(database: &DatabaseConnection, id: &u64) -> Result<bool, DbErr> {
+async fn exists_by_email(database: &DatabaseConnection, email: &str) -> Result<bool, DbErr> {
     let stmt = Statement::from_string(
         DbBackend::MySql,
         format!(
@@ -270,7 +1221,7 @@ pub async fn exists_by_id(database: &DatabaseConnection, id: &u64) -> Result<boo
                 EXISTS(
                     SELECT 1
                     FROM tb_user
-                    WHERE tb_user.id = (\"{id}\")
+                    WHERE tb_user.email = (\"{email}\")
                 ) AS 'exist'
         "
         ),
@@ -289,9 +1240,9 @@ pub async fn exists_by_id(database: &DatabaseConnection, id: &u64) -> Result<boo
 
 fn create_update_active_model(
     user_update_dto: UserInformationsUpdateDTO,
-    logged_user: Model,
+    logged_user: &Model,
 ) -> ActiveModel {
-    let active_model = ActiveModel {
+    ActiveModel {
         id: ActiveValue::Set(logged_user.id),
         email: match user_update_dto.get_email().trim().is_empty() {
             true => ActiveValue::NotSet,
@@ -309,7 +1260,94 @@ fn create_update_active_model(
             None => ActiveValue::NotSet,
         },
         ..Default::default()
+    }
+}
+
+fn create_admin_update_active_model(
+    user_patch_dto: UserPatchDTO,
+    target_user: &Model,
+) -> ActiveModel {
+    ActiveModel {
+        id: ActiveValue::Set(target_user.id),
+        email: match user_patch_dto.get_email() {
+            Some(email) => ActiveValue::Set(email.clone()),
+            None => ActiveValue::NotSet,
+        },
+        username: match user_patch_dto.get_username() {
+            Some(username) => ActiveValue::Set(username.clone()),
+            None => ActiveValue::NotSet,
+        },
+        role: match user_patch_dto.get_role() {
+            Some(role) => ActiveValue::Set(*role),
+            None => ActiveValue::NotSet,
+        },
+        phone: match user_patch_dto.get_phone() {
+            Some(phone) => ActiveValue::Set(phone.clone()),
+            None => ActiveValue::NotSet,
+        },
+        ..Default::default()
+    }
+}
+
+async fn can_manage(
+    database: &DatabaseConnection,
+    logged_user: &Model,
+    target_user: &Model,
+) -> Result<bool, BackendError> {
+    if target_user.id == 1 {
+        return Ok(logged_user.id == 1);
+    }
+
+    let logged_permissions =
+        match service_role::find_permissions_by_user_id(database, logged_user.id).await {
+            Ok(permissions) => permissions,
+            Err(backend_error) => return Err(backend_error),
+        };
+
+    if logged_permissions
+        .iter()
+        .any(|name| name == RoleManage::CODE)
+    {
+        return Ok(true);
+    }
+
+    let target_permissions =
+        match service_role::find_permissions_by_user_id(database, target_user.id).await {
+            Ok(permissions) => permissions,
+            Err(backend_error) => return Err(backend_error),
+        };
+
+    Ok(target_permissions.len() < logged_permissions.len()
+        && target_permissions
+            .iter()
+            .all(|permission| logged_permissions.contains(permission)))
+}
+
+async fn can_grant_role(
+    database: &DatabaseConnection,
+    logged_user: &Model,
+    role_id: u8,
+) -> Result<bool, BackendError> {
+    if let Err(backend_error) = service_role::find_role_by_id(database, role_id).await {
+        return match backend_error {
+            BackendError::ResourceNotFoundError => Err(BackendError::InvalidValuesError),
+            backend_error => Err(backend_error),
+        };
+    }
+
+    let logged_permissions =
+        match service_role::find_permissions_by_user_id(database, logged_user.id).await {
+            Ok(permissions) => permissions,
+            Err(backend_error) => return Err(backend_error),
+        };
+
+    let role_permissions = match service_role::find_permissions_by_role_id(database, role_id).await
+    {
+        Ok(permissions) => permissions,
+        Err(backend_error) => return Err(backend_error),
     };
 
-    active_model
+    Ok(role_permissions
+        .iter()
+        .all(|permission| logged_permissions.contains(permission)))
 }